@@ -1,4 +1,4 @@
-
+use anchor_lang::prelude::*;
 
 /// Minimum time buffer for end dates (1 hour in seconds)
 pub const MIN_END_TIME_BUFFER: i64 = 3600;
@@ -6,14 +6,380 @@ pub const MIN_END_TIME_BUFFER: i64 = 3600;
 /// Basis points denominator (10000 = 100%)
 pub const BPS_DENOMINATOR: u64 = 10000;
 
+/// Minimum bond an arbiter must stake to register (0.1 SOL in lamports)
+pub const MIN_ARBITER_BOND: u64 = 100_000_000;
+
+/// Default window for submitting dispute evidence (7 days in seconds)
+pub const DEFAULT_EVIDENCE_WINDOW: i64 = 604800;
+
+/// Default window for the arbiter's decision after the evidence phase (7 days in seconds)
+pub const DEFAULT_DECISION_WINDOW: i64 = 604800;
+
+/// Default bond each party posts in a dispute (0.05 SOL in lamports)
+pub const DEFAULT_DISPUTE_BOND: u64 = 50_000_000;
+
+/// Default shortest dispute window an escrow may set (1 day in seconds)
+pub const DEFAULT_MIN_DISPUTE_WINDOW: i64 = 86400;
+
+/// Default longest dispute window an escrow may set (30 days in seconds)
+pub const DEFAULT_MAX_DISPUTE_WINDOW: i64 = 2592000;
+
+/// Maximum configurable dispute phase length (90 days in seconds)
+pub const MAX_DISPUTE_PHASE_WINDOW: i64 = 7776000;
+
+/// Maximum evidence commitments stored on a dispute
+pub const MAX_EVIDENCE_PER_DISPUTE: usize = 16;
+
+/// SPL Governance (Realms) program owning governance arbiter accounts
+pub const SPL_GOVERNANCE_PROGRAM_ID: Pubkey = pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+/// Maximum entries stored in a dispute's mediation log
+pub const MAX_MEDIATION_ENTRIES: usize = 32;
+
+/// Maximum number of assets in an escrow bundle
+pub const MAX_BUNDLE_ASSETS: usize = 8;
+
+/// Maximum number of allowlisted transfer hook programs
+pub const MAX_TRANSFER_HOOK_PROGRAMS: usize = 8;
+
+/// Maximum number of payment mints an escrow can accept
+pub const MAX_ACCEPTED_MINTS: usize = 4;
+
+/// Maximum number of allowlisted lending adapter programs
+pub const MAX_LENDING_ADAPTERS: usize = 4;
+
+/// Maximum number of allowlisted settlement hook programs
+pub const MAX_SETTLEMENT_HOOKS: usize = 4;
+
+/// Maximum number of allowlisted automation framework programs
+pub const MAX_AUTOMATION_PROGRAMS: usize = 4;
+
+/// Shortest interval a recurring automation trigger may fire at (1 hour)
+pub const MIN_AUTOMATION_INTERVAL: i64 = 3600;
+
+/// Maximum number of allowlisted swap router programs
+pub const MAX_SWAP_ROUTERS: usize = 4;
+
+/// Default slippage tolerance swaps at settlement may be quoted with (1%)
+pub const DEFAULT_MAX_SWAP_SLIPPAGE_BPS: u16 = 100;
+
+/// Default price impact swaps at settlement may have against the fair price (3%)
+pub const DEFAULT_MAX_SWAP_PRICE_IMPACT_BPS: u16 = 300;
+
+/// Maximum number of allowlisted liquid staking pools
+pub const MAX_STAKE_POOLS: usize = 4;
+
+/// Maximum number of arbiters on a panel
+pub const MAX_PANEL_SIZE: usize = 7;
+
+/// Maximum number of arbiters in the jury pool
+pub const MAX_ARBITER_POOL_SIZE: usize = 64;
+
+/// Number of jurors drawn for a dispute
+pub const JURY_SIZE: usize = 3;
+
+/// Lowest counterparty rating score
+pub const MIN_RATING_SCORE: u8 = 1;
+
+/// Highest counterparty rating score
+pub const MAX_RATING_SCORE: u8 = 5;
+
+/// Number of most recent rating scores kept on a rating aggregate
+pub const RECENT_RATINGS_LEN: usize = 10;
+
+/// Window for escalating an automated settlement to human arbitration (3 days in seconds)
+pub const ESCALATION_WINDOW: i64 = 259200;
+
+/// Window for appealing an arbitration award (3 days in seconds)
+pub const APPEAL_WINDOW: i64 = 259200;
+
+/// Bond posted to appeal an arbitration award (0.1 SOL in lamports)
+pub const APPEAL_BOND: u64 = 100_000_000;
+
+/// Longest a session key can stay valid (~1 day of slots)
+pub const MAX_SESSION_SLOTS: u64 = 216_000;
+
+/// Maximum number of escrows that can be chained upstream of an escrow
+pub const MAX_ESCROW_CHAIN_DEPTH: u8 = 4;
+
+/// Maximum number of co-signers on a party's release consent
+pub const MAX_CO_SIGNERS: usize = 8;
+
+/// Maximum number of observer keys on an escrow
+pub const MAX_OBSERVERS: usize = 4;
+
+/// Window a guardian can hold a large release for (2 days in seconds)
+pub const GUARDIAN_HOLD_WINDOW: i64 = 172800;
+
+/// Maximum number of currencies tracked by the TVL ledger
+pub const MAX_TVL_CURRENCIES: usize = 16;
+
+/// Length of a volume history bucket (1 day in seconds)
+pub const VOLUME_BUCKET_SECONDS: i64 = 86400;
+
+/// Number of daily buckets kept by a volume history
+pub const VOLUME_HISTORY_BUCKETS: usize = 30;
+
+/// Maximum number of receivers on a counterparty allowlist
+pub const MAX_ALLOWED_COUNTERPARTIES: usize = 32;
+
+/// Maximum number of recovery addresses a party can register
+pub const MAX_RECOVERY_ADDRESSES: usize = 3;
+
+/// Shortest delay before a proposed key recovery can be executed (1 day in seconds)
+pub const MIN_RECOVERY_DELAY: i64 = 86400;
+
+/// Longest delay before a proposed key recovery can be executed (30 days in seconds)
+pub const MAX_RECOVERY_DELAY: i64 = 2592000;
+
+/// Pyth oracle program owning the price accounts USD-denominated escrows are settled against
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Pyth receiver program owning the price update accounts of Pyth pull oracles
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Switchboard on-demand program owning the pull feeds USD-denominated escrows can be settled against
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// SPL stake pool program issuing the liquid staking tokens escrowed SOL can be staked into
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Native stake program, used by stake pools to withdraw SOL
+pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
+
+/// Jupiter aggregator program, the default router for swaps at settlement
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+/// SPL Memo program escrow memos are written with
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Address lookup table program owning the program's table of hot accounts
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey = pubkey!("AddressLookupTab1e1111111111111111111111111");
+
+/// Maximum number of addresses an address lookup table holds
+pub const MAX_LOOKUP_TABLE_ADDRESSES: usize = 256;
+
+/// Maximum number of addresses added to the lookup table in one extension, to fit a transaction
+pub const MAX_LOOKUP_TABLE_EXTENSION: usize = 20;
+
+/// Maximum number of escrows cranked in one batch, to fit a transaction's accounts and compute
+pub const MAX_CRANK_BATCH: usize = 20;
+
+/// Wormhole core bridge program owning the posted VAAs cross-chain release triggers are read from
+pub const WORMHOLE_PROGRAM_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+/// Solana Attestation Service program owning the attestations regulated escrows are gated on
+pub const SAS_PROGRAM_ID: Pubkey = pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+
+/// Decimals of USD-denominated escrow amounts (1_000_000 = $1)
+pub const USD_DECIMALS: u32 = 6;
+
+/// Decimals of native SOL amounts (lamports)
+pub const NATIVE_DECIMALS: u8 = 9;
+
+/// Oldest Pyth price accepted for a USD conversion (60 seconds)
+pub const MAX_PRICE_AGE: i64 = 60;
+
+/// Widest Pyth confidence interval accepted for a USD conversion, relative to the price (2%)
+pub const MAX_PRICE_CONFIDENCE_BPS: u64 = 200;
+
+/// Seconds in a year, for annualized interest rates
+pub const SECONDS_PER_YEAR: i64 = 31_536_000;
+
+/// Maximum annual interest rate on late invoice balances (bps)
+pub const MAX_INTEREST_RATE_BPS: u16 = 10000;
+
 /// Seeds for PDA derivation
 pub mod seeds {
     /// Asymmetric escrow PDA seed
     pub const ASYM_ESCROW: &[u8] = b"asym_escrow";
     
+    /// Symmetric escrow PDA seed
+    pub const SYM_ESCROW: &[u8] = b"sym_escrow";
+    
+    /// Symmetric escrow deposit vault PDA seed
+    pub const SYM_VAULT: &[u8] = b"sym_vault";
+    
     /// Program config PDA seed
     pub const PROGRAM_CONFIG: &[u8] = b"program_config";
     
     /// Escrow vault PDA seed
     pub const ESCROW_VAULT: &[u8] = b"escrow_vault";
+    
+    /// Dispute PDA seed
+    pub const DISPUTE: &[u8] = b"dispute";
+    
+    /// Arbiter registry PDA seed
+    pub const ARBITER: &[u8] = b"arbiter";
+    
+    /// Arbiter panel PDA seed
+    pub const ARBITER_PANEL: &[u8] = b"arbiter_panel";
+    
+    /// Arbitration vote PDA seed
+    pub const ARBITRATION_VOTE: &[u8] = b"arbitration_vote";
+    
+    /// Arbiter pool PDA seed
+    pub const ARBITER_POOL: &[u8] = b"arbiter_pool";
+    
+    /// Reputation PDA seed
+    pub const REPUTATION: &[u8] = b"reputation";
+    
+    /// Rating PDA seed
+    pub const RATING: &[u8] = b"rating";
+    
+    /// Per-address rating aggregate PDA seed
+    pub const RATING_AGGREGATE: &[u8] = b"rating_aggregate";
+    
+    /// Dispute fee token vault PDA seed
+    pub const DISPUTE_FEE_VAULT: &[u8] = b"dispute_fee_vault";
+    
+    /// Insurance fund PDA seed
+    pub const INSURANCE_FUND: &[u8] = b"insurance_fund";
+    
+    /// Mediation log PDA seed
+    pub const MEDIATION_LOG: &[u8] = b"mediation_log";
+    
+    /// NFT escrow token vault PDA seed
+    pub const NFT_VAULT: &[u8] = b"nft_vault";
+    
+    /// Escrow bundle PDA seed
+    pub const BUNDLE: &[u8] = b"bundle";
+    
+    /// Escrow bundle asset vault PDA seed
+    pub const BUNDLE_VAULT: &[u8] = b"bundle_vault";
+    
+    /// Staked escrow liquid staking token vault PDA seed
+    pub const LST_VAULT: &[u8] = b"lst_vault";
+    
+    /// Escrow receipt NFT mint PDA seed
+    pub const RECEIPT_MINT: &[u8] = b"receipt_mint";
+    
+    /// Counterparty allowlist PDA seed
+    pub const COUNTERPARTY_ALLOWLIST: &[u8] = b"counterparty_allowlist";
+    
+    /// Sanctions blocklist entry PDA seed
+    pub const BLOCKED_ADDRESS: &[u8] = b"blocked_address";
+    
+    /// Registered platform PDA seed
+    pub const PLATFORM: &[u8] = b"platform";
+    
+    /// Per-currency statistics PDA seed
+    pub const CURRENCY_STATS: &[u8] = b"currency_stats";
+    
+    /// Global TVL ledger PDA seed
+    pub const TVL_LEDGER: &[u8] = b"tvl_ledger";
+    
+    /// Per-address lifetime counters PDA seed
+    pub const USER_STATS: &[u8] = b"user_stats";
+    
+    /// Per-currency daily volume history PDA seed
+    pub const VOLUME_HISTORY: &[u8] = b"volume_history";
+    
+    /// Per-category counters PDA seed
+    pub const CATEGORY_STATS: &[u8] = b"category_stats";
+    
+    /// Admin action audit trail entry PDA seed
+    pub const ADMIN_AUDIT: &[u8] = b"admin_audit";
+    
+    /// Settlement hook authority PDA seed, signing calls into settlement hook programs
+    pub const SETTLEMENT_HOOK_AUTHORITY: &[u8] = b"settlement_hook_authority";
+    
+    /// Automation trigger PDA seed
+    pub const AUTOMATION_TRIGGER: &[u8] = b"automation_trigger";
+    
+    /// Partner authority PDA seed, derived under a partner marketplace program rather than this one
+    pub const PARTNER_AUTHORITY: &[u8] = b"escrow_partner";
+    
+    /// Lookup table authority PDA seed, owning the program's address lookup table
+    pub const LOOKUP_TABLE_AUTHORITY: &[u8] = b"lookup_table_authority";
+    
+    /// Event authority PDA seed, signing self-CPI events (fixed by Anchor's `#[event_cpi]`)
+    pub const EVENT_AUTHORITY: &[u8] = b"__event_authority";
+}
+
+/// Session key permission bits
+pub mod session_permissions {
+    /// Deposit payments
+    pub const DEPOSIT: u8 = 1 << 0;
+    
+    /// Consent to release
+    pub const RELEASE: u8 = 1 << 1;
+    
+    /// Refund the payer (receiver only)
+    pub const REFUND: u8 = 1 << 2;
+    
+    /// All permissions
+    pub const ALL: u8 = DEPOSIT | RELEASE | REFUND;
+}
+
+/// Observer key permission bits
+pub mod observer_permissions {
+    /// Snapshot escrow state into the return data
+    pub const SNAPSHOT: u8 = 1 << 0;
+    
+    /// Append audit notes
+    pub const AUDIT_NOTE: u8 = 1 << 1;
+    
+    /// All permissions
+    pub const ALL: u8 = SNAPSHOT | AUDIT_NOTE;
+}
+
+/// Actions a party can sign off-chain for a relayer to submit
+pub mod relay_actions {
+    /// Consent to release the remaining amount
+    pub const RELEASE: u8 = 1;
+    
+    /// Refund an amount to the payer (receiver only)
+    pub const REFUND: u8 = 2;
+    
+    /// Open a dispute
+    pub const OPEN_DISPUTE: u8 = 3;
+}
+
+/// Layout version of every event, its first field
+/// 
+/// Decoders branch on the version; fields added later take the place of reserved bytes, keeping the 
+/// layout of older fields stable.
+pub const EVENT_VERSION: u8 = 1;
+
+/// Reserved padding at the end of every event, for future fields
+pub const EVENT_RESERVED_BYTES: usize = 16;
+
+/// Actions reported by compact events, which carry a code in place of a dedicated event type
+pub mod compact_actions {
+    /// Funds deposited into an escrow
+    pub const DEPOSIT: u8 = 1;
+    
+    /// Deposit that fully funded the escrow
+    pub const DEPOSIT_FULLY_PAID: u8 = 2;
+}
+
+/// Prefix of every program log line, for log-based alerting
+pub const LOG_PREFIX: &str = "solescrow";
+
+/// Actions named in program log lines (stable; operators match on them)
+pub mod log_actions {
+    /// Escrow created
+    pub const CREATE: &str = "create";
+    
+    /// Funds deposited into an escrow
+    pub const DEPOSIT: &str = "deposit";
+    
+    /// Funds released to the receiver (or swapped, for symmetric escrows)
+    pub const RELEASE: &str = "release";
+    
+    /// Funds refunded to the payer
+    pub const REFUND: &str = "refund";
+    
+    /// Dispute opened
+    pub const DISPUTE: &str = "dispute";
+    
+    /// Arbitration award paid out
+    pub const AWARD: &str = "award";
+    
+    /// Escrow marked expired
+    pub const EXPIRE: &str = "expire";
+    
+    /// Transfer held back by the sanctions blocklist
+    pub const BLOCK: &str = "block";
 }
\ No newline at end of file
@@ -0,0 +1,509 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::TokenAccount;
+use crate::constants::{seeds, MEMO_PROGRAM_ID};
+use crate::cpi::accounts::{
+    CreateAsymEscrow, CreateEscrowViaPartnerAsym, PlacePaymentAsym, PlaceTokenPaymentAsym, RefundEscrowAsym,
+    ReleaseEscrowAsym, ReleaseTokenEscrowAsym,
+};
+use crate::errors::EscrowError;
+use crate::instructions::asym_escrow::CreateAsymEscrowParams;
+use crate::instructions::utils::payout_recipient;
+use crate::state::{AsymEscrow, EscrowCategory, Platform, ProgramConfig};
+
+/// Program config PDA
+pub fn program_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[ProgramConfig::SEED], &crate::ID).0
+}
+
+/// Asymmetric escrow PDA of a creator and nonce
+pub fn asym_escrow_address(creator: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::ASYM_ESCROW, creator.as_ref(), &nonce.to_le_bytes()], &crate::ID).0
+}
+
+/// Native SOL vault PDA of an escrow
+pub fn escrow_vault_address(escrow: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::ESCROW_VAULT, escrow.as_ref()], &crate::ID).0
+}
+
+/// Event authority PDA, signing the program's self-CPI events
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::EVENT_AUTHORITY], &crate::ID).0
+}
+
+/// Sanctions blocklist entry PDA of an address
+pub fn blocklist_entry_address(addr: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::BLOCKED_ADDRESS, addr.as_ref()], &crate::ID).0
+}
+
+/// Counterparty allowlist PDA of a payer
+pub fn counterparty_allowlist_address(payer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::COUNTERPARTY_ALLOWLIST, payer.as_ref()], &crate::ID).0
+}
+
+/// Statistics PDA of a currency (the default pubkey for native SOL)
+pub fn currency_stats_address(currency: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::CURRENCY_STATS, currency.as_ref()], &crate::ID).0
+}
+
+/// Counters PDA of an escrow category
+pub fn category_stats_address(category: EscrowCategory) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::CATEGORY_STATS, &[category as u8]], &crate::ID).0
+}
+
+/// Daily volume history PDA of a currency
+pub fn volume_history_address(currency: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::VOLUME_HISTORY, currency.as_ref()], &crate::ID).0
+}
+
+/// Global TVL ledger PDA
+pub fn tvl_ledger_address() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::TVL_LEDGER], &crate::ID).0
+}
+
+/// Insurance fund PDA
+pub fn insurance_fund_address() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::INSURANCE_FUND], &crate::ID).0
+}
+
+/// Lifetime counters PDA of an address
+pub fn user_stats_address(addr: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::USER_STATS, addr.as_ref()], &crate::ID).0
+}
+
+/// Platform registry PDA of a platform authority
+pub fn platform_address(platform_authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::PLATFORM, platform_authority.as_ref()], &crate::ID).0
+}
+
+/// Partner authority PDA a marketplace program signs `create_escrow_via_partner` with
+pub fn partner_authority_address(partner_program: &Pubkey) -> Pubkey {
+    Platform::partner_authority(partner_program)
+}
+
+/// PDA signing calls into settlement hook programs
+pub fn settlement_hook_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::SETTLEMENT_HOOK_AUTHORITY], &crate::ID).0
+}
+
+/// PDA owning the program's address lookup table
+pub fn lookup_table_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::LOOKUP_TABLE_AUTHORITY], &crate::ID).0
+}
+
+/// Token accounts of an SPL token escrow instruction
+pub struct EscrowTokenAccounts<'info> {
+    /// Token account of the party paying in or being paid out
+    pub party_token_account: AccountInfo<'info>,
+    /// Escrow's token account
+    pub escrow_token_account: AccountInfo<'info>,
+    /// Fee token account (releases only)
+    pub fee_token_account: Option<AccountInfo<'info>>,
+    /// Escrowed token mint
+    pub token_mint: AccountInfo<'info>,
+    /// Token program of the mint
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Builds `CpiContext`s for the core asymmetric escrow instructions
+///
+/// The caller passes the escrow program and the accounts it was given; the PDAs each instruction needs
+/// are derived and picked out of those accounts, so they only have to be present, not wired up by hand.
+/// Optional accounts for features an escrow doesn't use are left out, and can be set on the returned
+/// context's accounts.
+pub struct EscrowCpi<'a, 'info> {
+    program: AccountInfo<'info>,
+    accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> EscrowCpi<'a, 'info> {
+    /// Create a builder for the escrow program, finding derived accounts among `accounts`
+    pub fn new(program: AccountInfo<'info>, accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        require_keys_eq!(*program.key, crate::ID, ErrorCode::InvalidProgramId);
+        Ok(Self { program, accounts })
+    }
+
+    /// Context for `create_asym_escrow`
+    pub fn create_asym_escrow(
+        &self,
+        creator: AccountInfo<'info>,
+        params: &CreateAsymEscrowParams,
+        token_mint: Option<AccountInfo<'info>>,
+    ) -> Result<CpiContext<'_, '_, '_, 'info, CreateAsymEscrow<'info>>> {
+        let accounts = CreateAsymEscrow {
+            escrow: self.account(&asym_escrow_address(creator.key, params.nonce))?,
+            creator,
+            program_config: self.account(&program_config_address())?,
+            token_mint,
+            payer_allowlist: self.account(&counterparty_allowlist_address(&params.payer))?,
+            system_program: self.account(&System::id())?,
+            event_authority: self.account(&event_authority_address())?,
+            program: self.program.clone(),
+        };
+
+        Ok(CpiContext::new(self.program.clone(), accounts))
+    }
+
+    /// Context for `create_escrow_via_partner`
+    ///
+    /// The partner authority is the calling program's own partner authority PDA; sign the CPI with its seeds
+    /// (`[seeds::PARTNER_AUTHORITY, &[bump]]`).
+    pub fn create_escrow_via_partner(
+        &self,
+        partner_authority: AccountInfo<'info>,
+        rent_payer: AccountInfo<'info>,
+        params: &CreateAsymEscrowParams,
+        token_mint: Option<AccountInfo<'info>>,
+    ) -> Result<CpiContext<'_, '_, '_, 'info, CreateEscrowViaPartnerAsym<'info>>> {
+        let accounts = CreateEscrowViaPartnerAsym {
+            escrow: self.account(&asym_escrow_address(partner_authority.key, params.nonce))?,
+            platform: self.account(&platform_address(partner_authority.key))?,
+            partner_authority,
+            rent_payer,
+            program_config: self.account(&program_config_address())?,
+            token_mint,
+            payer_allowlist: self.account(&counterparty_allowlist_address(&params.payer))?,
+            system_program: self.account(&System::id())?,
+            event_authority: self.account(&event_authority_address())?,
+            program: self.program.clone(),
+        };
+
+        Ok(CpiContext::new(self.program.clone(), accounts))
+    }
+
+    /// Context for `place_payment_asym`, paying native SOL
+    pub fn place_payment_asym(
+        &self,
+        payer: AccountInfo<'info>,
+        escrow: AccountInfo<'info>,
+    ) -> Result<CpiContext<'_, '_, '_, 'info, PlacePaymentAsym<'info>>> {
+        let state = load_escrow(&escrow)?;
+
+        let accounts = PlacePaymentAsym {
+            payer,
+            program_config: self.account(&program_config_address())?,
+            escrow_vault: self.account(&escrow_vault_address(escrow.key))?,
+            price_feed: None,
+            payer_blocklist_entry: self.account(&blocklist_entry_address(&state.payer.addr))?,
+            receiver_blocklist_entry: self.account(&blocklist_entry_address(&state.receiver.addr))?,
+            currency_stats: self.account(&currency_stats_address(&state.payer.currency))?,
+            category_stats: self.account(&category_stats_address(state.category))?,
+            volume_history: self.account(&volume_history_address(&state.payer.currency))?,
+            tvl_ledger: self.account(&tvl_ledger_address())?,
+            memo_program: self.memo_program(&state)?,
+            system_program: self.account(&System::id())?,
+            event_authority: self.account(&event_authority_address())?,
+            program: self.program.clone(),
+            escrow,
+        };
+
+        Ok(CpiContext::new(self.program.clone(), accounts))
+    }
+
+    /// Context for `place_token_payment_asym`, paying in the escrow's mint
+    pub fn place_token_payment_asym(
+        &self,
+        payer: AccountInfo<'info>,
+        escrow: AccountInfo<'info>,
+        token_accounts: EscrowTokenAccounts<'info>,
+    ) -> Result<CpiContext<'_, '_, '_, 'info, PlaceTokenPaymentAsym<'info>>> {
+        let state = load_escrow(&escrow)?;
+        let mint = *token_accounts.token_mint.key;
+
+        let accounts = PlaceTokenPaymentAsym {
+            payer,
+            program_config: self.account(&program_config_address())?,
+            escrow_vault: self.account(&escrow_vault_address(escrow.key))?,
+            payer_token_account: token_accounts.party_token_account,
+            escrow_token_account: token_accounts.escrow_token_account,
+            token_mint: token_accounts.token_mint,
+            price_feed: None,
+            payer_blocklist_entry: self.account(&blocklist_entry_address(&state.payer.addr))?,
+            receiver_blocklist_entry: self.account(&blocklist_entry_address(&state.receiver.addr))?,
+            currency_stats: self.account(&currency_stats_address(&mint))?,
+            category_stats: self.account(&category_stats_address(state.category))?,
+            volume_history: self.account(&volume_history_address(&mint))?,
+            tvl_ledger: self.account(&tvl_ledger_address())?,
+            memo_program: self.memo_program(&state)?,
+            token_program: token_accounts.token_program,
+            associated_token_program: self.account(&anchor_spl::associated_token::ID)?,
+            system_program: self.account(&System::id())?,
+            event_authority: self.account(&event_authority_address())?,
+            program: self.program.clone(),
+            escrow,
+        };
+
+        Ok(CpiContext::new(self.program.clone(), accounts))
+    }
+
+    /// Context for `release_escrow_asym`, releasing a native SOL escrow
+    ///
+    /// Escrows with a tokenized claim pay the holder of `claim_token_account`.
+    pub fn release_escrow_asym(
+        &self,
+        signer: AccountInfo<'info>,
+        escrow: AccountInfo<'info>,
+        claim_token_account: Option<AccountInfo<'info>>,
+    ) -> Result<CpiContext<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>> {
+        let state = load_escrow(&escrow)?;
+        let program_config = self.account(&program_config_address())?;
+        let fee_vault = ProgramConfig::try_deserialize(&mut &program_config.try_borrow_data()?[..])?.fee_vault;
+
+        let receiver = self.account(&release_recipient(&state, claim_token_account.as_ref())?)?;
+        let (settlement_hook, settlement_hook_authority) = self.settlement_hook_accounts(&state)?;
+        let (receipt_mint, receipt_token_account, receipt_token_program) =
+            self.receipt_accounts(&state, claim_token_account.clone())?;
+        let chained_escrow = match state.is_chained() {
+            true => Some(self.account(&state.chained_escrow)?),
+            false => None,
+        };
+        let insurance_fund = match state.fee_terms.insurance_fee_bps > 0 {
+            true => Some(self.account(&insurance_fund_address())?),
+            false => None,
+        };
+
+        let accounts = ReleaseEscrowAsym {
+            signer,
+            program_config,
+            escrow_vault: self.account(&escrow_vault_address(escrow.key))?,
+            payout_blocklist_entry: self.account(&blocklist_entry_address(receiver.key))?,
+            receiver,
+            fee_vault: self.account(&fee_vault)?,
+            insurance_fund,
+            claim_token_account,
+            chained_escrow,
+            payer_reputation: None,
+            receiver_reputation: None,
+            receipt_mint,
+            receipt_token_account,
+            receipt_token_program,
+            price_feed: None,
+            posted_vaa: None,
+            sysvar_instructions: None,
+            payer_attestation: None,
+            receiver_attestation: None,
+            payer_blocklist_entry: self.account(&blocklist_entry_address(&state.payer.addr))?,
+            receiver_blocklist_entry: self.account(&blocklist_entry_address(&state.receiver.addr))?,
+            currency_stats: self.account(&currency_stats_address(&state.payer.currency))?,
+            category_stats: self.account(&category_stats_address(state.category))?,
+            volume_history: self.account(&volume_history_address(&state.payer.currency))?,
+            tvl_ledger: self.account(&tvl_ledger_address())?,
+            payer_stats: self.account(&user_stats_address(&state.payer.addr))?,
+            receiver_stats: self.account(&user_stats_address(&state.receiver.addr))?,
+            settlement_hook,
+            settlement_hook_authority,
+            settlement_hook_state: None,
+            memo_program: self.memo_program(&state)?,
+            system_program: self.account(&System::id())?,
+            event_authority: self.account(&event_authority_address())?,
+            program: self.program.clone(),
+            escrow,
+        };
+
+        Ok(CpiContext::new(self.program.clone(), accounts))
+    }
+
+    /// Context for `release_token_escrow_asym`, releasing an SPL token or NFT escrow
+    ///
+    /// Escrows with a tokenized claim pay the holder of `claim_token_account`.
+    pub fn release_token_escrow_asym(
+        &self,
+        signer: AccountInfo<'info>,
+        escrow: AccountInfo<'info>,
+        token_accounts: EscrowTokenAccounts<'info>,
+        claim_token_account: Option<AccountInfo<'info>>,
+    ) -> Result<CpiContext<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>> {
+        let state = load_escrow(&escrow)?;
+
+        let receiver = self.account(&release_recipient(&state, claim_token_account.as_ref())?)?;
+        let (settlement_hook, settlement_hook_authority) = self.settlement_hook_accounts(&state)?;
+        let (receipt_mint, receipt_token_account, receipt_token_program) =
+            self.receipt_accounts(&state, claim_token_account.clone())?;
+        let chained_escrow = match state.is_chained() {
+            true => Some(self.account(&state.chained_escrow)?),
+            false => None,
+        };
+
+        let accounts = ReleaseTokenEscrowAsym {
+            signer,
+            program_config: self.account(&program_config_address())?,
+            escrow_vault: self.account(&escrow_vault_address(escrow.key))?,
+            payout_blocklist_entry: self.account(&blocklist_entry_address(receiver.key))?,
+            receiver,
+            escrow_token_account: token_accounts.escrow_token_account,
+            receiver_token_account: token_accounts.party_token_account,
+            claim_token_account,
+            chained_escrow,
+            fee_token_account: token_accounts.fee_token_account,
+            payer_reputation: None,
+            receiver_reputation: None,
+            token_mint: token_accounts.token_mint,
+            receipt_mint,
+            receipt_token_account,
+            receipt_token_program,
+            price_feed: None,
+            posted_vaa: None,
+            sysvar_instructions: None,
+            payer_attestation: None,
+            receiver_attestation: None,
+            payer_blocklist_entry: self.account(&blocklist_entry_address(&state.payer.addr))?,
+            receiver_blocklist_entry: self.account(&blocklist_entry_address(&state.receiver.addr))?,
+            currency_stats: self.account(&currency_stats_address(&state.payer.currency))?,
+            category_stats: self.account(&category_stats_address(state.category))?,
+            volume_history: self.account(&volume_history_address(&state.payer.currency))?,
+            tvl_ledger: self.account(&tvl_ledger_address())?,
+            payer_stats: self.account(&user_stats_address(&state.payer.addr))?,
+            receiver_stats: self.account(&user_stats_address(&state.receiver.addr))?,
+            settlement_hook,
+            settlement_hook_authority,
+            settlement_hook_state: None,
+            memo_program: self.memo_program(&state)?,
+            token_program: token_accounts.token_program,
+            system_program: self.account(&System::id())?,
+            event_authority: self.account(&event_authority_address())?,
+            program: self.program.clone(),
+            escrow,
+        };
+
+        Ok(CpiContext::new(self.program.clone(), accounts))
+    }
+
+    /// Context for `refund_escrow_asym`
+    ///
+    /// Escrows with a tokenized claim burn the receipt held in `claim_token_account` if the refund completes them.
+    pub fn refund_escrow_asym(
+        &self,
+        signer: AccountInfo<'info>,
+        escrow: AccountInfo<'info>,
+        token_accounts: Option<EscrowTokenAccounts<'info>>,
+        claim_token_account: Option<AccountInfo<'info>>,
+    ) -> Result<CpiContext<'_, '_, '_, 'info, RefundEscrowAsym<'info>>> {
+        let state = load_escrow(&escrow)?;
+        let (party_token_account, escrow_token_account, token_mint, token_program) = split_token_accounts(token_accounts);
+        let (settlement_hook, settlement_hook_authority) = self.settlement_hook_accounts(&state)?;
+        let (receipt_mint, receipt_token_account, receipt_token_program) =
+            self.receipt_accounts(&state, claim_token_account)?;
+
+        let accounts = RefundEscrowAsym {
+            signer,
+            program_config: self.account(&program_config_address())?,
+            escrow_vault: self.account(&escrow_vault_address(escrow.key))?,
+            payer: self.account(&state.payer.addr)?,
+            escrow_token_account,
+            payer_token_account: party_token_account,
+            payer_reputation: None,
+            receiver_reputation: None,
+            token_mint,
+            receipt_mint,
+            receipt_token_account,
+            receipt_token_program,
+            sysvar_instructions: None,
+            currency_stats: self.account(&currency_stats_address(&state.payer.currency))?,
+            category_stats: self.account(&category_stats_address(state.category))?,
+            volume_history: self.account(&volume_history_address(&state.payer.currency))?,
+            tvl_ledger: self.account(&tvl_ledger_address())?,
+            payer_stats: self.account(&user_stats_address(&state.payer.addr))?,
+            receiver_stats: self.account(&user_stats_address(&state.receiver.addr))?,
+            settlement_hook,
+            settlement_hook_authority,
+            settlement_hook_state: None,
+            memo_program: self.memo_program(&state)?,
+            token_program,
+            system_program: self.account(&System::id())?,
+            event_authority: self.account(&event_authority_address())?,
+            program: self.program.clone(),
+            escrow,
+        };
+
+        Ok(CpiContext::new(self.program.clone(), accounts))
+    }
+
+    //find the memo program, if the escrow writes memos
+    fn memo_program(&self, state: &AsymEscrow) -> Result<Option<AccountInfo<'info>>> {
+        match state.memo_enabled {
+            true => Ok(Some(self.account(&MEMO_PROGRAM_ID)?)),
+            false => Ok(None),
+        }
+    }
+
+    //find the settlement hook program and its signing authority, if the escrow calls a hook
+    fn settlement_hook_accounts(&self, state: &AsymEscrow) -> Result<(Option<AccountInfo<'info>>, Option<AccountInfo<'info>>)> {
+        if !state.has_settlement_hook() {
+            return Ok((None, None));
+        }
+        Ok((
+            Some(self.account(&state.settlement_hook)?),
+            Some(self.account(&settlement_hook_authority_address())?),
+        ))
+    }
+
+    //find the receipt mint, the account holding the receipt and the Token-2022 program, if the escrow has a 
+    //receipt; a tokenized claim is held in the claim token account, any other receipt by the receiver
+    #[allow(clippy::type_complexity)]
+    fn receipt_accounts(
+        &self,
+        state: &AsymEscrow,
+        claim_token_account: Option<AccountInfo<'info>>,
+    ) -> Result<(Option<AccountInfo<'info>>, Option<AccountInfo<'info>>, Option<AccountInfo<'info>>)> {
+        if !state.has_receipt() {
+            return Ok((None, None, None));
+        }
+        let receipt_token_account = match claim_token_account {
+            Some(claim) if state.is_claim_tokenized() => claim,
+            _ => self.account(&get_associated_token_address_with_program_id(
+                &state.receiver.addr,
+                &state.receipt_mint,
+                &Token2022::id(),
+            ))?,
+        };
+        Ok((
+            Some(self.account(&state.receipt_mint)?),
+            Some(receipt_token_account),
+            Some(self.account(&Token2022::id())?),
+        ))
+    }
+
+    //find an account among the caller's accounts by address
+    fn account(&self, address: &Pubkey) -> Result<AccountInfo<'info>> {
+        self.accounts
+            .iter()
+            .find(|account| account.key == address)
+            .cloned()
+            .ok_or_else(|| error!(EscrowError::MissingCpiAccount))
+    }
+}
+
+//deserialize the escrow an instruction is for, to derive its PDAs
+fn load_escrow(escrow: &AccountInfo) -> Result<AsymEscrow> {
+    require_keys_eq!(*escrow.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    AsymEscrow::try_deserialize(&mut &escrow.try_borrow_data()?[..])
+}
+
+//find who a release pays: the holder of a tokenized claim, or else the escrow's payout recipient
+fn release_recipient(state: &AsymEscrow, claim_token_account: Option<&AccountInfo>) -> Result<Pubkey> {
+    match claim_token_account {
+        Some(claim) if state.is_claim_tokenized() => {
+            let claim = TokenAccount::try_deserialize(&mut &claim.try_borrow_data()?[..])?;
+            require!(claim.mint == state.receipt_mint && claim.amount == 1, EscrowError::InvalidReceiver);
+            Ok(claim.owner)
+        },
+        _ => Ok(payout_recipient(state, None)),
+    }
+}
+
+//split optional token accounts into the optional context fields
+#[allow(clippy::type_complexity)]
+fn split_token_accounts<'info>(
+    token_accounts: Option<EscrowTokenAccounts<'info>>,
+) -> (
+    Option<AccountInfo<'info>>,
+    Option<AccountInfo<'info>>,
+    Option<AccountInfo<'info>>,
+    Option<AccountInfo<'info>>,
+) {
+    match token_accounts {
+        Some(t) => (Some(t.party_token_account), Some(t.escrow_token_account), Some(t.token_mint), Some(t.token_program)),
+        None => (None, None, None, None),
+    }
+}
\ No newline at end of file
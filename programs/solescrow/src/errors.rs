@@ -49,4 +49,275 @@ pub enum EscrowError {
     
     #[msg("Already released")]
     AlreadyReleased,
+    
+    #[msg("Invalid arbiter")]
+    InvalidArbiter,
+    
+    #[msg("Invalid arbitration fee")]
+    InvalidArbitrationFee,
+    
+    #[msg("Dispute already resolved")]
+    DisputeResolved,
+    
+    #[msg("Insufficient arbiter bond")]
+    InsufficientBond,
+    
+    #[msg("Award already appealed")]
+    AlreadyAppealed,
+    
+    #[msg("Appeal window closed")]
+    AppealWindowClosed,
+    
+    #[msg("Award not final")]
+    AwardNotFinal,
+    
+    #[msg("Evidence window closed")]
+    EvidenceWindowClosed,
+    
+    #[msg("Evidence limit reached")]
+    EvidenceLimitReached,
+    
+    #[msg("Decision deadline passed")]
+    DecisionDeadlinePassed,
+    
+    #[msg("Decision deadline not reached")]
+    DecisionDeadlineNotReached,
+    
+    #[msg("Invalid config")]
+    InvalidConfig,
+    
+    #[msg("Invalid arbiter panel")]
+    InvalidPanel,
+    
+    #[msg("Arbiter pool is full")]
+    ArbiterPoolFull,
+    
+    #[msg("Not enough arbiters in pool")]
+    InsufficientJurors,
+    
+    #[msg("Dispute already answered")]
+    DisputeAlreadyAnswered,
+    
+    #[msg("Invalid rating")]
+    InvalidRating,
+    
+    #[msg("Escalation window has closed")]
+    EscalationWindowClosed,
+    
+    #[msg("Mediation log is full")]
+    MediationLogFull,
+    
+    #[msg("Invalid dispute status transition")]
+    InvalidDisputeTransition,
+    
+    #[msg("Arbitration fee already deposited")]
+    FeeAlreadyDeposited,
+    
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Invalid dispute window")]
+    InvalidDisputeWindow,
+    
+    #[msg("Transfer hook program is not allowlisted")]
+    TransferHookNotAllowed,
+    
+    #[msg("Missing transfer hook accounts")]
+    MissingTransferHookAccounts,
+    
+    #[msg("Amount does not match the escrow's amount commitment")]
+    AmountCommitmentMismatch,
+    
+    #[msg("Amount already disclosed")]
+    AmountAlreadyDisclosed,
+    
+    #[msg("Invalid NFT")]
+    InvalidNft,
+    
+    #[msg("NFT is not a verified member of the required collection")]
+    InvalidNftCollection,
+    
+    #[msg("Missing programmable NFT accounts")]
+    MissingProgrammableNftAccounts,
+    
+    #[msg("Invalid bundle")]
+    InvalidBundle,
+    
+    #[msg("Bundle asset already deposited")]
+    BundleAssetAlreadyDeposited,
+    
+    #[msg("Bundle already settled")]
+    BundleAlreadySettled,
+    
+    #[msg("Escrow has not expired")]
+    EscrowNotExpired,
+    
+    #[msg("Already deposited")]
+    AlreadyDeposited,
+    
+    #[msg("Invalid price feed")]
+    InvalidPriceFeed,
+    
+    #[msg("Price is stale")]
+    StalePrice,
+    
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    
+    #[msg("Invalid price collar")]
+    InvalidPriceCollar,
+    
+    #[msg("Lending adapter is not allowlisted")]
+    LendingAdapterNotAllowed,
+    
+    #[msg("Escrowed funds are lent out")]
+    FundsLent,
+    
+    #[msg("Stake pool is not allowlisted")]
+    StakePoolNotAllowed,
+    
+    #[msg("Escrowed funds are staked")]
+    FundsStaked,
+    
+    #[msg("Mint decimals do not match the escrow")]
+    InvalidDecimals,
+    
+    #[msg("Invalid interest terms")]
+    InvalidInterestTerms,
+    
+    #[msg("Invalid hashlock")]
+    InvalidHashlock,
+    
+    #[msg("Invalid timelock")]
+    InvalidTimelock,
+    
+    #[msg("Preimage does not match the hashlock")]
+    InvalidPreimage,
+    
+    #[msg("HTLC timelock has expired")]
+    TimelockExpired,
+    
+    #[msg("HTLC timelock has not expired")]
+    TimelockNotExpired,
+    
+    #[msg("Invalid Wormhole VAA")]
+    InvalidVaa,
+    
+    #[msg("Escrow receipt already minted")]
+    ReceiptAlreadyMinted,
+    
+    #[msg("Invalid escrow chain")]
+    InvalidEscrowChain,
+    
+    #[msg("Escrow chain is too deep")]
+    EscrowChainTooDeep,
+    
+    #[msg("Invalid delegate")]
+    InvalidDelegate,
+    
+    #[msg("Invalid session key")]
+    InvalidSessionKey,
+    
+    #[msg("Invalid co-signers")]
+    InvalidCoSigners,
+    
+    #[msg("Invalid relayed intent")]
+    InvalidRelayedIntent,
+    
+    #[msg("Invalid recovery")]
+    InvalidRecovery,
+    
+    #[msg("Recovery delay has not elapsed")]
+    RecoveryDelayNotElapsed,
+    
+    #[msg("Counterparty not on the payer's allowlist")]
+    CounterpartyNotAllowed,
+    
+    #[msg("Invalid counterparty allowlist")]
+    InvalidAllowlist,
+    
+    #[msg("Missing or invalid attestation")]
+    InvalidAttestation,
+    
+    #[msg("Invalid guardian")]
+    InvalidGuardian,
+    
+    #[msg("Release is held by the guardian")]
+    ReleaseOnHold,
+    
+    #[msg("Invalid platform")]
+    InvalidPlatform,
+    
+    #[msg("Invalid observer")]
+    InvalidObserver,
+    
+    #[msg("Native SOL must come from a system-owned account without data")]
+    InvalidFundingAccount,
+    
+    #[msg("Invalid TVL ledger currency")]
+    InvalidTvlCurrency,
+    
+    #[msg("Escrow vault holds less than the escrow's locked funds")]
+    TvlInvariantViolated,
+    
+    #[msg("Account required by the escrow instruction was not provided")]
+    MissingCpiAccount,
+    
+    #[msg("Settlement hook program is not allowlisted or was not provided")]
+    SettlementHookNotAllowed,
+    
+    #[msg("Automation program is not allowlisted")]
+    AutomationProgramNotAllowed,
+    
+    #[msg("Invalid automation trigger schedule")]
+    InvalidAutomationSchedule,
+    
+    #[msg("Escrow writes memos but the memo program was not provided")]
+    MemoProgramRequired,
+    
+    #[msg("Client reference must be UTF-8")]
+    InvalidClientRef,
+    
+    #[msg("Invalid Realms governance")]
+    InvalidGovernance,
+    
+    #[msg("Invalid address lookup table")]
+    InvalidLookupTable,
+    
+    #[msg("Escrow is locked by an external call in progress")]
+    ReentrantCall,
+    
+    #[msg("Crank batch must list between one and the maximum number of writable escrows")]
+    InvalidCrankBatch,
+    
+    #[msg("Garbage collected accounts must be finished accounts of the escrow, each followed by its rent payer")]
+    InvalidCleanupAccount,
+    
+    #[msg("Transfer involves an address on the sanctions blocklist")]
+    AddressBlocked,
+}
+
+/// Errors of the swap adapter, kept apart from the escrow errors so routers' failures are easy to tell apart
+#[error_code(offset = 7000)]
+pub enum SwapError {
+    #[msg("Swap router is not allowlisted")]
+    RouterNotAllowed,
+    
+    #[msg("Swap quote is invalid")]
+    InvalidQuote,
+    
+    #[msg("Swap slippage tolerance exceeds the protocol limit")]
+    SlippageTooHigh,
+    
+    #[msg("Swap returned less than the minimum output")]
+    SlippageExceeded,
+    
+    #[msg("Swap price impact exceeds the protocol limit")]
+    PriceImpactExceeded,
+    
+    #[msg("Swap spent more than its input amount")]
+    InputExceeded,
+    
+    #[msg("Swap token accounts must be distinct mints held by the escrow vault")]
+    InvalidSwapAccounts,
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+#[allow(deprecated)]
+use anchor_lang::solana_program::keccak;
+use crate::instructions::asym_escrow::{
+    EscrowCreatedEvent, EscrowRefundedEvent, EscrowReleasedEvent, PaymentReceivedEvent, ReleaseAssentGivenEvent,
+};
+
+/// Event in the log layout of the EVM escrow contract
+///
+/// Topic 0 is the keccak hash of the event signature, indexed parameters follow as topics, and the other
+/// parameters are ABI-encoded into the data, so a multichain indexer can decode both deployments alike.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvmLog {
+    /// Signature hash, then indexed parameters
+    pub topics: Vec<[u8; 32]>,
+    /// ABI-encoded non-indexed parameters
+    pub data: Vec<u8>,
+}
+
+/// Program event with a counterpart in the EVM escrow contract
+///
+/// Escrow ids and amounts carry over as they are; amounts stay in base units of the escrow currency. Solana
+/// addresses take the whole 32-byte word where EVM addresses are left-padded, so indexers should read
+/// `address` parameters as `bytes32`.
+pub trait EvmEvent {
+    /// Canonical Solidity signature of the counterpart event, as hashed into topic 0
+    const SIGNATURE: &'static str;
+
+    /// Indexed parameters, in order
+    fn indexed(&self) -> Vec<[u8; 32]>;
+
+    /// Non-indexed parameters as ABI words, in order
+    fn words(&self) -> Vec<[u8; 32]>;
+
+    /// Map the event onto the EVM log layout
+    fn to_evm_log(&self) -> EvmLog {
+        let mut topics = vec![evm_topic(Self::SIGNATURE)];
+        topics.extend(self.indexed());
+        EvmLog {
+            topics,
+            data: self.words().concat(),
+        }
+    }
+}
+
+/// Topic 0 of an event with the given canonical signature
+pub fn evm_topic(signature: &str) -> [u8; 32] {
+    #[allow(deprecated)]
+    keccak::hash(signature.as_bytes()).to_bytes()
+}
+
+//helper function to encode an unsigned integer as a uint256 ABI word
+fn uint_word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// `EscrowCreated(bytes32 indexed escrowId, address indexed payer, address indexed receiver, address currency,
+/// uint256 amount)`
+impl EvmEvent for EscrowCreatedEvent {
+    const SIGNATURE: &'static str = "EscrowCreated(bytes32,address,address,address,uint256)";
+
+    fn indexed(&self) -> Vec<[u8; 32]> {
+        vec![self.escrow_id, self.payer.to_bytes(), self.receiver.to_bytes()]
+    }
+
+    fn words(&self) -> Vec<[u8; 32]> {
+        vec![self.currency.to_bytes(), uint_word(self.amount)]
+    }
+}
+
+/// `PaymentReceived(bytes32 indexed escrowId, address indexed from, uint256 amount, uint256 totalPaid)`
+impl EvmEvent for PaymentReceivedEvent {
+    const SIGNATURE: &'static str = "PaymentReceived(bytes32,address,uint256,uint256)";
+
+    fn indexed(&self) -> Vec<[u8; 32]> {
+        vec![self.escrow_id, self.payer.to_bytes()]
+    }
+
+    fn words(&self) -> Vec<[u8; 32]> {
+        vec![uint_word(self.amount), uint_word(self.total_paid)]
+    }
+}
+
+/// `ReleaseAssentGiven(bytes32 indexed escrowId, address indexed assentingAddress, uint8 assentType)`
+impl EvmEvent for ReleaseAssentGivenEvent {
+    const SIGNATURE: &'static str = "ReleaseAssentGiven(bytes32,address,uint8)";
+
+    fn indexed(&self) -> Vec<[u8; 32]> {
+        vec![self.escrow_id, self.assenting_address.to_bytes()]
+    }
+
+    fn words(&self) -> Vec<[u8; 32]> {
+        vec![uint_word(self.assent_type as u64)]
+    }
+}
+
+/// `EscrowReleased(bytes32 indexed escrowId, uint256 amount, uint256 fee)`
+impl EvmEvent for EscrowReleasedEvent {
+    const SIGNATURE: &'static str = "EscrowReleased(bytes32,uint256,uint256)";
+
+    fn indexed(&self) -> Vec<[u8; 32]> {
+        vec![self.escrow_id]
+    }
+
+    fn words(&self) -> Vec<[u8; 32]> {
+        vec![uint_word(self.amount), uint_word(self.fee)]
+    }
+}
+
+/// `EscrowRefunded(bytes32 indexed escrowId, uint256 amount)`
+impl EvmEvent for EscrowRefundedEvent {
+    const SIGNATURE: &'static str = "EscrowRefunded(bytes32,uint256)";
+
+    fn indexed(&self) -> Vec<[u8; 32]> {
+        vec![self.escrow_id]
+    }
+
+    fn words(&self) -> Vec<[u8; 32]> {
+        vec![uint_word(self.amount)]
+    }
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+/// Opt an address into a counterparty allowlist, restricting the receivers it can enter escrows with
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeCounterpartyAllowlist<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = CounterpartyAllowlist::space(),
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, owner.key().as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, CounterpartyAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_counterparty_allowlist(
+    ctx: Context<InitializeCounterpartyAllowlist>,
+    counterparties: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        counterparties.len() <= MAX_ALLOWED_COUNTERPARTIES,
+        EscrowError::InvalidAllowlist
+    );
+
+    let allowlist = &mut ctx.accounts.allowlist;
+    allowlist.owner = ctx.accounts.owner.key();
+    allowlist.counterparties = counterparties;
+    allowlist.bump = ctx.bumps.allowlist;
+
+    emit_cpi!(CounterpartyAllowlistUpdatedEvent {
+        version: EVENT_VERSION,
+        owner: allowlist.owner,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        counterparties: allowlist.counterparties.clone(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Replace the receivers on an address's counterparty allowlist (owner only)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateCounterpartyAllowlist<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ EscrowError::Unauthorized,
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, owner.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, CounterpartyAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn update_counterparty_allowlist(
+    ctx: Context<UpdateCounterpartyAllowlist>,
+    counterparties: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        counterparties.len() <= MAX_ALLOWED_COUNTERPARTIES,
+        EscrowError::InvalidAllowlist
+    );
+
+    let allowlist = &mut ctx.accounts.allowlist;
+    allowlist.counterparties = counterparties;
+
+    emit_cpi!(CounterpartyAllowlistUpdatedEvent {
+        version: EVENT_VERSION,
+        owner: allowlist.owner,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        counterparties: allowlist.counterparties.clone(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Close an address's counterparty allowlist, lifting the restriction (owner only)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseCounterpartyAllowlist<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ EscrowError::Unauthorized,
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, owner.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, CounterpartyAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn close_counterparty_allowlist(ctx: Context<CloseCounterpartyAllowlist>) -> Result<()> {
+    emit_cpi!(CounterpartyAllowlistClosedEvent {
+        version: EVENT_VERSION,
+        owner: ctx.accounts.owner.key(),
+        seq: ctx.accounts.program_config.next_event_seq(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Check a receiver against the payer's counterparty allowlist, if the payer has opted into one
+/// 
+/// The allowlist account is always passed at its PDA address; an address that never created it (or 
+/// closed it) leaves the account empty and unrestricted.
+pub fn check_counterparty_allowlist(allowlist: &UncheckedAccount, receiver: &Pubkey) -> Result<()> {
+    if allowlist.owner != &crate::ID || allowlist.data_is_empty() {
+        return Ok(());
+    }
+
+    let data = allowlist.try_borrow_data()?;
+    let allowlist = CounterpartyAllowlist::try_deserialize(&mut &data[..])?;
+    require!(allowlist.allows(receiver), EscrowError::CounterpartyNotAllowed);
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct CounterpartyAllowlistUpdatedEvent {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub seq: u64,
+    pub counterparties: Vec<Pubkey>,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct CounterpartyAllowlistClosedEvent {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub seq: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,276 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+use crate::instructions::insurance::InsuranceFundDepositEvent;
+
+/// Initialize the arbiter pool used for jury selection
+#[derive(Accounts)]
+pub struct InitializeArbiterPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ArbiterPool::space(),
+        seeds = [seeds::ARBITER_POOL],
+        bump
+    )]
+    pub arbiter_pool: Account<'info, ArbiterPool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_arbiter_pool(ctx: Context<InitializeArbiterPool>) -> Result<()> {
+    let arbiter_pool = &mut ctx.accounts.arbiter_pool;
+    arbiter_pool.entries = Vec::new();
+    arbiter_pool.bump = ctx.bumps.arbiter_pool;
+
+    Ok(())
+}
+
+/// Register an arbiter and stake their bond
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterArbiter<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        init,
+        payer = arbiter,
+        space = ArbiterAccount::space(),
+        seeds = [seeds::ARBITER, arbiter.key().as_ref()],
+        bump
+    )]
+    pub arbiter_account: Account<'info, ArbiterAccount>,
+
+    #[account(
+        mut,
+        seeds = [seeds::ARBITER_POOL],
+        bump = arbiter_pool.bump
+    )]
+    pub arbiter_pool: Account<'info, ArbiterPool>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_arbiter(ctx: Context<RegisterArbiter>, bond: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    require!(bond >= MIN_ARBITER_BOND, EscrowError::InsufficientBond);
+
+    //stake bond on the arbiter account
+    transfer_native_sol(
+        ctx.accounts.arbiter.to_account_info(),
+        ctx.accounts.arbiter_account.to_account_info(),
+        bond,
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let arbiter_account = &mut ctx.accounts.arbiter_account;
+    arbiter_account.arbiter = ctx.accounts.arbiter.key();
+    arbiter_account.bond = bond;
+    arbiter_account.slashed = 0;
+    arbiter_account.registered_at = Clock::get()?.unix_timestamp;
+    arbiter_account.bump = ctx.bumps.arbiter_account;
+
+    //add to the jury pool
+    let arbiter_pool = &mut ctx.accounts.arbiter_pool;
+    require!(
+        arbiter_pool.entries.len() < MAX_ARBITER_POOL_SIZE,
+        EscrowError::ArbiterPoolFull
+    );
+    arbiter_pool.entries.push(ArbiterPoolEntry {
+        arbiter: arbiter_account.arbiter,
+        stake: bond,
+    });
+
+    emit_cpi!(ArbiterRegisteredEvent {
+        version: EVENT_VERSION,
+        arbiter: arbiter_account.arbiter,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        bond,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Slash an arbiter's bond for a fraudulent award
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SlashArbiter<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    #[account(
+        mut,
+        seeds = [seeds::ARBITER, arbiter_account.arbiter.as_ref()],
+        bump = arbiter_account.bump
+    )]
+    pub arbiter_account: Account<'info, ArbiterAccount>,
+
+    #[account(
+        mut,
+        seeds = [seeds::ARBITER_POOL],
+        bump = arbiter_pool.bump
+    )]
+    pub arbiter_pool: Account<'info, ArbiterPool>,
+
+    /// Awarded dispute the slashing is for
+    #[account(
+        constraint = dispute.status == DisputeStatus::Resolved @ EscrowError::InvalidEscrowState,
+        constraint = dispute.arbiter == arbiter_account.arbiter @ EscrowError::InvalidArbiter,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: recipient of the slashed bond (the wronged party); may be a program-owned multisig vault
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Insurance fund, receives the insurance share of the slashed bond
+    #[account(
+        mut,
+        seeds = [seeds::INSURANCE_FUND],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn slash_arbiter(ctx: Context<SlashArbiter>, amount: u64, insurance_amount: u64) -> Result<()> {
+    let arbiter_account = &mut ctx.accounts.arbiter_account;
+
+    //validate amount
+    require!(amount > 0, EscrowError::InvalidAmount);
+    require!(amount <= arbiter_account.bond, EscrowError::InsufficientBond);
+    require!(insurance_amount <= amount, EscrowError::AmountExceeded);
+
+    //move the insurance share of the slashed bond to the insurance fund
+    if insurance_amount > 0 {
+        let insurance_fund = ctx.accounts.insurance_fund
+            .as_mut()
+            .ok_or(EscrowError::InvalidConfig)?;
+        transfer_lamports_from_program_account(
+            &arbiter_account.to_account_info(),
+            &insurance_fund.to_account_info(),
+            insurance_amount,
+        )?;
+        insurance_fund.record_deposit(insurance_amount).ok_or(EscrowError::ArithmeticOverflow)?;
+
+        emit_cpi!(InsuranceFundDepositEvent {
+            version: EVENT_VERSION,
+            source: arbiter_account.key(),
+            seq: ctx.accounts.program_config.next_event_seq(),
+            amount: insurance_amount,
+            balance: insurance_fund.balance,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+
+    //move the rest of the slashed bond to the destination
+    let destination_amount = amount - insurance_amount;
+    if destination_amount > 0 {
+        transfer_lamports_from_program_account(
+            &arbiter_account.to_account_info(),
+            &ctx.accounts.destination.to_account_info(),
+            destination_amount,
+        )?;
+    }
+
+    arbiter_account.bond -= amount;
+    arbiter_account.slashed = arbiter_account.slashed
+        .checked_add(amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+
+    //reduce jury selection weight
+    if let Some(entry) = ctx.accounts.arbiter_pool.entry_mut(&arbiter_account.arbiter) {
+        entry.stake = arbiter_account.bond;
+    }
+
+    emit_cpi!(ArbiterSlashedEvent {
+        version: EVENT_VERSION,
+        arbiter: arbiter_account.arbiter,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        dispute: ctx.accounts.dispute.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        insurance_amount,
+        remaining_bond: arbiter_account.bond,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        &mut ctx.accounts.program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::SlashArbiter,
+        &[
+            arbiter_account.arbiter.as_ref(),
+            ctx.accounts.dispute.key().as_ref(),
+            ctx.accounts.destination.key().as_ref(),
+            &amount.to_le_bytes(),
+            &insurance_amount.to_le_bytes(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct ArbiterRegisteredEvent {
+    pub version: u8,
+    pub arbiter: Pubkey,
+    pub seq: u64,
+    pub bond: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ArbiterSlashedEvent {
+    pub version: u8,
+    pub arbiter: Pubkey,
+    pub seq: u64,
+    pub dispute: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub insurance_amount: u64,
+    pub remaining_bond: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
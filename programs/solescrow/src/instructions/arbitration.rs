@@ -0,0 +1,2446 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_2022::Token2022;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+use crate::instructions::insurance::InsuranceFundDepositEvent;
+use crate::instructions::asym_escrow::EscrowStatusChangedEvent;
+use crate::instructions::chaining::credit_chained_escrow;
+use crate::instructions::relay::verify_relayed_intent;
+use crate::instructions::attestation::verify_party_attestations;
+use crate::instructions::blocklist::{require_not_blocked, BlockedTransfer};
+use crate::instructions::stats::{
+    update_category_stats, update_currency_stats, update_tvl_ledger, update_user_stats, update_volume_history,
+};
+use crate::instructions::expiry::mark_expired_asym;
+use crate::instructions::receipt::{burn_settled_receipt, SettlementReceiptAccounts};
+use crate::instructions::settlement_hook::{invoke_settlement_hook, SettlementOutcome};
+
+/// Open a dispute on an asymmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenDisputeAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Active
+            || escrow.status == EscrowStatus::Expired @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Dispute::space(),
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: instructions sysvar (only required for relayed intents), read by verify_relayed_intent
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_dispute(ctx: Context<OpenDisputeAsym>) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    open_dispute_for(ctx, signer)
+}
+
+/// Open a dispute for a party from an intent it signed off-chain, submitted by any relayer
+/// 
+/// The relayer pays the dispute account's rent and posts the dispute bond.
+pub fn relay_open_dispute(ctx: Context<OpenDisputeAsym>) -> Result<()> {
+    let escrow_key = ctx.accounts.escrow.key();
+    let party = verify_relayed_intent(
+        ctx.accounts.sysvar_instructions.as_ref(),
+        &escrow_key,
+        &mut ctx.accounts.escrow,
+        relay_actions::OPEN_DISPUTE,
+        0,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    open_dispute_for(ctx, party)
+}
+
+//open a dispute on behalf of a party, with the signer posting the bond
+fn open_dispute_for(ctx: Context<OpenDisputeAsym>, signer: Pubkey) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+
+    //check authorization (payer or receiver)
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+
+    //an escrow past its end time is recorded as expired before it is frozen
+    mark_expired_asym(
+        escrow,
+        signer,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+
+    //disputes require an arbiter and something left to dispute
+    require!(escrow.can_dispute(), EscrowError::InvalidArbiter);
+    require!(escrow.get_amount_remaining() > 0, EscrowError::InvalidEscrowState);
+    require!(
+        escrow.is_dispute_window_open(Clock::get()?.unix_timestamp),
+        EscrowError::DisputeWindowClosed
+    );
+
+    //opener posts the dispute bond
+    let bond_amount = escrow.fee_terms.dispute_bond;
+    if bond_amount > 0 {
+        transfer_native_sol(
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.dispute.to_account_info(),
+            bond_amount,
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    //initialize dispute
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.escrow = escrow.key();
+    dispute.rent_payer = ctx.accounts.signer.key();
+    begin_human_arbitration(
+        dispute,
+        escrow,
+        &ctx.accounts.program_config,
+        signer,
+        bond_amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    dispute.status = DisputeStatus::EvidencePhase;
+    dispute.defaulted = false;
+    dispute.evidence = Vec::new();
+    dispute.payer_fee_deposit = 0;
+    dispute.receiver_fee_deposit = 0;
+    dispute.automated_outcome = false;
+    dispute.escalated = false;
+    dispute.disclosed_amount = None;
+    dispute.bump = ctx.bumps.dispute;
+
+    //freeze the escrow until the award
+    if let Some(old) = escrow.set_status(EscrowStatus::Arbitration) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Arbitration,
+            actor: signer,
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+
+    log_escrow_action(log_actions::DISPUTE, &escrow.id, &[("amount", escrow.get_amount_remaining())]);
+    emit_cpi!(DisputeOpenedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        dispute: dispute.key(),
+        opened_by: signer,
+        respondent: if signer == escrow.payer.addr { escrow.receiver.addr } else { escrow.payer.addr },
+        arbiter: escrow.arbiter,
+        disputed_amount: escrow.get_amount_remaining(),
+        bond: bond_amount,
+        evidence_deadline: dispute.evidence_deadline,
+        decision_deadline: dispute.decision_deadline,
+        timestamp: dispute.opened_at,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Answer a dispute by posting the respondent's bond
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AnswerDisputeAsym<'info> {
+    #[account(mut)]
+    pub respondent: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn answer_dispute(ctx: Context<AnswerDisputeAsym>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let respondent = ctx.accounts.respondent.key();
+
+    //check authorization (the party that didn't open the dispute)
+    let is_payer = respondent == escrow.payer.addr;
+    let is_receiver = respondent == escrow.receiver.addr;
+    require!(is_payer || is_receiver, EscrowError::Unauthorized);
+    require!(respondent != ctx.accounts.dispute.opened_by, EscrowError::Unauthorized);
+
+    let already_answered = if is_payer {
+        ctx.accounts.dispute.payer_bond > 0
+    } else {
+        ctx.accounts.dispute.receiver_bond > 0
+    };
+    require!(!already_answered, EscrowError::DisputeAlreadyAnswered);
+
+    //respondent posts the dispute bond
+    let bond_amount = ctx.accounts.dispute.bond_amount;
+    if bond_amount > 0 {
+        transfer_native_sol(
+            ctx.accounts.respondent.to_account_info(),
+            ctx.accounts.dispute.to_account_info(),
+            bond_amount,
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    let dispute = &mut ctx.accounts.dispute;
+    if is_payer {
+        dispute.payer_bond = bond_amount;
+    } else {
+        dispute.receiver_bond = bond_amount;
+    }
+
+    emit_cpi!(DisputeAnsweredEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        respondent,
+        bond: bond_amount,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Submit an evidence commitment to a dispute
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitEvidenceAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+}
+
+pub fn submit_evidence(
+    ctx: Context<SubmitEvidenceAsym>,
+    content_hash: [u8; 32],
+    scheme: EvidenceHashScheme,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+
+    //check evidence window
+    let now = Clock::get()?.unix_timestamp;
+    dispute.advance_phase(now);
+    require!(
+        dispute.status == DisputeStatus::EvidencePhase,
+        EscrowError::EvidenceWindowClosed
+    );
+    require!(
+        dispute.evidence.len() < MAX_EVIDENCE_PER_DISPUTE,
+        EscrowError::EvidenceLimitReached
+    );
+
+    dispute.evidence.push(EvidenceCommitment {
+        submitted_by: signer,
+        content_hash,
+        scheme,
+        timestamp: now,
+    });
+
+    emit_cpi!(EvidenceSubmittedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        submitted_by: signer,
+        content_hash,
+        scheme,
+        index: (dispute.evidence.len() - 1) as u8,
+        timestamp: now,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Disclose the hidden agreed amount of an escrow for its dispute
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DiscloseAmountAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = escrow.has_hidden_terms() @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+}
+
+pub fn disclose_amount(ctx: Context<DiscloseAmountAsym>, amount: u64, salt: [u8; 32]) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+    require!(dispute.disclosed_amount.is_none(), EscrowError::AmountAlreadyDisclosed);
+
+    //the opening must match the commitment made at creation
+    require!(
+        compute_amount_commitment(&escrow.key(), amount, &salt) == escrow.amount_commitment,
+        EscrowError::AmountCommitmentMismatch
+    );
+    dispute.disclosed_amount = Some(amount);
+
+    emit_cpi!(AmountDisclosedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        dispute: dispute.key(),
+        disclosed_by: signer,
+        arbiter: dispute.arbiter,
+        amount,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Close the evidence phase of a dispute and put it under review
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseEvidencePhaseAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::EvidencePhase @ EscrowError::InvalidDisputeTransition,
+    )]
+    pub dispute: Account<'info, Dispute>,
+}
+
+pub fn close_evidence_phase(ctx: Context<CloseEvidencePhaseAsym>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+
+    //the arbiter may close evidence early; anyone may once the deadline has passed
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.signer.key() == dispute.arbiter || now > dispute.evidence_deadline,
+        EscrowError::Unauthorized
+    );
+
+    dispute.transition(DisputeStatus::UnderReview)?;
+
+    emit_cpi!(DisputeStatusChangedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        dispute: dispute.key(),
+        status: dispute.status,
+        timestamp: now,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Award a disputed asymmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AwardArbitrationAsym<'info> {
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn award_arbitration(
+    ctx: Context<AwardArbitrationAsym>,
+    payer_amount: u64,
+    rationale_hash: [u8; 32],
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+
+    //check authorization (arbiter deciding alone, or a governance PDA signing an executed proposal)
+    require!(ctx.accounts.arbiter.key() == dispute.arbiter, EscrowError::Unauthorized);
+    match escrow.arbiter_kind {
+        ArbiterKind::Individual => {},
+        ArbiterKind::Governance => require!(
+            *ctx.accounts.arbiter.owner == SPL_GOVERNANCE_PROGRAM_ID,
+            EscrowError::InvalidArbiter
+        ),
+        _ => return err!(EscrowError::Unauthorized),
+    }
+
+    //check decision deadline
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= dispute.decision_deadline, EscrowError::DecisionDeadlinePassed);
+    dispute.advance_phase(now);
+
+    record_award(dispute, escrow, payer_amount, true)?;
+    conclude_award(dispute, escrow, now)?;
+    dispute.rationale_hash = rationale_hash;
+
+    emit_cpi!(award_event(escrow, dispute, dispute.arbiter));
+
+    Ok(())
+}
+
+/// Assign an arbiter panel to an asymmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AssignArbiterPanelAsym<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ EscrowError::Unauthorized,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = ArbiterPanel::space(),
+        seeds = [seeds::ARBITER_PANEL, escrow.key().as_ref()],
+        bump
+    )]
+    pub arbiter_panel: Account<'info, ArbiterPanel>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn assign_arbiter_panel(
+    ctx: Context<AssignArbiterPanelAsym>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+
+    //panels are convened by the arbiter of record
+    require!(escrow.has_arbiter(), EscrowError::InvalidArbiter);
+    require!(escrow.arbiter_kind == ArbiterKind::Individual, EscrowError::InvalidPanel);
+
+    //validate members and threshold
+    require!(
+        !members.is_empty() && members.len() <= MAX_PANEL_SIZE,
+        EscrowError::InvalidPanel
+    );
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        EscrowError::InvalidPanel
+    );
+    for (i, member) in members.iter().enumerate() {
+        require!(
+            *member != Pubkey::default()
+                && *member != escrow.payer.addr
+                && *member != escrow.receiver.addr
+                && !members[..i].contains(member),
+            EscrowError::InvalidPanel
+        );
+    }
+
+    let arbiter_panel = &mut ctx.accounts.arbiter_panel;
+    arbiter_panel.escrow = escrow.key();
+    arbiter_panel.members = members;
+    arbiter_panel.threshold = threshold;
+    arbiter_panel.votes = Vec::new();
+    arbiter_panel.rent_payer = ctx.accounts.creator.key();
+    arbiter_panel.bump = ctx.bumps.arbiter_panel;
+
+    escrow.arbiter_kind = ArbiterKind::Panel;
+
+    emit_cpi!(ArbiterPanelAssignedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        members: arbiter_panel.members.clone(),
+        threshold,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Draw a jury from the arbiter pool for a disputed escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DrawJuryAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = escrow.arbiter_kind == ArbiterKind::Jury @ EscrowError::InvalidPanel,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = ArbiterPanel::space(),
+        seeds = [seeds::ARBITER_PANEL, escrow.key().as_ref()],
+        bump
+    )]
+    pub arbiter_panel: Account<'info, ArbiterPanel>,
+
+    #[account(
+        seeds = [seeds::ARBITER_POOL],
+        bump = arbiter_pool.bump
+    )]
+    pub arbiter_pool: Account<'info, ArbiterPool>,
+
+    /// CHECK: slot hashes sysvar, read for the selection seed
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub recent_slothashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn draw_jury(ctx: Context<DrawJuryAsym>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+
+    //seed from the most recent slot hash and the dispute address
+    let slothashes = ctx.accounts.recent_slothashes.try_borrow_data()?;
+    require!(slothashes.len() >= 48, EscrowError::InsufficientJurors);
+    let seed = anchor_lang::solana_program::hash::hashv(&[
+        &slothashes[16..48],
+        dispute.key().as_ref(),
+    ]).to_bytes();
+    drop(slothashes);
+
+    //parties can't sit on their own jury
+    let candidates: Vec<ArbiterPoolEntry> = ctx.accounts.arbiter_pool.entries
+        .iter()
+        .filter(|e| {
+            e.stake >= MIN_ARBITER_BOND
+                && e.arbiter != escrow.payer.addr
+                && e.arbiter != escrow.receiver.addr
+                && e.arbiter != escrow.appeal_arbiter
+        })
+        .cloned()
+        .collect();
+    require!(candidates.len() >= JURY_SIZE, EscrowError::InsufficientJurors);
+
+    let jurors = select_weighted(&candidates, seed, JURY_SIZE);
+
+    let arbiter_panel = &mut ctx.accounts.arbiter_panel;
+    arbiter_panel.escrow = escrow.key();
+    arbiter_panel.members = jurors;
+    arbiter_panel.threshold = (JURY_SIZE / 2 + 1) as u8;
+    arbiter_panel.votes = Vec::new();
+    arbiter_panel.rent_payer = ctx.accounts.signer.key();
+    arbiter_panel.bump = ctx.bumps.arbiter_panel;
+
+    //the jury stands in as arbiter of record
+    dispute.arbiter = arbiter_panel.key();
+
+    emit_cpi!(ArbiterPanelAssignedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        members: arbiter_panel.members.clone(),
+        threshold: arbiter_panel.threshold,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Cast a panel member's vote on a disputed escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VoteAwardAsym<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = matches!(escrow.arbiter_kind, ArbiterKind::Panel | ArbiterKind::Jury) @ EscrowError::InvalidPanel,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [seeds::ARBITER_PANEL, escrow.key().as_ref()],
+        bump = arbiter_panel.bump
+    )]
+    pub arbiter_panel: Account<'info, ArbiterPanel>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = ArbitrationVote::space(),
+        seeds = [seeds::ARBITRATION_VOTE, dispute.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, ArbitrationVote>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn vote_award(
+    ctx: Context<VoteAwardAsym>,
+    payer_amount: u64,
+    rationale_hash: [u8; 32],
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+    let arbiter_panel = &mut ctx.accounts.arbiter_panel;
+    let voter = ctx.accounts.voter.key();
+
+    //check authorization (panel member)
+    require!(arbiter_panel.is_member(&voter), EscrowError::Unauthorized);
+
+    //check decision deadline; votes are cast once the evidence phase is over
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= dispute.decision_deadline, EscrowError::DecisionDeadlinePassed);
+    dispute.advance_phase(now);
+    require!(
+        dispute.status == DisputeStatus::UnderReview,
+        EscrowError::InvalidDisputeTransition
+    );
+    require!(
+        payer_amount <= escrow.get_amount_remaining(),
+        EscrowError::AmountExceeded
+    );
+
+    //record vote
+    let vote = &mut ctx.accounts.vote;
+    vote.dispute = dispute.key();
+    vote.voter = voter;
+    vote.payer_amount = payer_amount;
+    vote.rationale_hash = rationale_hash;
+    vote.timestamp = now;
+    vote.bump = ctx.bumps.vote;
+
+    arbiter_panel.votes.push(PanelVote { voter, payer_amount });
+    let votes_for = arbiter_panel.votes_for(payer_amount);
+
+    emit_cpi!(ArbitrationVoteCastEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        voter,
+        payer_amount,
+        rationale_hash,
+        votes_for: votes_for as u8,
+        threshold: arbiter_panel.threshold,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    //award once the threshold agrees on the split
+    if votes_for >= arbiter_panel.threshold as usize {
+        record_award(dispute, escrow, payer_amount, true)?;
+        conclude_award(dispute, escrow, now)?;
+
+        dispute.rationale_hash = rationale_hash;
+        emit_cpi!(award_event(escrow, dispute, dispute.arbiter));
+    }
+
+    Ok(())
+}
+
+/// Apply the default resolution to a dispute the arbiter failed to decide in time
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveExpiredDisputeAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+}
+
+pub fn resolve_expired_dispute(ctx: Context<ResolveExpiredDisputeAsym>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+
+    //check decision deadline
+    let now = Clock::get()?.unix_timestamp;
+    require!(now > dispute.decision_deadline, EscrowError::DecisionDeadlineNotReached);
+
+    //apply default resolution; the arbiter forfeits the arbitration fee
+    let remaining_amount = escrow.get_amount_remaining();
+    let payer_amount = match dispute.default_resolution {
+        DefaultResolution::Refund => remaining_amount,
+        DefaultResolution::Split => remaining_amount - remaining_amount / 2,
+    };
+    record_award(dispute, escrow, payer_amount, false)?;
+
+    dispute.advance_phase(now);
+    dispute.transition(DisputeStatus::Awarded)?;
+    dispute.awarded_at = now;
+    dispute.appeal_deadline = now;
+    dispute.final_award = true;
+    dispute.defaulted = true;
+    dispute.rationale_hash = [0u8; 32];
+
+    emit_cpi!(DisputeDefaultResolvedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        resolution: dispute.default_resolution,
+        payer_award: dispute.payer_award,
+        receiver_award: dispute.receiver_award,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    emit_cpi!(award_event(escrow, dispute, dispute.arbiter));
+
+    Ok(())
+}
+
+/// Propose an automated settlement for an asymmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitAutomatedOutcomeAsym<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Active @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        init,
+        payer = settler,
+        space = Dispute::space(),
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn submit_automated_outcome(
+    ctx: Context<SubmitAutomatedOutcomeAsym>,
+    payer_amount: u64,
+    rationale_hash: [u8; 32],
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+
+    //check authorization (automated settler)
+    let settler = ctx.accounts.settler.key();
+    require!(
+        escrow.has_automated_settler() && settler == escrow.automated_settler,
+        EscrowError::Unauthorized
+    );
+    require!(escrow.get_amount_remaining() > 0, EscrowError::InvalidEscrowState);
+
+    //record the outcome as an award that takes effect unless escalated
+    let now = Clock::get()?.unix_timestamp;
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.escrow = escrow.key();
+    dispute.rent_payer = settler;
+    dispute.opened_by = settler;
+    dispute.arbiter = settler;
+    dispute.opened_at = now;
+    dispute.evidence_deadline = now;
+    dispute.decision_deadline = now;
+    dispute.default_resolution = ctx.accounts.program_config.default_resolution;
+    dispute.bond_amount = 0;
+    dispute.payer_bond = 0;
+    dispute.receiver_bond = 0;
+    dispute.defaulted = false;
+    dispute.evidence = Vec::new();
+    dispute.payer_fee_deposit = 0;
+    dispute.receiver_fee_deposit = 0;
+    dispute.bump = ctx.bumps.dispute;
+
+    //the outcome starts out awarded; escalation reopens the evidence phase
+    record_award(dispute, escrow, payer_amount, false)?;
+    dispute.status = DisputeStatus::Awarded;
+    dispute.awarded_at = now;
+    dispute.appeal_deadline = now
+        .checked_add(ESCALATION_WINDOW)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    dispute.final_award = false;
+    dispute.rationale_hash = rationale_hash;
+    dispute.automated_outcome = true;
+    dispute.escalated = false;
+    dispute.disclosed_amount = None;
+
+    //freeze the escrow until the outcome is executed or escalated
+    if let Some(old) = escrow.set_status(EscrowStatus::Arbitration) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Arbitration,
+            actor: ctx.accounts.settler.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+
+    emit_cpi!(AutomatedOutcomeSubmittedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        settler,
+        payer_award: dispute.payer_award,
+        receiver_award: dispute.receiver_award,
+        rationale_hash,
+        escalation_deadline: dispute.appeal_deadline,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Contest an automated settlement and move the escrow to human arbitration
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EscalateAutomatedOutcomeAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.automated_outcome @ EscrowError::InvalidEscrowState,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn escalate_automated_outcome(ctx: Context<EscalateAutomatedOutcomeAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+
+    //human arbitration requires an arbiter
+    require!(escrow.can_dispute(), EscrowError::InvalidArbiter);
+
+    //check escalation window
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= ctx.accounts.dispute.appeal_deadline, EscrowError::EscalationWindowClosed);
+
+    //escalating party posts the dispute bond
+    let bond_amount = escrow.fee_terms.dispute_bond;
+    if bond_amount > 0 {
+        transfer_native_sol(
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.dispute.to_account_info(),
+            bond_amount,
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    //discard the automated outcome and restart as a human dispute
+    let dispute = &mut ctx.accounts.dispute;
+    let settler = dispute.opened_by;
+    dispute.transition(DisputeStatus::EvidencePhase)?;
+    begin_human_arbitration(dispute, escrow, &ctx.accounts.program_config, signer, bond_amount, now)?;
+    dispute.automated_outcome = false;
+    dispute.escalated = true;
+
+    emit_cpi!(AutomatedOutcomeEscalatedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        escalated_by: signer,
+        settler,
+        arbiter: dispute.arbiter,
+        bond: bond_amount,
+        evidence_deadline: dispute.evidence_deadline,
+        decision_deadline: dispute.decision_deadline,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Appeal an arbitration award to the second arbitration tier
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AppealAwardAsym<'info> {
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Awarded @ EscrowError::InvalidEscrowState,
+        constraint = !dispute.final_award @ EscrowError::DisputeResolved,
+        constraint = !dispute.appealed @ EscrowError::AlreadyAppealed,
+        constraint = !dispute.automated_outcome @ EscrowError::InvalidEscrowState,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn appeal_award(ctx: Context<AppealAwardAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    let appellant = ctx.accounts.appellant.key();
+
+    //only the losing party may appeal (either party on an even split)
+    let is_payer = appellant == escrow.payer.addr;
+    let is_receiver = appellant == escrow.receiver.addr;
+    let authorized = match ctx.accounts.dispute.award_against {
+        Some(DisputeParty::Payer) => is_payer,
+        Some(DisputeParty::Receiver) => is_receiver,
+        None => is_payer || is_receiver,
+    };
+    require!(authorized, EscrowError::Unauthorized);
+
+    //check appeal window
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= ctx.accounts.dispute.appeal_deadline, EscrowError::AppealWindowClosed);
+
+    //post appeal bond on the dispute account
+    transfer_native_sol(
+        ctx.accounts.appellant.to_account_info(),
+        ctx.accounts.dispute.to_account_info(),
+        APPEAL_BOND,
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.transition(DisputeStatus::Appealed)?;
+    dispute.appealed = true;
+    dispute.appellant = appellant;
+    dispute.appeal_bond = APPEAL_BOND;
+
+    emit_cpi!(AwardAppealedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        appellant,
+        appeal_arbiter: escrow.appeal_arbiter,
+        appeal_bond: APPEAL_BOND,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Decide an appealed award (final)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AwardAppealAsym<'info> {
+    #[account(mut)]
+    pub appeal_arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Appealed @ EscrowError::InvalidEscrowState,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: appellant, refunded the appeal bond if the appeal succeeds; may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = dispute.appellant @ EscrowError::InvalidPartyAddress
+    )]
+    pub appellant: UncheckedAccount<'info>,
+}
+
+pub fn award_appeal(
+    ctx: Context<AwardAppealAsym>,
+    payer_amount: u64,
+    rationale_hash: [u8; 32],
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &mut ctx.accounts.dispute;
+
+    //check authorization (appeal arbiter)
+    require!(
+        ctx.accounts.appeal_arbiter.key() == escrow.appeal_arbiter,
+        EscrowError::Unauthorized
+    );
+
+    //appellant's share under the original award
+    let appellant_is_payer = dispute.appellant == escrow.payer.addr;
+    let original_share = if appellant_is_payer { dispute.payer_award } else { dispute.receiver_award };
+
+    record_award(dispute, escrow, payer_amount, true)?;
+    dispute.transition(DisputeStatus::Awarded)?;
+    dispute.awarded_at = Clock::get()?.unix_timestamp;
+    dispute.final_award = true;
+    dispute.rationale_hash = rationale_hash;
+
+    //appeal succeeds if the appellant's share improved
+    let appealed_share = if appellant_is_payer { dispute.payer_award } else { dispute.receiver_award };
+    let appeal_upheld = appealed_share > original_share;
+
+    //return bond to a successful appellant, otherwise it compensates the appeal arbiter
+    let bond_recipient = if appeal_upheld {
+        ctx.accounts.appellant.to_account_info()
+    } else {
+        ctx.accounts.appeal_arbiter.to_account_info()
+    };
+    let appeal_bond = dispute.appeal_bond;
+    transfer_lamports_from_program_account(
+        &dispute.to_account_info(),
+        &bond_recipient,
+        appeal_bond,
+    )?;
+    dispute.appeal_bond = 0;
+
+    emit_cpi!(award_event(escrow, dispute, ctx.accounts.appeal_arbiter.key()));
+
+    emit_cpi!(AppealDecidedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        appellant: dispute.appellant,
+        appeal_upheld,
+        appeal_bond,
+        bond_recipient: bond_recipient.key(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Execute an arbitration award once it can no longer be appealed
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteAwardAsym<'info> {
+    /// Pays for any token records created when a programmable NFT is moved
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = !escrow.external_call_in_progress @ EscrowError::ReentrantCall,
+    )]
+    pub escrow: Box<Account<'info, AsymEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Awarded @ EscrowError::InvalidEscrowState,
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Escrow vault
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    /// CHECK: payer account for native refunds; may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = escrow.payer.addr @ EscrowError::InvalidPayer
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: receiver account for native releases (the chained escrow's vault, or the claim token holder 
+    /// if the claim is tokenized); may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = payout_recipient(&escrow, claim_token_account.as_deref()) @ EscrowError::InvalidReceiver
+    )]
+    pub receiver: UncheckedAccount<'info>,
+
+    /// CHECK: arbiter of record, receives native arbitration fees; may be a program-owned account
+    #[account(
+        mut,
+        address = dispute.arbiter @ EscrowError::InvalidArbiter
+    )]
+    pub arbiter: UncheckedAccount<'info>,
+
+    /// Fee vault
+    #[account(
+        mut,
+        address = program_config.fee_vault @ EscrowError::InvalidConfig
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// Insurance fund, receives its share of native protocol fees
+    #[account(
+        mut,
+        seeds = [seeds::INSURANCE_FUND],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+
+    /// For SPL token transfers
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == escrow_vault.key()
+            && escrow_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub escrow_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == escrow.payer.addr @ EscrowError::InvalidPayer,
+        constraint = payer_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = receiver_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub receiver_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = arbiter_token_account.owner == dispute.arbiter @ EscrowError::InvalidArbiter
+    )]
+    pub arbiter_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Account holding the escrow's claim token (only required if the claim is tokenized)
+    pub claim_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Escrow the receiver's share is paid into (only required for chained escrows)
+    #[account(
+        mut,
+        address = escrow.chained_escrow @ EscrowError::InvalidEscrowChain
+    )]
+    pub chained_escrow: Option<Box<Account<'info, AsymEscrow>>>,
+
+    /// Fee vault's token account of the escrow currency (only required for SPL token escrows)
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == program_config.fee_vault
+            && fee_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub fee_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Party reputations, updated when the escrow completes
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.payer.addr.as_ref()],
+        bump = payer_reputation.bump
+    )]
+    pub payer_reputation: Option<Box<Account<'info, Reputation>>>,
+
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.receiver.addr.as_ref()],
+        bump = receiver_reputation.bump
+    )]
+    pub receiver_reputation: Option<Box<Account<'info, Reputation>>>,
+
+    /// Token mint (only required for SPL token escrows)
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Receipt mint of the escrow (only required if the escrow has a receipt), burned once it completes
+    #[account(
+        mut,
+        address = escrow.receipt_mint @ EscrowError::InvalidToken
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Token account holding the escrow's receipt (only required if the escrow has a receipt)
+    #[account(
+        mut,
+        constraint = receipt_token_account.mint == escrow.receipt_mint @ EscrowError::InvalidToken,
+    )]
+    pub receipt_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token-2022 program of the receipt (only required if the escrow has a receipt)
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+
+    /// CHECK: parties' attestations (only required for regulated escrows), checked by verify_attestation
+    pub payer_attestation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: see payer_attestation
+    pub receiver_attestation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: blocklist entries of the payer and receiver, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_blocklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: see payer_blocklist_entry
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_blocklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: blocklist entry of the payout recipient, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, receiver.key().as_ref()],
+        bump
+    )]
+    pub payout_blocklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: statistics of the escrow currency, updated by update_currency_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CURRENCY_STATS, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub currency_stats: UncheckedAccount<'info>,
+
+    /// CHECK: counters of the escrow category, updated by update_category_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CATEGORY_STATS, &[escrow.category as u8]],
+        bump
+    )]
+    pub category_stats: UncheckedAccount<'info>,
+
+    /// CHECK: daily volume history of the escrow currency, updated by update_volume_history if kept
+    #[account(
+        mut,
+        seeds = [seeds::VOLUME_HISTORY, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub volume_history: UncheckedAccount<'info>,
+
+    /// CHECK: global TVL ledger, updated by update_tvl_ledger once it exists
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: parties' lifetime counters, updated by update_user_stats if they exist
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_stats: UncheckedAccount<'info>,
+
+    /// CHECK: see payer_stats
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_stats: UncheckedAccount<'info>,
+
+    /// CHECK: settlement hook program (only required if the escrow has one), called by invoke_settlement_hook
+    #[account(
+        executable,
+        address = escrow.settlement_hook @ EscrowError::SettlementHookNotAllowed
+    )]
+    pub settlement_hook: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA signing the settlement hook call
+    #[account(
+        seeds = [seeds::SETTLEMENT_HOOK_AUTHORITY],
+        bump
+    )]
+    pub settlement_hook_authority: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: state account of the settlement hook, passed through to it unchecked
+    #[account(mut)]
+    pub settlement_hook_state: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn execute_award<'info>(mut ctx: Context<'_, '_, '_, 'info, ExecuteAwardAsym<'info>>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    //award must be final, or past its appeal window without an appeal
+    let dispute = &ctx.accounts.dispute;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        dispute.final_award || (!dispute.appealed && now > dispute.appeal_deadline),
+        EscrowError::AwardNotFinal
+    );
+
+    //lent or staked funds must be withdrawn before the award is paid out
+    require!(!ctx.accounts.escrow.is_lent(), EscrowError::FundsLent);
+    require!(!ctx.accounts.escrow.is_staked(), EscrowError::FundsStaked);
+
+    let payer_award = dispute.payer_award;
+    let receiver_award = dispute.receiver_award;
+
+    //regulated escrows pay the receiver only between attested parties
+    if receiver_award > 0 {
+        verify_party_attestations(
+            &ctx.accounts.escrow,
+            ctx.accounts.payer_attestation.as_ref(),
+            ctx.accounts.receiver_attestation.as_ref(),
+        )?;
+    }
+
+    //awards involving a sanctioned address are rejected
+    require_not_blocked(
+        &[
+            &ctx.accounts.payer_blocklist_entry,
+            &ctx.accounts.receiver_blocklist_entry,
+            &ctx.accounts.payout_blocklist_entry,
+        ],
+        &ctx.accounts.escrow.id,
+        BlockedTransfer::Award,
+    )?;
+    //fee token fees are settled from the dispute fee vault instead
+    let arbitration_fee = if ctx.accounts.escrow.has_fee_token() { 0 } else { dispute.arbitration_fee };
+
+    //protocol fee applies to the released share only
+    let (protocol_fee, receiver_payout) = release_fee_and_payout(&ctx.accounts.escrow, receiver_award)?;
+
+    //divert the insurance fund's share of native protocol fees
+    let insurance_share = insurance_fee_share(&ctx.accounts.escrow, protocol_fee)?;
+    require!(
+        insurance_share == 0 || ctx.accounts.insurance_fund.is_some(),
+        EscrowError::InvalidConfig
+    );
+
+    transfer_award(&ctx, payer_award, receiver_payout, arbitration_fee, protocol_fee - insurance_share, insurance_share)?;
+
+    if insurance_share > 0 {
+        if let Some(insurance_fund) = ctx.accounts.insurance_fund.as_mut() {
+            insurance_fund.record_deposit(insurance_share).ok_or(EscrowError::ArithmeticOverflow)?;
+            emit_cpi!(InsuranceFundDepositEvent {
+                version: EVENT_VERSION,
+                source: ctx.accounts.escrow.key(),
+                seq: ctx.accounts.program_config.next_event_seq(),
+                amount: insurance_share,
+                balance: insurance_fund.balance,
+                reserved: [0; EVENT_RESERVED_BYTES],
+            });
+        }
+    }
+
+    //net of any token transfer fee withheld on the receiver's payout
+    let receiver_delivered = match ctx.accounts.token_mint.as_ref() {
+        Some(token_mint) if ctx.accounts.escrow.payer.currency_type != CurrencyType::Native => receiver_payout
+            .checked_sub(calculate_transfer_fee(token_mint, receiver_payout)?)
+            .ok_or(EscrowError::ArithmeticOverflow)?,
+        _ => receiver_payout,
+    };
+
+    //update escrow state
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.payer.amount_refunded = escrow.payer.amount_refunded
+        .checked_add(payer_award)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    escrow.payer.amount_released = escrow.payer.amount_released
+        .checked_add(receiver_award)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    escrow.payer.amount_delivered = escrow.payer.amount_delivered
+        .checked_add(receiver_delivered)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    escrow.arbitration_fee_paid = escrow.arbitration_fee_paid
+        .checked_add(arbitration_fee)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    let completed = escrow.set_status(EscrowStatus::Completed);
+    if let Some(old) = completed {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Completed,
+            actor: ctx.accounts.signer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+
+    //the award completes the escrow, so its receipt and any claim it carries end here
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    burn_settled_receipt(
+        escrow,
+        SettlementReceiptAccounts {
+            receipt_mint: ctx.accounts.receipt_mint.as_deref(),
+            receipt_token_account: ctx.accounts.receipt_token_account.as_deref(),
+            token_program: ctx.accounts.receipt_token_program.as_ref(),
+            escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+        },
+        &[&vault_seeds[..]],
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+
+    update_currency_stats(&ctx.accounts.currency_stats, |stats, now| {
+        stats.record_release(receiver_payout, protocol_fee, false, now);
+        stats.record_refund(payer_award, completed.is_some(), now);
+    })?;
+    if completed.is_some() {
+        update_category_stats(&ctx.accounts.category_stats, |stats, now| stats.record_settled(true, now))?;
+    }
+
+    //a receiver share paid into a chained escrow stays locked, in the downstream vault
+    let relocked = if escrow.is_chained() { receiver_delivered } else { 0 };
+    let withdrawn = payer_award
+        .checked_add(receiver_award)
+        .and_then(|w| w.checked_add(arbitration_fee))
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    update_tvl_ledger(&ctx.accounts.tvl_ledger, &escrow.payer.currency, relocked, withdrawn)?;
+    update_volume_history(&ctx.accounts.volume_history, 0, withdrawn)?;
+    if completed.is_some() {
+        update_user_stats(
+            &ctx.accounts.payer_stats,
+            &ctx.accounts.receiver_stats,
+            escrow.payer.amount_paid,
+            true,
+        )?;
+    }
+
+    //the receiver's share of a chained escrow counts as payment downstream
+    if escrow.is_chained() && receiver_delivered > 0 {
+        let chained_escrow = ctx.accounts.chained_escrow
+            .as_mut()
+            .ok_or(EscrowError::InvalidEscrowChain)?;
+        credit_chained_escrow(
+            chained_escrow,
+            escrow_key,
+            receiver_delivered,
+            &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+    }
+
+    ctx.accounts.dispute.transition(DisputeStatus::Resolved)?;
+
+    //update party reputations
+    let dispute = &ctx.accounts.dispute;
+    let lost_by = if dispute.defaulted || dispute.automated_outcome { None } else { dispute.award_against };
+    let volume = escrow.payer.amount_paid;
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(reputation) = ctx.accounts.payer_reputation.as_mut() {
+        reputation.record_dispute(
+            volume,
+            dispute.opened_by == escrow.payer.addr,
+            lost_by == Some(DisputeParty::Payer),
+            now,
+        );
+    }
+    if let Some(reputation) = ctx.accounts.receiver_reputation.as_mut() {
+        reputation.record_dispute(
+            volume,
+            dispute.opened_by == escrow.receiver.addr,
+            lost_by == Some(DisputeParty::Receiver),
+            now,
+        );
+    }
+
+    log_escrow_action(log_actions::AWARD, &escrow.id, &[
+        ("payer_award", payer_award),
+        ("receiver_award", receiver_award),
+    ]);
+    emit_cpi!(AwardExecutedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        payer_award,
+        receiver_payout,
+        arbitration_fee,
+        protocol_fee,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    settle_dispute_bonds(&mut ctx)?;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.payer_bond = 0;
+    dispute.receiver_bond = 0;
+
+    //the hook hears about each side of a split award as its own settlement
+    let outcomes = [
+        (SettlementOutcome::Refund, payer_award, 0, payer_award),
+        (SettlementOutcome::Release, receiver_payout, protocol_fee, receiver_delivered),
+    ];
+    for (outcome, amount, fee, amount_delivered) in outcomes {
+        if amount == 0 {
+            continue;
+        }
+        invoke_settlement_hook(
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.program_config,
+            ctx.accounts.settlement_hook.as_ref(),
+            ctx.accounts.settlement_hook_authority.as_ref(),
+            ctx.bumps.settlement_hook_authority,
+            ctx.accounts.settlement_hook_state.as_ref(),
+            outcome,
+            amount,
+            fee,
+            amount_delivered,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Create the fee token vault holding a dispute's arbitration fee deposits
+#[derive(Accounts)]
+pub struct InitializeDisputeFeeVaultAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = escrow.has_fee_token() @ EscrowError::InvalidToken,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        address = escrow.arbitration_fee_mint @ EscrowError::InvalidToken
+    )]
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        token::mint = fee_mint,
+        token::authority = dispute,
+        token::token_program = token_program,
+        seeds = [seeds::DISPUTE_FEE_VAULT, dispute.key().as_ref()],
+        bump
+    )]
+    pub dispute_fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_dispute_fee_vault(_ctx: Context<InitializeDisputeFeeVaultAsym>) -> Result<()> {
+    Ok(())
+}
+
+/// Deposit a party's arbitration fee in the fee token
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositArbitrationFeeAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.has_fee_token() @ EscrowError::InvalidToken,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE_FEE_VAULT, dispute.key().as_ref()],
+        bump
+    )]
+    pub dispute_fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = signer_token_account.owner == signer.key() @ EscrowError::Unauthorized,
+        constraint = signer_token_account.mint == escrow.arbitration_fee_mint @ EscrowError::InvalidToken,
+    )]
+    pub signer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = escrow.arbitration_fee_mint @ EscrowError::InvalidToken
+    )]
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn deposit_arbitration_fee<'info>(ctx: Context<'_, '_, '_, 'info, DepositArbitrationFeeAsym<'info>>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+
+    //check authorization (payer or receiver)
+    let is_payer = signer == escrow.payer.addr;
+    require!(is_payer || signer == escrow.receiver.addr, EscrowError::Unauthorized);
+
+    let already_deposited = if is_payer {
+        ctx.accounts.dispute.payer_fee_deposit > 0
+    } else {
+        ctx.accounts.dispute.receiver_fee_deposit > 0
+    };
+    require!(!already_deposited, EscrowError::FeeAlreadyDeposited);
+
+    //each party covers the full flat fee up front; the winner's deposit is returned
+    let amount = escrow.arbitration_fee.value;
+    transfer_spl_tokens(
+        &ctx.accounts.signer_token_account,
+        &ctx.accounts.dispute_fee_vault,
+        &ctx.accounts.fee_mint,
+        &ctx.accounts.signer,
+        amount,
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+    )?;
+
+    let dispute = &mut ctx.accounts.dispute;
+    if is_payer {
+        dispute.payer_fee_deposit = amount;
+    } else {
+        dispute.receiver_fee_deposit = amount;
+    }
+
+    emit_cpi!(ArbitrationFeeDepositedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        depositor: signer,
+        mint: escrow.arbitration_fee_mint,
+        amount,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Pay the arbiter from the fee token deposits of an executed award and refund the rest
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleArbitrationFeeAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.has_fee_token() @ EscrowError::InvalidToken,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Resolved @ EscrowError::InvalidEscrowState,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [seeds::DISPUTE_FEE_VAULT, dispute.key().as_ref()],
+        bump
+    )]
+    pub dispute_fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payer_fee_token_account.owner == escrow.payer.addr @ EscrowError::InvalidPayer,
+        constraint = payer_fee_token_account.mint == escrow.arbitration_fee_mint @ EscrowError::InvalidToken,
+    )]
+    pub payer_fee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = receiver_fee_token_account.owner == escrow.receiver.addr @ EscrowError::InvalidReceiver,
+        constraint = receiver_fee_token_account.mint == escrow.arbitration_fee_mint @ EscrowError::InvalidToken,
+    )]
+    pub receiver_fee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = arbiter_fee_token_account.owner == dispute.arbiter @ EscrowError::InvalidArbiter,
+        constraint = arbiter_fee_token_account.mint == escrow.arbitration_fee_mint @ EscrowError::InvalidToken,
+    )]
+    pub arbiter_fee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = escrow.arbitration_fee_mint @ EscrowError::InvalidToken
+    )]
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn settle_arbitration_fee<'info>(ctx: Context<'_, '_, '_, 'info, SettleArbitrationFeeAsym<'info>>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &ctx.accounts.dispute;
+
+    //each party pays its share of the fee out of its own deposit
+    let (payer_share, receiver_share) = fee_token_shares(dispute.arbitration_fee, dispute.award_against);
+    let payer_fee = payer_share.min(dispute.payer_fee_deposit);
+    let receiver_fee = receiver_share.min(dispute.receiver_fee_deposit);
+    let payer_refund = dispute.payer_fee_deposit - payer_fee;
+    let receiver_refund = dispute.receiver_fee_deposit - receiver_fee;
+    let arbiter_fee = payer_fee
+        .checked_add(receiver_fee)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+
+    //generate signer seeds for the dispute (fee vault authority)
+    let escrow_key = escrow.key();
+    let dispute_seeds = &[
+        seeds::DISPUTE,
+        escrow_key.as_ref(),
+        &[dispute.bump],
+    ];
+    let dispute_signer = &[&dispute_seeds[..]];
+
+    let transfers = [
+        (&ctx.accounts.arbiter_fee_token_account, arbiter_fee),
+        (&ctx.accounts.payer_fee_token_account, payer_refund),
+        (&ctx.accounts.receiver_fee_token_account, receiver_refund),
+    ];
+    for (to, amount) in transfers {
+        if amount > 0 {
+            transfer_spl_tokens_signed(
+                &ctx.accounts.dispute_fee_vault,
+                to,
+                &ctx.accounts.fee_mint,
+                dispute.to_account_info(),
+                amount,
+                &ctx.accounts.token_program,
+                dispute_signer,
+                ctx.remaining_accounts,
+            )?;
+        }
+    }
+
+    emit_cpi!(ArbitrationFeeSettledEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        arbiter: dispute.arbiter,
+        arbiter_fee,
+        payer_refund,
+        receiver_refund,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.payer_fee_deposit = 0;
+    dispute.receiver_fee_deposit = 0;
+
+    Ok(())
+}
+
+//helper function to return the prevailing party's bond and pay the loser's toward arbitration costs
+fn settle_dispute_bonds(ctx: &mut Context<ExecuteAwardAsym>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let dispute = &ctx.accounts.dispute;
+    let payer_bond = dispute.payer_bond;
+    let receiver_bond = dispute.receiver_bond;
+
+    //arbitration costs go to the arbiter of record (the protocol for juries)
+    let costs_recipient = if escrow.arbiter_kind == ArbiterKind::Jury {
+        ctx.accounts.fee_vault.to_account_info()
+    } else {
+        ctx.accounts.arbiter.to_account_info()
+    };
+
+    //a defaulted dispute has no arbitration costs to cover
+    let award_against = if dispute.defaulted { None } else { dispute.award_against };
+    let (payer_bond_to, receiver_bond_to) = match award_against {
+        Some(DisputeParty::Payer) => (costs_recipient, ctx.accounts.receiver.to_account_info()),
+        Some(DisputeParty::Receiver) => (ctx.accounts.payer.to_account_info(), costs_recipient),
+        None => (ctx.accounts.payer.to_account_info(), ctx.accounts.receiver.to_account_info()),
+    };
+
+    let dispute_info = dispute.to_account_info();
+    if payer_bond > 0 {
+        transfer_lamports_from_program_account(&dispute_info, &payer_bond_to, payer_bond)?;
+    }
+    if receiver_bond > 0 {
+        transfer_lamports_from_program_account(&dispute_info, &receiver_bond_to, receiver_bond)?;
+    }
+
+    emit_cpi!(DisputeBondsSettledEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        payer_bond,
+        payer_bond_to: payer_bond_to.key(),
+        receiver_bond,
+        receiver_bond_to: receiver_bond_to.key(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+//helper function to start (or restart) the human arbitration phases of a dispute
+fn begin_human_arbitration(
+    dispute: &mut Dispute,
+    escrow: &AsymEscrow,
+    program_config: &ProgramConfig,
+    opened_by: Pubkey,
+    bond_amount: u64,
+    now: i64,
+) -> Result<()> {
+    dispute.opened_by = opened_by;
+    dispute.arbiter = escrow.arbiter;
+    dispute.opened_at = now;
+    dispute.evidence_deadline = now
+        .checked_add(program_config.evidence_window)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    dispute.decision_deadline = dispute.evidence_deadline
+        .checked_add(program_config.decision_window)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    dispute.default_resolution = program_config.default_resolution;
+    dispute.bond_amount = bond_amount;
+    dispute.payer_bond = if opened_by == escrow.payer.addr { bond_amount } else { 0 };
+    dispute.receiver_bond = if opened_by == escrow.receiver.addr { bond_amount } else { 0 };
+    dispute.awarded_at = 0;
+    dispute.appeal_deadline = 0;
+    dispute.final_award = false;
+    dispute.payer_award = 0;
+    dispute.receiver_award = 0;
+    dispute.arbitration_fee = 0;
+    dispute.award_against = None;
+    dispute.rationale_hash = [0u8; 32];
+
+    Ok(())
+}
+
+//helper function to record an award split on the dispute
+fn record_award(
+    dispute: &mut Dispute,
+    escrow: &AsymEscrow,
+    payer_amount: u64,
+    charge_fee: bool,
+) -> Result<()> {
+    //validate award
+    let remaining_amount = escrow.get_amount_remaining();
+    require!(payer_amount <= remaining_amount, EscrowError::AmountExceeded);
+    let receiver_amount = remaining_amount - payer_amount;
+
+    //charge arbitration fee against the losing party (from their fee token deposit, if configured)
+    let arbitration_fee = if charge_fee {
+        escrow.arbitration_fee
+            .calculate(remaining_amount)
+            .ok_or(EscrowError::ArithmeticOverflow)?
+    } else {
+        0
+    };
+    let escrowed_fee = if escrow.has_fee_token() { 0 } else { arbitration_fee };
+    let (payer_award, receiver_award, award_against) =
+        split_arbitration_fee(payer_amount, receiver_amount, escrowed_fee)?;
+
+    dispute.payer_award = payer_award;
+    dispute.receiver_award = receiver_award;
+    dispute.arbitration_fee = arbitration_fee;
+    dispute.award_against = award_against;
+
+    Ok(())
+}
+
+//helper function to mark a dispute awarded; the award is final unless a second arbitration tier is available
+fn conclude_award(dispute: &mut Dispute, escrow: &AsymEscrow, now: i64) -> Result<()> {
+    dispute.transition(DisputeStatus::Awarded)?;
+    dispute.awarded_at = now;
+    if escrow.has_appeal_arbiter() {
+        dispute.appeal_deadline = now
+            .checked_add(APPEAL_WINDOW)
+            .ok_or(EscrowError::ArithmeticOverflow)?;
+    } else {
+        dispute.appeal_deadline = now;
+        dispute.final_award = true;
+    }
+
+    Ok(())
+}
+
+//helper function to build the structured award event
+fn award_event(escrow: &mut Account<AsymEscrow>, dispute: &Account<Dispute>, arbiter: Pubkey) -> ArbitrationAwardEvent {
+    //the losing party's bond covers arbitration costs (none on a default resolution)
+    let bond_forfeited_by = if dispute.defaulted { None } else { dispute.award_against };
+
+    ArbitrationAwardEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        dispute: dispute.key(),
+        arbiter,
+        payer_award: dispute.payer_award,
+        receiver_award: dispute.receiver_award,
+        arbitration_fee: dispute.arbitration_fee,
+        award_against: dispute.award_against,
+        payer_bond: dispute.payer_bond,
+        receiver_bond: dispute.receiver_bond,
+        bond_forfeited_by,
+        rationale_hash: dispute.rationale_hash,
+        final_award: dispute.final_award,
+        timestamp: dispute.awarded_at,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    }
+}
+
+//helper function to move awarded funds out of the vault
+fn transfer_award<'info>(
+    ctx: &Context<'_, '_, '_, 'info, ExecuteAwardAsym<'info>>,
+    payer_award: u64,
+    receiver_payout: u64,
+    arbitration_fee: u64,
+    protocol_fee: u64,
+    insurance_share: u64,
+) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    //transfer funds based on currency type
+    match escrow.payer.currency_type {
+        CurrencyType::Native => {
+            let vault = ctx.accounts.escrow_vault.to_account_info();
+            let system_program = ctx.accounts.system_program.to_account_info();
+
+            let transfers = [
+                (ctx.accounts.payer.to_account_info(), payer_award),
+                (ctx.accounts.receiver.to_account_info(), receiver_payout),
+                (ctx.accounts.arbiter.to_account_info(), arbitration_fee),
+                (ctx.accounts.fee_vault.to_account_info(), protocol_fee),
+            ];
+            let insurance_transfer = ctx.accounts.insurance_fund
+                .as_ref()
+                .map(|fund| (fund.to_account_info(), insurance_share));
+            for (to, amount) in transfers.into_iter().chain(insurance_transfer) {
+                if amount > 0 {
+                    transfer_from_native_vault(
+                        vault.clone(),
+                        to,
+                        amount,
+                        system_program.clone(),
+                        vault_signer,
+                    )?;
+                }
+            }
+        },
+        CurrencyType::SplToken | CurrencyType::Nft => {
+            let escrow_token_account = ctx.accounts.escrow_token_account
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
+            let token_mint = ctx.accounts.token_mint
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
+            let token_program = ctx.accounts.token_program
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
+            validate_transfer_hook(token_mint, &ctx.accounts.program_config, ctx.remaining_accounts)?;
+
+            let transfers = [
+                (&ctx.accounts.payer_token_account, payer_award),
+                (&ctx.accounts.receiver_token_account, receiver_payout),
+                (&ctx.accounts.arbiter_token_account, arbitration_fee),
+                (&ctx.accounts.fee_token_account, protocol_fee),
+            ];
+            for (to, amount) in transfers {
+                if amount > 0 {
+                    let to = to.as_ref().ok_or(EscrowError::InvalidToken)?;
+                    transfer_spl_tokens_signed(
+                        escrow_token_account,
+                        to,
+                        token_mint,
+                        ctx.accounts.escrow_vault.to_account_info(),
+                        amount,
+                        token_program,
+                        vault_signer,
+                        ctx.remaining_accounts,
+                    )?;
+                }
+            }
+        },
+        CurrencyType::ProgrammableNft => {
+            let escrow_token_account = ctx.accounts.escrow_token_account
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
+            let token_mint = ctx.accounts.token_mint
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
+            let token_program = ctx.accounts.token_program
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
+            let pnft = ProgrammableNftAccounts::from_remaining_accounts(ctx.remaining_accounts)?;
+
+            //the NFT goes whole to one party, through Metaplex
+            let transfers = [
+                (&ctx.accounts.payer_token_account, ctx.accounts.payer.to_account_info(), payer_award),
+                (&ctx.accounts.receiver_token_account, ctx.accounts.receiver.to_account_info(), receiver_payout),
+            ];
+            for (to, to_owner, amount) in transfers {
+                if amount > 0 {
+                    let to = to.as_ref().ok_or(EscrowError::InvalidToken)?;
+                    transfer_programmable_nft(
+                        &escrow_token_account.to_account_info(),
+                        &to.to_account_info(),
+                        &to_owner,
+                        &token_mint.to_account_info(),
+                        &ctx.accounts.escrow_vault.to_account_info(),
+                        &ctx.accounts.signer.to_account_info(),
+                        &token_program.to_account_info(),
+                        &ctx.accounts.system_program.to_account_info(),
+                        &pnft,
+                        vault_signer,
+                    )?;
+                }
+            }
+        },
+    }
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct DisputeOpenedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub dispute: Pubkey,
+    pub opened_by: Pubkey,
+    pub respondent: Pubkey,
+    pub arbiter: Pubkey,
+    pub disputed_amount: u64,
+    pub bond: u64,
+    pub evidence_deadline: i64,
+    pub decision_deadline: i64,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct DisputeAnsweredEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub respondent: Pubkey,
+    pub bond: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct EvidenceSubmittedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub submitted_by: Pubkey,
+    pub content_hash: [u8; 32],
+    pub scheme: EvidenceHashScheme,
+    pub index: u8,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AmountDisclosedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub dispute: Pubkey,
+    pub disclosed_by: Pubkey,
+    pub arbiter: Pubkey,
+    pub amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct DisputeStatusChangedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub dispute: Pubkey,
+    pub status: DisputeStatus,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ArbiterPanelAssignedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ArbitrationVoteCastEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub voter: Pubkey,
+    pub payer_amount: u64,
+    pub rationale_hash: [u8; 32],
+    pub votes_for: u8,
+    pub threshold: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ArbitrationAwardEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub dispute: Pubkey,
+    pub arbiter: Pubkey,
+    pub payer_award: u64,
+    pub receiver_award: u64,
+    pub arbitration_fee: u64,
+    pub award_against: Option<DisputeParty>,
+    pub payer_bond: u64,
+    pub receiver_bond: u64,
+    pub bond_forfeited_by: Option<DisputeParty>,
+    pub rationale_hash: [u8; 32],
+    pub final_award: bool,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct DisputeDefaultResolvedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub resolution: DefaultResolution,
+    pub payer_award: u64,
+    pub receiver_award: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AutomatedOutcomeSubmittedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub settler: Pubkey,
+    pub payer_award: u64,
+    pub receiver_award: u64,
+    pub rationale_hash: [u8; 32],
+    pub escalation_deadline: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AutomatedOutcomeEscalatedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub escalated_by: Pubkey,
+    pub settler: Pubkey,
+    pub arbiter: Pubkey,
+    pub bond: u64,
+    pub evidence_deadline: i64,
+    pub decision_deadline: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AwardAppealedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub appellant: Pubkey,
+    pub appeal_arbiter: Pubkey,
+    pub appeal_bond: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AppealDecidedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub appellant: Pubkey,
+    pub appeal_upheld: bool,
+    pub appeal_bond: u64,
+    pub bond_recipient: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct DisputeBondsSettledEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub payer_bond: u64,
+    pub payer_bond_to: Pubkey,
+    pub receiver_bond: u64,
+    pub receiver_bond_to: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AwardExecutedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub payer_award: u64,
+    pub receiver_payout: u64,
+    pub arbitration_fee: u64,
+    pub protocol_fee: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ArbitrationFeeDepositedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub depositor: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ArbitrationFeeSettledEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub arbiter: Pubkey,
+    pub arbiter_fee: u64,
+    pub payer_refund: u64,
+    pub receiver_refund: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -1,10 +1,30 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::associated_token::{self, AssociatedToken, get_associated_token_address_with_program_id};
+use anchor_spl::metadata::{Metadata, MetadataAccount, mpl_token_metadata::types::TokenStandard};
 use crate::state::*;
 use crate::errors::*;
 use crate::constants::*;
 use crate::instructions::utils::*;
+use crate::instructions::oracle::*;
+use crate::instructions::interest::*;
+use crate::instructions::wormhole::*;
+use crate::instructions::chaining::credit_chained_escrow;
+use crate::instructions::co_signers::{record_co_signer_assent, reset_co_signer_assents};
+use crate::instructions::relay::verify_relayed_intent;
+use crate::instructions::allowlist::check_counterparty_allowlist;
+use crate::instructions::attestation::*;
+use crate::instructions::blocklist::*;
+use crate::instructions::stats::{
+    update_category_stats, update_currency_stats, update_tvl_ledger, update_user_stats,
+    update_volume_history,
+};
+use crate::instructions::expiry::mark_expired_asym;
+use crate::instructions::settlement_hook::{invoke_settlement_hook, SettlementOutcome};
+use crate::instructions::memo::emit_escrow_memo;
+use crate::instructions::insurance::InsuranceFundDepositEvent;
+use crate::instructions::receipt::{burn_settled_receipt, mint_receipt, ReceiptMintAccounts, SettlementReceiptAccounts};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct CreateAsymEscrowParams {
@@ -15,9 +35,19 @@ pub struct CreateAsymEscrowParams {
     pub start_time: i64,
     pub end_time: i64,
     pub nonce: u64,
+    pub arbiter: Pubkey, // Pubkey::default() for no arbitration
+    pub arbitration_fee: ArbitrationFee,
+    pub appeal_arbiter: Pubkey, // Pubkey::default() for no appeals
+    pub arbiter_kind: ArbiterKind, // Individual, Jury or Governance; panels are assigned separately
+    pub automated_settler: Pubkey, // Pubkey::default() for no automated settlement
+    pub arbitration_fee_mint: Pubkey, // Pubkey::default() to pay arbitration fees from the escrowed funds
+    pub dispute_window: i64, // 0 for the config maximum
+    pub amount_commitment: [u8; 32], // [0; 32] for a public amount; amount must then be 0
+    pub category: EscrowCategory, // Uncategorized if not given
 }
 
 /// Create asymmetric escrow
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: CreateAsymEscrowParams)]
 pub struct CreateAsymEscrow<'info> {
@@ -40,7 +70,14 @@ pub struct CreateAsymEscrow<'info> {
     pub program_config: Account<'info, ProgramConfig>,
     
     /// Token mint (only required for SPL token escrows)
-    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
+    #[account(
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
+        bump
+    )]
+    pub payer_allowlist: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
 }
@@ -51,74 +88,124 @@ pub fn create_escrow(
 ) -> Result<()> {
     require_not_paused(&ctx.accounts.program_config)?;
     
-    //validate inputs
-    require!(params.payer != Pubkey::default(), EscrowError::InvalidPayer);
-    require!(params.receiver != Pubkey::default(), EscrowError::InvalidReceiver);
-    require!(params.payer != params.receiver, EscrowError::InvalidReceiver);
-    require!(params.amount > 0, EscrowError::InvalidAmount);
-    
-    //validate currency
-    if params.currency != Pubkey::default() {
-        require!(
-            ctx.accounts.token_mint.is_some(),
-            EscrowError::InvalidToken
-        );
+    //escrows with hidden terms keep the agreed amount off-chain behind its commitment
+    if params.amount_commitment != [0u8; 32] {
+        require!(params.amount == 0, EscrowError::InvalidAmount);
+    } else {
+        require!(params.amount > 0, EscrowError::InvalidAmount);
     }
     
-    //validate dates
-    validate_escrow_dates(params.start_time, params.end_time)?;
+    let (currency_type, decimals) = validate_escrow_currency(
+        params.currency,
+        ctx.accounts.token_mint.as_ref(),
+        &ctx.accounts.program_config,
+    )?;
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(&ctx.accounts.payer_allowlist, &params.receiver)?;
+    initialize_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        ctx.accounts.creator.key(),
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        currency_type,
+        decimals,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
+}
+
+/// Create asymmetric escrow on behalf of end users
+/// 
+/// A registered platform creates the escrow and pays its rent; the platform is recorded apart from the 
+/// parties, for fee-share attribution.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateAsymEscrowParams)]
+pub struct CreateEscrowForAsym<'info> {
+    #[account(mut)]
+    pub platform_authority: Signer<'info>,
     
-    //initialize escrow
-    let escrow = &mut ctx.accounts.escrow;
-    let escrow_id = generate_escrow_id(&ctx.accounts.creator.key(), params.nonce);
+    #[account(
+        seeds = [seeds::PLATFORM, platform_authority.key().as_ref()],
+        bump = platform.bump,
+        constraint = platform.authority == platform_authority.key() @ EscrowError::InvalidPlatform,
+    )]
+    pub platform: Account<'info, Platform>,
     
-    escrow.id = escrow_id;
-    escrow.payer = EscrowParty {
-        addr: params.payer,
-        currency: params.currency,
-        currency_type: if params.currency == Pubkey::default() {
-            CurrencyType::Native
-        } else {
-            CurrencyType::SplToken
-        },
-        amount: params.amount,
-        ..Default::default()
-    };
-    escrow.receiver = EscrowParty {
-        addr: params.receiver,
-        ..Default::default()
-    };
-    escrow.timestamp = Clock::get()?.unix_timestamp;
-    escrow.start_time = params.start_time;
-    escrow.end_time = params.end_time;
-    escrow.status = EscrowStatus::Pending;
-    escrow.released = false;
-    escrow.fee_bps = ctx.accounts.program_config.default_fee_bps;
-    escrow.creator = ctx.accounts.creator.key();
-    escrow.nonce = params.nonce;
-    escrow.bump = ctx.bumps.escrow;
+    #[account(
+        init,
+        payer = platform_authority,
+        space = AsymEscrow::space(),
+        seeds = [seeds::ASYM_ESCROW, platform_authority.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
     
-    emit!(EscrowCreatedEvent {
-        escrow_id,
-        creator: ctx.accounts.creator.key(),
-        payer: params.payer,
-        receiver: params.receiver,
-        amount: params.amount,
-    });
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
     
-    Ok(())
+    /// Token mint (only required for SPL token escrows)
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
+    #[account(
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
+        bump
+    )]
+    pub payer_allowlist: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_escrow_for(
+    ctx: Context<CreateEscrowForAsym>,
+    params: CreateAsymEscrowParams,
+) -> Result<()> {
+    create_platform_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        &ctx.accounts.platform,
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        ctx.accounts.token_mint.as_ref(),
+        &ctx.accounts.payer_allowlist,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
 }
 
-/// Place payment in asymmetric escrow
+/// Create asymmetric escrow from a partner marketplace program (CPI only)
+/// 
+/// The partner program signs as its partner authority PDA (seeds `[seeds::PARTNER_AUTHORITY]` under the 
+/// program), which only that program can do, so the platform registered for the PDA identifies the caller. 
+/// The platform's fee share and attribution are applied as for `create_escrow_for`; the rent is paid by any 
+/// signer the partner chooses, e.g. its end user.
+#[event_cpi]
 #[derive(Accounts)]
-pub struct PlacePaymentAsym<'info> {
+#[instruction(params: CreateAsymEscrowParams)]
+pub struct CreateEscrowViaPartnerAsym<'info> {
+    pub partner_authority: Signer<'info>,
+    
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub rent_payer: Signer<'info>,
     
     #[account(
-        mut,
-        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
-        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        seeds = [seeds::PLATFORM, partner_authority.key().as_ref()],
+        bump = platform.bump,
+        constraint = platform.authority == partner_authority.key() @ EscrowError::InvalidPlatform,
+        constraint = platform.is_partner_program() @ EscrowError::InvalidPlatform,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = rent_payer,
+        space = AsymEscrow::space(),
+        seeds = [seeds::ASYM_ESCROW, partner_authority.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
     )]
     pub escrow: Account<'info, AsymEscrow>,
     
@@ -128,115 +215,167 @@ pub struct PlacePaymentAsym<'info> {
     )]
     pub program_config: Account<'info, ProgramConfig>,
     
-    /// Escrow vault to hold funds
+    /// Token mint (only required for SPL token escrows)
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
     #[account(
-        mut,
-        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
         bump
     )]
-    pub escrow_vault: SystemAccount<'info>,
-    
-    /// For SPL token payments
-    #[account(mut)]
-    pub payer_token_account: Option<Account<'info, TokenAccount>>,
-    
-    #[account(mut)]
-    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    pub payer_allowlist: UncheckedAccount<'info>,
     
-    pub token_program: Option<Program<'info, Token>>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn place_payment(
-    ctx: Context<PlacePaymentAsym>,
-    amount: u64,
+pub fn create_escrow_via_partner(
+    ctx: Context<CreateEscrowViaPartnerAsym>,
+    params: CreateAsymEscrowParams,
 ) -> Result<()> {
-    require_not_paused(&ctx.accounts.program_config)?;
-    
-    let escrow = &mut ctx.accounts.escrow;
+    create_platform_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        &ctx.accounts.platform,
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        ctx.accounts.token_mint.as_ref(),
+        &ctx.accounts.payer_allowlist,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
+}
+
+//helper function to create an escrow on behalf of end users, attributed to the platform creating it
+#[allow(clippy::too_many_arguments)]
+fn create_platform_escrow(
+    escrow: &mut Account<AsymEscrow>,
+    params: &CreateAsymEscrowParams,
+    platform: &Platform,
+    bump: u8,
+    program_config: &ProgramConfig,
+    token_mint: Option<&InterfaceAccount<Mint>>,
+    payer_allowlist: &UncheckedAccount,
+    emitter: &EventEmitter,
+) -> Result<()> {
+    require_not_paused(program_config)?;
+    require!(params.amount > 0, EscrowError::InvalidAmount);
+    require!(params.amount_commitment == [0u8; 32], EscrowError::InvalidAmount);
     
-    //validate payer
+    //the platform is never one of the parties
+    let platform_authority = platform.authority;
     require!(
-        ctx.accounts.payer.key() == escrow.payer.addr,
-        EscrowError::Unauthorized
+        params.payer != platform_authority && params.receiver != platform_authority,
+        EscrowError::InvalidPlatform
     );
     
-    //check escrow timing
-    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
-    
-    //validate amount
-    require!(amount > 0, EscrowError::InvalidAmount);
+    let (currency_type, decimals) = validate_escrow_currency(params.currency, token_mint, program_config)?;
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(payer_allowlist, &params.receiver)?;
+    initialize_escrow(
+        escrow,
+        params,
+        platform_authority,
+        bump,
+        program_config,
+        currency_type,
+        decimals,
+        emitter,
+    )?;
+    
+    escrow.platform = platform_authority;
+    escrow.fee_terms.platform_fee_share_bps = platform.fee_share_bps;
+    
+    emitter.emit(EscrowCreatedForEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        platform: platform_authority,
+        payer: params.payer,
+        receiver: params.receiver,
+        amount: params.amount,
+        fee_share_bps: escrow.fee_terms.platform_fee_share_bps,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
     
-    //transfer payment based on currency type
-    match escrow.payer.currency_type {
-        CurrencyType::Native => {
-            //transfer SOL to escrow vault
-            transfer_native_sol(
-                ctx.accounts.payer.to_account_info(),
-                ctx.accounts.escrow_vault.to_account_info(),
-                amount,
-                ctx.accounts.system_program.to_account_info(),
-            )?;
-        },
-        CurrencyType::SplToken => {
-            //transfer SPL tokens to escrow token account
-            let payer_token_account = ctx.accounts.payer_token_account
-                .as_ref()
-                .ok_or(EscrowError::InvalidToken)?;
-            let escrow_token_account = ctx.accounts.escrow_token_account
-                .as_ref()
-                .ok_or(EscrowError::InvalidToken)?;
-            let token_program = ctx.accounts.token_program
-                .as_ref()
-                .ok_or(EscrowError::InvalidToken)?;
-            
-            transfer_spl_tokens(
-                payer_token_account,
-                escrow_token_account,
-                &ctx.accounts.payer,
-                amount,
-                token_program,
-            )?;
-        },
-    }
+    Ok(())
+}
+
+/// Create hash-time-locked escrow (HTLC)
+/// 
+/// The receiver claims the escrowed funds by presenting the preimage of the hashlock before the timelock; 
+/// after the timelock, the payer can reclaim them. Neither path needs the other party's consent.
+pub fn create_htlc_escrow(
+    ctx: Context<CreateAsymEscrow>,
+    params: CreateAsymEscrowParams,
+    hashlock: [u8; 32],
+    timelock: i64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    require!(params.amount > 0, EscrowError::InvalidAmount);
+    require!(params.amount_commitment == [0u8; 32], EscrowError::InvalidAmount);
     
-    //update escrow state
-    escrow.status = EscrowStatus::Active;
-    escrow.payer.amount_paid = escrow.payer.amount_paid
-        .checked_add(amount)
-        .ok_or(EscrowError::ArithmeticOverflow)?;
+    //validate hashlock and timelock
+    require!(hashlock != [0u8; 32], EscrowError::InvalidHashlock);
+    require!(
+        timelock > Clock::get()?.unix_timestamp && timelock > params.start_time,
+        EscrowError::InvalidTimelock
+    );
+    require!(params.end_time == 0 || timelock <= params.end_time, EscrowError::InvalidTimelock);
+    
+    let (currency_type, decimals) = validate_escrow_currency(
+        params.currency,
+        ctx.accounts.token_mint.as_ref(),
+        &ctx.accounts.program_config,
+    )?;
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(&ctx.accounts.payer_allowlist, &params.receiver)?;
+    initialize_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        ctx.accounts.creator.key(),
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        currency_type,
+        decimals,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
     
-    //check if fully paid
-    let is_fully_paid = escrow.payer.amount_paid >= escrow.payer.amount;
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.hashlock = hashlock;
+    escrow.timelock = timelock;
     
-    emit!(PaymentReceivedEvent {
+    emit_cpi!(HtlcCreatedEvent {
+        version: EVENT_VERSION,
         escrow_id: escrow.id,
-        payer: ctx.accounts.payer.key(),
-        amount,
-        total_paid: escrow.payer.amount_paid,
-        fully_paid: is_fully_paid,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        hashlock,
+        timelock,
+        reserved: [0; EVENT_RESERVED_BYTES],
     });
     
-    if is_fully_paid {
-        emit!(EscrowFullyPaidEvent {
-            escrow_id: escrow.id,
-            total_amount: escrow.payer.amount_paid,
-        });
-    }
-    
     Ok(())
 }
 
-/// Release escrow (consent-based)
+/// Create regulated asymmetric escrow, gated on both parties holding an attestation
+/// 
+/// Both parties must hold a valid attestation (a Solana Attestation Service credential or a designated 
+/// soulbound token) when the escrow is created and again whenever funds are released to the receiver.
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ReleaseEscrowAsym<'info> {
+#[instruction(params: CreateAsymEscrowParams)]
+pub struct CreateAttestedEscrowAsym<'info> {
     #[account(mut)]
-    pub signer: Signer<'info>,
+    pub creator: Signer<'info>,
     
     #[account(
-        mut,
-        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
-        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        init,
+        payer = creator,
+        space = AsymEscrow::space(),
+        seeds = [seeds::ASYM_ESCROW, creator.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
     )]
     pub escrow: Account<'info, AsymEscrow>,
     
@@ -246,89 +385,115 @@ pub struct ReleaseEscrowAsym<'info> {
     )]
     pub program_config: Account<'info, ProgramConfig>,
     
-    /// Escrow vault
+    /// Token mint (only required for SPL token escrows)
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
     #[account(
-        mut,
-        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
         bump
     )]
-    pub escrow_vault: SystemAccount<'info>,
-    
-    /// Receiver account for native transfers
-    #[account(mut)]
-    pub receiver: SystemAccount<'info>,
-    
-    /// Fee vault
-    #[account(mut)]
-    pub fee_vault: SystemAccount<'info>,
+    pub payer_allowlist: UncheckedAccount<'info>,
     
-    /// For SPL token transfers
-    #[account(mut)]
-    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
-    
-    #[account(mut)]
-    pub receiver_token_account: Option<Account<'info, TokenAccount>>,
+    /// CHECK: parties' SAS attestations or soulbound token accounts, checked by verify_attestation
+    pub payer_attestation: UncheckedAccount<'info>,
     
-    #[account(mut)]
-    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    /// CHECK: see payer_attestation
+    pub receiver_attestation: UncheckedAccount<'info>,
     
-    pub token_program: Option<Program<'info, Token>>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn release_escrow(ctx: Context<ReleaseEscrowAsym>) -> Result<()> {
+pub fn create_attested_escrow(
+    ctx: Context<CreateAttestedEscrowAsym>,
+    params: CreateAsymEscrowParams,
+    requirement: AttestationRequirement,
+) -> Result<()> {
     require_not_paused(&ctx.accounts.program_config)?;
+    require!(params.amount > 0, EscrowError::InvalidAmount);
+    require!(params.amount_commitment == [0u8; 32], EscrowError::InvalidAmount);
     
-    let escrow = &mut ctx.accounts.escrow;
-    
-    //check authorization (payer or receiver)
-    let is_payer = ctx.accounts.signer.key() == escrow.payer.addr;
-    let is_receiver = ctx.accounts.signer.key() == escrow.receiver.addr;
-    require!(is_payer || is_receiver, EscrowError::Unauthorized);
-    
-    //check escrow timing
-    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
-    
-    let remaining_amount = escrow.get_amount_remaining();
-    require!(remaining_amount > 0, EscrowError::InvalidEscrowState);
-    
-    //record consent
-    if is_payer && !escrow.payer.released {
-        escrow.payer.released = true;
-        emit!(ReleaseAssentGivenEvent {
-            escrow_id: escrow.id,
-            assenting_address: ctx.accounts.signer.key(),
-            assent_type: ReleaseAssentType::Payer,
-        });
-    }
+    //validate the requirement; SAS attestations are also bound to a schema
+    require!(
+        requirement.kind != AttestationKind::None && requirement.issuer != Pubkey::default(),
+        EscrowError::InvalidAttestation
+    );
+    require!(
+        (requirement.kind == AttestationKind::Sas) == (requirement.schema != Pubkey::default()),
+        EscrowError::InvalidAttestation
+    );
     
-    if is_receiver && !escrow.receiver.released {
-        escrow.receiver.released = true;
-        emit!(ReleaseAssentGivenEvent {
-            escrow_id: escrow.id,
-            assenting_address: ctx.accounts.signer.key(),
-            assent_type: ReleaseAssentType::Receiver,
-        });
-    }
+    let (currency_type, decimals) = validate_escrow_currency(
+        params.currency,
+        ctx.accounts.token_mint.as_ref(),
+        &ctx.accounts.program_config,
+    )?;
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(&ctx.accounts.payer_allowlist, &params.receiver)?;
+    initialize_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        ctx.accounts.creator.key(),
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        currency_type,
+        decimals,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
     
-    //execute release if both parties consent
-    if escrow.payer.released && escrow.receiver.released {
-        execute_release(ctx, remaining_amount)?;
-    }
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.attestation_kind = requirement.kind;
+    escrow.attestation_issuer = requirement.issuer;
+    escrow.attestation_schema = requirement.schema;
+    
+    //both parties must already be attested
+    verify_party_attestations(
+        escrow,
+        Some(&ctx.accounts.payer_attestation),
+        Some(&ctx.accounts.receiver_attestation),
+    )?;
+    
+    emit_cpi!(AttestationRequiredEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        kind: requirement.kind,
+        issuer: requirement.issuer,
+        schema: requirement.schema,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
     
     Ok(())
 }
 
-/// Release escrow (consent-based)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UsdEscrowParams {
+    pub usd_amount: u64, // with USD_DECIMALS decimals
+    pub oracle_source: OracleSource,
+    pub price_floor: u64, // lowest USD price per whole token accepted at settlement, 0 for no floor
+    pub price_ceiling: u64, // highest USD price per whole token accepted at settlement, 0 for no ceiling
+}
+
+/// Create asymmetric escrow whose obligation is denominated in USD
+/// 
+/// The SOL or token amount owed is converted from the USD amount through a Pyth or Switchboard price feed, 
+/// at deposit and again at settlement time. With Pyth pull oracles, a price update is posted in each of 
+/// these transactions and the escrow records its feed id. The last price used is kept on the escrow.
+#[event_cpi]
 #[derive(Accounts)]
-pub struct RefundEscrowAsym<'info> {
+#[instruction(params: CreateAsymEscrowParams)]
+pub struct CreateUsdEscrowAsym<'info> {
     #[account(mut)]
-    pub signer: Signer<'info>,
+    pub creator: Signer<'info>,
     
     #[account(
-        mut,
-        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
-        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        init,
+        payer = creator,
+        space = AsymEscrow::space(),
+        seeds = [seeds::ASYM_ESCROW, creator.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
     )]
     pub escrow: Account<'info, AsymEscrow>,
     
@@ -338,36 +503,1975 @@ pub struct RefundEscrowAsym<'info> {
     )]
     pub program_config: Account<'info, ProgramConfig>,
     
-    /// Escrow vault
+    /// CHECK: oracle feed or Pyth price update of the escrow currency in USD, parsed by load_oracle_price
+    pub price_feed: UncheckedAccount<'info>,
+    
+    /// Token mint (only required for SPL token escrows)
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
     #[account(
-        mut,
-        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
         bump
     )]
-    pub escrow_vault: SystemAccount<'info>,
+    pub payer_allowlist: UncheckedAccount<'info>,
     
-    /// Payer account for refunds
-    #[account(mut)]
-    pub payer: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_usd_escrow(
+    ctx: Context<CreateUsdEscrowAsym>,
+    params: CreateAsymEscrowParams,
+    usd_params: UsdEscrowParams,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
     
-    /// For SPL token refunds
-    #[account(mut)]
-    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    //the currency amount is derived from the USD amount
+    require!(usd_params.usd_amount > 0, EscrowError::InvalidAmount);
+    require!(params.amount == 0, EscrowError::InvalidAmount);
+    require!(params.amount_commitment == [0u8; 32], EscrowError::InvalidAmount);
     
-    #[account(mut)]
-    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+    //validate price collar
+    require!(
+        usd_params.price_floor == 0
+            || usd_params.price_ceiling == 0
+            || usd_params.price_floor <= usd_params.price_ceiling,
+        EscrowError::InvalidPriceCollar
+    );
     
-    pub token_program: Option<Program<'info, Token>>,
-    pub system_program: Program<'info, System>,
+    let (currency_type, decimals) = validate_escrow_currency(
+        params.currency,
+        ctx.accounts.token_mint.as_ref(),
+        &ctx.accounts.program_config,
+    )?;
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(&ctx.accounts.payer_allowlist, &params.receiver)?;
+    initialize_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        ctx.accounts.creator.key(),
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        currency_type,
+        decimals,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.usd_amount = usd_params.usd_amount;
+    escrow.price_feed = oracle_feed_id(usd_params.oracle_source, &ctx.accounts.price_feed)?;
+    escrow.oracle_source = usd_params.oracle_source;
+    escrow.price_floor = usd_params.price_floor;
+    escrow.price_ceiling = usd_params.price_ceiling;
+    
+    //quote the obligation up front, which also checks the feed is live
+    let (amount, _) = quote_usd_amount(
+        escrow,
+        Some(&ctx.accounts.price_feed),
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    escrow.payer.amount = amount;
+    
+    Ok(())
+}
+
+/// Create asymmetric escrow payable in any of several approved mints
+/// 
+/// The escrow starts out in the first accepted mint; the payer may switch to another accepted mint with 
+/// the first deposit, after which the escrow is locked to that mint. Mint accounts are passed as remaining 
+/// accounts, in the order of the accepted mints.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateAsymEscrowParams)]
+pub struct CreateMultiMintEscrowAsym<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = AsymEscrow::space(),
+        seeds = [seeds::ASYM_ESCROW, creator.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
+    #[account(
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
+        bump
+    )]
+    pub payer_allowlist: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_multi_mint_escrow<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateMultiMintEscrowAsym<'info>>,
+    params: CreateAsymEscrowParams,
+    accepted_mints: Vec<AcceptedMint>,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //amounts are set per mint
+    require!(params.amount == 0, EscrowError::InvalidAmount);
+    require!(params.amount_commitment == [0u8; 32], EscrowError::InvalidAmount);
+    require!(
+        accepted_mints.len() >= 2 && accepted_mints.len() <= MAX_ACCEPTED_MINTS,
+        EscrowError::InvalidToken
+    );
+    require!(
+        ctx.remaining_accounts.len() == accepted_mints.len(),
+        EscrowError::InvalidToken
+    );
+    
+    //validate each mint, rejecting duplicates
+    for (i, accepted) in accepted_mints.iter().enumerate() {
+        require!(accepted.amount > 0, EscrowError::InvalidAmount);
+        require!(
+            !accepted_mints[..i].iter().any(|m| m.mint == accepted.mint),
+            EscrowError::InvalidToken
+        );
+        
+        let token_mint = InterfaceAccount::<Mint>::try_from(&ctx.remaining_accounts[i])?;
+        let (currency_type, _) =
+            validate_escrow_currency(accepted.mint, Some(&token_mint), &ctx.accounts.program_config)?;
+        require!(currency_type == CurrencyType::SplToken, EscrowError::InvalidToken);
+    }
+    let decimals = InterfaceAccount::<Mint>::try_from(&ctx.remaining_accounts[0])?.decimals;
+    
+    //the escrow starts out in the first accepted mint
+    let params = CreateAsymEscrowParams {
+        currency: accepted_mints[0].mint,
+        amount: accepted_mints[0].amount,
+        ..params
+    };
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(&ctx.accounts.payer_allowlist, &params.receiver)?;
+    initialize_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        ctx.accounts.creator.key(),
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        CurrencyType::SplToken,
+        decimals,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    ctx.accounts.escrow.accepted_mints = accepted_mints;
+    
+    Ok(())
+}
+
+/// Create asymmetric escrow of a Metaplex NFT
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateAsymEscrowParams)]
+pub struct CreateNftEscrowAsym<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = AsymEscrow::space(),
+        seeds = [seeds::ASYM_ESCROW, creator.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// NFT mint, escrowed as the payer's currency
+    #[account(
+        address = params.currency @ EscrowError::InvalidToken,
+        constraint = nft_mint.decimals == 0 && nft_mint.supply == 1 @ EscrowError::InvalidNft,
+    )]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Metaplex metadata of the NFT
+    #[account(
+        seeds = [b"metadata", Metadata::id().as_ref(), nft_mint.key().as_ref()],
+        seeds::program = Metadata::id(),
+        bump
+    )]
+    pub nft_metadata: Account<'info, MetadataAccount>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
+    #[account(
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
+        bump
+    )]
+    pub payer_allowlist: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_nft_escrow(
+    ctx: Context<CreateNftEscrowAsym>,
+    params: CreateAsymEscrowParams,
+    collection: Pubkey,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //an NFT escrow holds exactly one token, in the open
+    require!(
+        params.amount == 1 && params.amount_commitment == [0u8; 32],
+        EscrowError::InvalidAmount
+    );
+    
+    //arbitration fees can't be taken out of the NFT itself
+    require!(
+        params.arbitration_fee.value == 0 || params.arbitration_fee_mint != Pubkey::default(),
+        EscrowError::InvalidArbitrationFee
+    );
+    
+    //validate metadata and collection
+    validate_nft_metadata(&ctx.accounts.nft_metadata, collection)?;
+    let currency_type = if ctx.accounts.nft_metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible) {
+        CurrencyType::ProgrammableNft
+    } else {
+        CurrencyType::Nft
+    };
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(&ctx.accounts.payer_allowlist, &params.receiver)?;
+    initialize_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        ctx.accounts.creator.key(),
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        currency_type,
+        ctx.accounts.nft_mint.decimals,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    ctx.accounts.escrow.nft_collection = collection;
+    
+    Ok(())
+}
+
+/// Create asymmetric escrow and mint its receipt NFT to the receiver in the same instruction
+/// 
+/// Takes the accounts of `mint_escrow_receipt_asym` alongside those of `create_asym_escrow`.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateAsymEscrowParams)]
+pub struct CreateReceiptEscrowAsym<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = AsymEscrow::space(),
+        seeds = [seeds::ASYM_ESCROW, creator.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Token mint (only required for SPL token escrows)
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: payer's counterparty allowlist PDA, enforced by check_counterparty_allowlist if the payer opted in
+    #[account(
+        seeds = [seeds::COUNTERPARTY_ALLOWLIST, params.payer.as_ref()],
+        bump
+    )]
+    pub payer_allowlist: UncheckedAccount<'info>,
+    
+    /// Escrow vault, authority of the receipt mint
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: receipt mint, created and initialized by this instruction
+    #[account(
+        mut,
+        seeds = [seeds::RECEIPT_MINT, escrow.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: receiver, owner of the receipt token account; may be a program-owned multisig vault
+    #[account(
+        address = params.receiver @ EscrowError::InvalidReceiver
+    )]
+    pub receiver: UncheckedAccount<'info>,
+    
+    /// CHECK: receiver's associated token account for the receipt, created by this instruction
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &receiver.key(),
+            &receipt_mint.key(),
+            &receipt_token_program.key(),
+        ) @ EscrowError::InvalidToken
+    )]
+    pub receiver_receipt_account: UncheckedAccount<'info>,
+    
+    pub receipt_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_receipt_escrow(
+    ctx: Context<CreateReceiptEscrowAsym>,
+    params: CreateAsymEscrowParams,
+    uri: String,
+    transferable: bool,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //escrows with hidden terms keep the agreed amount off-chain behind its commitment
+    if params.amount_commitment != [0u8; 32] {
+        require!(params.amount == 0, EscrowError::InvalidAmount);
+    } else {
+        require!(params.amount > 0, EscrowError::InvalidAmount);
+    }
+    
+    let (currency_type, decimals) = validate_escrow_currency(
+        params.currency,
+        ctx.accounts.token_mint.as_ref(),
+        &ctx.accounts.program_config,
+    )?;
+    
+    //the payer may be restricted to allowlisted receivers
+    check_counterparty_allowlist(&ctx.accounts.payer_allowlist, &params.receiver)?;
+    let emitter = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    initialize_escrow(
+        &mut ctx.accounts.escrow,
+        &params,
+        ctx.accounts.creator.key(),
+        ctx.bumps.escrow,
+        &ctx.accounts.program_config,
+        currency_type,
+        decimals,
+        &emitter,
+    )?;
+    
+    mint_receipt(
+        &mut ctx.accounts.escrow,
+        ReceiptMintAccounts {
+            creator: &ctx.accounts.creator,
+            escrow_vault: &ctx.accounts.escrow_vault,
+            vault_bump: ctx.bumps.escrow_vault,
+            receipt_mint: &ctx.accounts.receipt_mint,
+            mint_bump: ctx.bumps.receipt_mint,
+            receiver: &ctx.accounts.receiver,
+            receiver_receipt_account: &ctx.accounts.receiver_receipt_account,
+            token_program: &ctx.accounts.receipt_token_program,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        uri,
+        transferable,
+        &emitter,
+    )
+}
+
+/// Deposit the NFT of an NFT escrow into its vault-owned token account
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositNftAsym<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.currency_type == CurrencyType::Nft @ EscrowError::InvalidToken,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, owner of the NFT token account
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = payer,
+        token::token_program = token_program,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = payer,
+        token::mint = nft_mint,
+        token::authority = escrow_vault,
+        token::token_program = token_program,
+        seeds = [seeds::NFT_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit_nft<'info>(ctx: Context<'_, '_, '_, 'info, DepositNftAsym<'info>>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //validate payer
+    require!(
+        ctx.accounts.payer.key() == escrow.payer.addr,
+        EscrowError::Unauthorized
+    );
+    
+    //check escrow timing
+    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
+    
+    //move the NFT into the vault-owned token account
+    transfer_spl_tokens(
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.payer,
+        1,
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+    )?;
+    
+    //update escrow state
+    if let Some(old) = escrow.set_status(EscrowStatus::Active) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Active,
+            actor: ctx.accounts.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    escrow.payer.amount_paid = 1;
+    
+    log_escrow_action(log_actions::DEPOSIT, &escrow.id, &[("amount", 1)]);
+    emit_cpi!(NftDepositedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        payer: ctx.accounts.payer.key(),
+        mint: ctx.accounts.nft_mint.key(),
+        vault_token_account: ctx.accounts.escrow_token_account.key(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    emit_cpi!(EscrowFullyPaidEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        total_amount: escrow.payer.amount_paid,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Deposit the programmable NFT of an NFT escrow into the escrow vault's associated token account
+/// 
+/// The Metaplex accounts are passed as remaining accounts (see `ProgrammableNftAccounts`).
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositProgrammableNftAsym<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.currency_type == CurrencyType::ProgrammableNft @ EscrowError::InvalidToken,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, owner of the NFT token account
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = payer,
+        token::token_program = token_program,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: escrow vault's associated token account, created by the token metadata program
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &escrow_vault.key(),
+            &nft_mint.key(),
+            &token_program.key(),
+        ) @ EscrowError::InvalidToken
+    )]
+    pub escrow_token_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit_programmable_nft<'info>(ctx: Context<'_, '_, '_, 'info, DepositProgrammableNftAsym<'info>>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //validate payer
+    require!(
+        ctx.accounts.payer.key() == ctx.accounts.escrow.payer.addr,
+        EscrowError::Unauthorized
+    );
+    
+    //check escrow timing
+    require!(ctx.accounts.escrow.is_active_time(), EscrowError::EscrowNotActive);
+    
+    //move the NFT into the vault's token account through Metaplex
+    let pnft = ProgrammableNftAccounts::from_remaining_accounts(ctx.remaining_accounts)?;
+    let payer = ctx.accounts.payer.to_account_info();
+    transfer_programmable_nft(
+        &ctx.accounts.payer_token_account.to_account_info(),
+        &ctx.accounts.escrow_token_account.to_account_info(),
+        &ctx.accounts.escrow_vault.to_account_info(),
+        &ctx.accounts.nft_mint.to_account_info(),
+        &payer,
+        &payer,
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &pnft,
+        &[],
+    )?;
+    
+    //update escrow state
+    let escrow = &mut ctx.accounts.escrow;
+    if let Some(old) = escrow.set_status(EscrowStatus::Active) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Active,
+            actor: ctx.accounts.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    escrow.payer.amount_paid = 1;
+    
+    log_escrow_action(log_actions::DEPOSIT, &escrow.id, &[("amount", 1)]);
+    emit_cpi!(NftDepositedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        payer: ctx.accounts.payer.key(),
+        mint: ctx.accounts.nft_mint.key(),
+        vault_token_account: ctx.accounts.escrow_token_account.key(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    emit_cpi!(EscrowFullyPaidEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        total_amount: escrow.payer.amount_paid,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Place a native SOL payment in asymmetric escrow
+/// 
+/// The payer can be another program holding the escrow position directly: it signs for its PDA with 
+/// `invoke_signed` and pays from a system-owned PDA. Token escrows are paid through `place_token_payment_asym`, 
+/// so neither context carries the other currency's accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlacePaymentAsym<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = !escrow.external_call_in_progress @ EscrowError::ReentrantCall,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault to hold funds
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: oracle feed or Pyth price update (only required for USD-denominated escrows), matched against 
+    /// the escrow's feed and parsed by load_oracle_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: blocklist entries of the payer and receiver, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: see payer_blocklist_entry
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: statistics of the escrow currency, updated by update_currency_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CURRENCY_STATS, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub currency_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: counters of the escrow category, updated by update_category_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CATEGORY_STATS, &[escrow.category as u8]],
+        bump
+    )]
+    pub category_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: daily volume history of the escrow currency, updated by update_volume_history if kept
+    #[account(
+        mut,
+        seeds = [seeds::VOLUME_HISTORY, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub volume_history: UncheckedAccount<'info>,
+    
+    /// CHECK: global TVL ledger, updated by update_tvl_ledger once it exists
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL Memo program (only required if the escrow has memos turned on)
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn place_payment(ctx: Context<PlacePaymentAsym>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let emitter = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //NFTs are deposited whole through deposit_nft or deposit_programmable_nft
+    require!(escrow.payer.currency_type == CurrencyType::Native, EscrowError::InvalidToken);
+    open_payment(
+        escrow,
+        &ctx.accounts.payer.key(),
+        amount,
+        &[&ctx.accounts.payer_blocklist_entry, &ctx.accounts.receiver_blocklist_entry],
+        &emitter,
+    )?;
+    
+    //transfer SOL to escrow vault, with its rent reserve on the first deposit
+    let rent_reserve = vault_rent_reserve()?.saturating_sub(ctx.accounts.escrow_vault.lamports());
+    transfer_native_sol(
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.escrow_vault.to_account_info(),
+        amount.checked_add(rent_reserve).ok_or(EscrowError::ArithmeticOverflow)?,
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    record_payment(
+        escrow,
+        &ctx.accounts.payer.key(),
+        amount,
+        amount,
+        ctx.accounts.price_feed.as_ref(),
+        PaymentStats {
+            currency_stats: &ctx.accounts.currency_stats,
+            category_stats: &ctx.accounts.category_stats,
+            volume_history: &ctx.accounts.volume_history,
+            tvl_ledger: &ctx.accounts.tvl_ledger,
+        },
+        ctx.accounts.memo_program.as_ref(),
+        ctx.accounts.program_config.compact_events,
+        &emitter,
+    )
+}
+
+/// Place an SPL token payment in asymmetric escrow
+/// 
+/// The payer can be another program holding the escrow position directly, paying from a PDA-owned token 
+/// account. Multi-mint escrows take any accepted mint with the first deposit, and are locked to it after. 
+/// The escrow's token account (the vault's associated token account) is created by the first deposit, so 
+/// escrows that are never funded never pay its rent.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceTokenPaymentAsym<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = !escrow.external_call_in_progress @ EscrowError::ReentrantCall,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, owner of the escrow's token account
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == token_mint.key() @ EscrowError::InvalidToken
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: escrow vault's associated token account, created by the first deposit in the mint
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &escrow_vault.key(),
+            &token_mint.key(),
+            &token_program.key(),
+        ) @ EscrowError::InvalidToken
+    )]
+    pub escrow_token_account: UncheckedAccount<'info>,
+    
+    /// Token mint (the escrow's currency, or any accepted mint before the first deposit)
+    #[account(
+        constraint = token_mint.key() == escrow.payer.currency
+            || escrow.can_select_mint(&token_mint.key()) @ EscrowError::InvalidToken
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: oracle feed or Pyth price update (only required for USD-denominated escrows), matched against 
+    /// the escrow's feed and parsed by load_oracle_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: blocklist entries of the payer and receiver, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: see payer_blocklist_entry
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: statistics of the deposit mint, updated by update_currency_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CURRENCY_STATS, token_mint.key().as_ref()],
+        bump
+    )]
+    pub currency_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: counters of the escrow category, updated by update_category_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CATEGORY_STATS, &[escrow.category as u8]],
+        bump
+    )]
+    pub category_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: daily volume history of the deposit mint, updated by update_volume_history if kept
+    #[account(
+        mut,
+        seeds = [seeds::VOLUME_HISTORY, token_mint.key().as_ref()],
+        bump
+    )]
+    pub volume_history: UncheckedAccount<'info>,
+    
+    /// CHECK: global TVL ledger, updated by update_tvl_ledger once it exists
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL Memo program (only required if the escrow has memos turned on)
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn place_token_payment<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlaceTokenPaymentAsym<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let emitter = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    let escrow = &mut ctx.accounts.escrow;
+    
+    require!(escrow.payer.currency_type == CurrencyType::SplToken, EscrowError::InvalidToken);
+    open_payment(
+        escrow,
+        &ctx.accounts.payer.key(),
+        amount,
+        &[&ctx.accounts.payer_blocklist_entry, &ctx.accounts.receiver_blocklist_entry],
+        &emitter,
+    )?;
+    
+    //multi-mint escrows lock to the mint chosen with the first deposit (or swapped into since)
+    let token_mint = &ctx.accounts.token_mint;
+    if token_mint.key() != escrow.payer.currency {
+        require!(escrow.payer.amount_paid == 0, EscrowError::InvalidToken);
+        let accepted = *escrow.accepted_mints
+            .iter()
+            .find(|m| m.mint == token_mint.key())
+            .ok_or(EscrowError::InvalidToken)?;
+        escrow.payer.currency = accepted.mint;
+        escrow.payer.amount = accepted.amount;
+        escrow.payer.decimals = token_mint.decimals;
+        
+        emitter.emit(PaymentMintSelectedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            mint: accepted.mint,
+            amount: accepted.amount,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    
+    require!(token_mint.decimals == escrow.payer.decimals, EscrowError::InvalidDecimals);
+    validate_transfer_hook(token_mint, &ctx.accounts.program_config, ctx.remaining_accounts)?;
+    
+    //the vault's token account is created with the first deposit in its mint
+    associated_token::create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.payer.to_account_info(),
+            associated_token: ctx.accounts.escrow_token_account.to_account_info(),
+            authority: ctx.accounts.escrow_vault.to_account_info(),
+            mint: token_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+    let escrow_token_account = &ctx.accounts.escrow_token_account;
+    let escrow_balance = || -> Result<u64> {
+        Ok(TokenAccount::try_deserialize(&mut &escrow_token_account.try_borrow_data()?[..])?.amount)
+    };
+    
+    //transfer SPL tokens to escrow token account, recording what it actually received
+    let balance_before = escrow_balance()?;
+    transfer_spl_tokens(
+        &ctx.accounts.payer_token_account,
+        escrow_token_account,
+        token_mint,
+        &ctx.accounts.payer,
+        amount,
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+    )?;
+    
+    //transfer fee extensions withhold part of the amount at the destination
+    let amount_received = escrow_balance()?
+        .checked_sub(balance_before)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    require!(amount_received > 0, EscrowError::InvalidAmount);
+    
+    record_payment(
+        escrow,
+        &ctx.accounts.payer.key(),
+        amount,
+        amount_received,
+        ctx.accounts.price_feed.as_ref(),
+        PaymentStats {
+            currency_stats: &ctx.accounts.currency_stats,
+            category_stats: &ctx.accounts.category_stats,
+            volume_history: &ctx.accounts.volume_history,
+            tvl_ledger: &ctx.accounts.tvl_ledger,
+        },
+        ctx.accounts.memo_program.as_ref(),
+        ctx.accounts.program_config.compact_events,
+        &emitter,
+    )
+}
+
+//tracking accounts a deposit is recorded on
+struct PaymentStats<'a, 'info> {
+    currency_stats: &'a UncheckedAccount<'info>,
+    category_stats: &'a UncheckedAccount<'info>,
+    volume_history: &'a UncheckedAccount<'info>,
+    tvl_ledger: &'a UncheckedAccount<'info>,
+}
+
+//helper function to check a deposit can be taken, returning false if it is held back for involving a 
+//sanctioned address (with the block on record)
+fn open_payment(
+    escrow: &mut Account<AsymEscrow>,
+    payer: &Pubkey,
+    amount: u64,
+    blocklist_entries: &[&UncheckedAccount],
+    emitter: &EventEmitter,
+) -> Result<()> {
+    //validate payer (or its delegate within its limit, or its session key)
+    let slot = Clock::get()?.slot;
+    require!(
+        escrow.payer.can_act(payer, amount, session_permissions::DEPOSIT, slot),
+        EscrowError::Unauthorized
+    );
+    
+    //check escrow timing
+    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
+    
+    //validate amount
+    require!(amount > 0, EscrowError::InvalidAmount);
+    
+    //deposits from or to a sanctioned address are rejected
+    require_not_blocked(blocklist_entries, &escrow.id, BlockedTransfer::Deposit)?;
+    
+    //late invoices owe interest on the balance left unpaid up to now
+    crystallize_interest(escrow, Clock::get()?.unix_timestamp, emitter)?;
+    
+    Ok(())
+}
+
+//helper function to record a deposit the vault received on the escrow and the tracking accounts
+#[allow(clippy::too_many_arguments)]
+fn record_payment<'info>(
+    escrow: &mut Account<'info, AsymEscrow>,
+    payer: &Pubkey,
+    amount: u64,
+    amount_received: u64,
+    price_feed: Option<&UncheckedAccount<'info>>,
+    stats: PaymentStats<'_, 'info>,
+    memo_program: Option<&UncheckedAccount<'info>>,
+    compact_events: bool,
+    emitter: &EventEmitter,
+) -> Result<()> {
+    //USD-denominated escrows owe the USD amount at the current price
+    if escrow.is_usd_denominated() {
+        let (amount, _) = quote_usd_amount(escrow, price_feed, emitter)?;
+        escrow.payer.amount = amount;
+    }
+    
+    //update escrow state
+    let activated = escrow.set_status(EscrowStatus::Active);
+    if let Some(old) = activated {
+        emitter.emit(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Active,
+            actor: *payer,
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    escrow.payer.amount_paid = escrow.payer.amount_paid
+        .checked_add(amount_received)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    update_currency_stats(stats.currency_stats, |currency_stats, now| {
+        currency_stats.record_deposit(amount_received, activated.is_some(), now)
+    })?;
+    if activated.is_some() {
+        update_category_stats(stats.category_stats, |category_stats, now| category_stats.record_funded(now))?;
+    }
+    update_tvl_ledger(stats.tvl_ledger, &escrow.payer.currency, amount_received, 0)?;
+    update_volume_history(stats.volume_history, amount_received, 0)?;
+    
+    //check if fully paid (unknown on-chain when the terms are hidden, though the deposits themselves are public)
+    let is_fully_paid = !escrow.has_hidden_terms() && escrow.payer.amount_paid >= escrow.payer.amount;
+    
+    log_escrow_action(log_actions::DEPOSIT, &escrow.id, &[
+        ("amount", amount_received),
+        ("total_paid", escrow.payer.amount_paid),
+    ]);
+    emit_escrow_memo(escrow, memo_program, log_actions::DEPOSIT, amount_received)?;
+    
+    //one compact event stands in for both payment events
+    if compact_events {
+        return emitter.emit(CompactPaymentEvent {
+            version: EVENT_VERSION,
+            action: if is_fully_paid { compact_actions::DEPOSIT_FULLY_PAID } else { compact_actions::DEPOSIT },
+            escrow: escrow.key(),
+            seq: escrow.next_event_seq(),
+            amount: amount_received,
+        });
+    }
+    emitter.emit(PaymentReceivedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        payer: *payer,
+        amount: amount_received,
+        transfer_fee: amount.saturating_sub(amount_received),
+        total_paid: escrow.payer.amount_paid,
+        fully_paid: is_fully_paid,
+        decimals: escrow.payer.decimals,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    if is_fully_paid {
+        emitter.emit(EscrowFullyPaidEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            total_amount: escrow.payer.amount_paid,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    
+    Ok(())
+}
+
+/// Release a native SOL escrow (consent-based)
+/// 
+/// Parties that are programs consent like any other party, by signing for their PDA through a CPI. Token 
+/// escrows are released through `release_token_escrow_asym`, so neither context carries the other currency's 
+/// accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseEscrowAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = !escrow.external_call_in_progress @ EscrowError::ReentrantCall,
+        constraint = escrow.payer.currency_type == CurrencyType::Native @ EscrowError::InvalidToken,
+    )]
+    pub escrow: Box<Account<'info, AsymEscrow>>,
+    
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+    
+    /// Escrow vault
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: receiver account for native transfers (the chained escrow's vault, or the claim token holder 
+    /// if the claim is tokenized); may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = payout_recipient(&escrow, claim_token_account.as_deref()) @ EscrowError::InvalidReceiver
+    )]
+    pub receiver: UncheckedAccount<'info>,
+    
+    /// Fee vault
+    #[account(
+        mut,
+        address = program_config.fee_vault @ EscrowError::InvalidConfig
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    /// Insurance fund, receives its share of native protocol fees
+    #[account(
+        mut,
+        seeds = [seeds::INSURANCE_FUND],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+    
+    /// Account holding the escrow's claim token (only required if the claim is tokenized)
+    pub claim_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Escrow the release is paid into (only required for chained escrows)
+    #[account(
+        mut,
+        address = escrow.chained_escrow @ EscrowError::InvalidEscrowChain
+    )]
+    pub chained_escrow: Option<Box<Account<'info, AsymEscrow>>>,
+    
+    /// Party reputations, updated when the escrow completes
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.payer.addr.as_ref()],
+        bump = payer_reputation.bump
+    )]
+    pub payer_reputation: Option<Box<Account<'info, Reputation>>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.receiver.addr.as_ref()],
+        bump = receiver_reputation.bump
+    )]
+    pub receiver_reputation: Option<Box<Account<'info, Reputation>>>,
+    
+    /// Receipt mint of the escrow (only required if the escrow has a receipt), burned once it completes
+    #[account(
+        mut,
+        address = escrow.receipt_mint @ EscrowError::InvalidToken
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token account holding the escrow's receipt (only required if the escrow has a receipt)
+    #[account(
+        mut,
+        constraint = receipt_token_account.mint == escrow.receipt_mint @ EscrowError::InvalidToken,
+    )]
+    pub receipt_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token-2022 program of the receipt (only required if the escrow has a receipt)
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+    
+    /// CHECK: oracle feed or Pyth price update (only required for USD-denominated escrows), matched against 
+    /// the escrow's feed and parsed by load_oracle_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Wormhole posted VAA (only required for cross-chain releases), parsed by load_posted_vaa
+    pub posted_vaa: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: instructions sysvar (only required for relayed intents), read by verify_relayed_intent
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: parties' attestations (only required for regulated escrows), checked by verify_attestation
+    pub payer_attestation: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: see payer_attestation
+    pub receiver_attestation: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: blocklist entries of the payer and receiver, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: see payer_blocklist_entry
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: blocklist entry of the payout recipient, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, receiver.key().as_ref()],
+        bump
+    )]
+    pub payout_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: statistics of the escrow currency, updated by update_currency_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CURRENCY_STATS, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub currency_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: counters of the escrow category, updated by update_category_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CATEGORY_STATS, &[escrow.category as u8]],
+        bump
+    )]
+    pub category_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: daily volume history of the escrow currency, updated by update_volume_history if kept
+    #[account(
+        mut,
+        seeds = [seeds::VOLUME_HISTORY, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub volume_history: UncheckedAccount<'info>,
+    
+    /// CHECK: global TVL ledger, updated by update_tvl_ledger once it exists
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: UncheckedAccount<'info>,
+    
+    /// CHECK: parties' lifetime counters, updated by update_user_stats if they exist
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: see payer_stats
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: settlement hook program (only required if the escrow has one), called by invoke_settlement_hook
+    #[account(
+        executable,
+        address = escrow.settlement_hook @ EscrowError::SettlementHookNotAllowed
+    )]
+    pub settlement_hook: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: PDA signing the settlement hook call
+    #[account(
+        seeds = [seeds::SETTLEMENT_HOOK_AUTHORITY],
+        bump
+    )]
+    pub settlement_hook_authority: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: state account of the settlement hook, passed through to it unchecked
+    #[account(mut)]
+    pub settlement_hook_state: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: SPL Memo program (only required if the escrow has memos turned on)
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_escrow<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>) -> Result<()> {
+    trigger_release(ctx, ReleaseTrigger::Consent)
+}
+
+/// Release an SPL token or NFT escrow (consent-based)
+/// 
+/// Takes the same parties and triggers as `release_escrow_asym`, with the escrow's token accounts, mint and 
+/// token program as required accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseTokenEscrowAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = !escrow.external_call_in_progress @ EscrowError::ReentrantCall,
+        constraint = escrow.payer.currency_type != CurrencyType::Native @ EscrowError::InvalidToken,
+    )]
+    pub escrow: Box<Account<'info, AsymEscrow>>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+    
+    /// Escrow vault, owner of the escrow token account
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: owner of the receiver token account (the chained escrow's vault, or the claim token holder if 
+    /// the claim is tokenized); may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = payout_recipient(&escrow, claim_token_account.as_deref()) @ EscrowError::InvalidReceiver
+    )]
+    pub receiver: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == escrow_vault.key()
+            && escrow_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = receiver_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub receiver_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Account holding the escrow's claim token (only required if the claim is tokenized)
+    pub claim_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Escrow the release is paid into (only required for chained escrows)
+    #[account(
+        mut,
+        address = escrow.chained_escrow @ EscrowError::InvalidEscrowChain
+    )]
+    pub chained_escrow: Option<Box<Account<'info, AsymEscrow>>>,
+    
+    /// Fee vault's token account of the escrow mint (only required if the release carries a fee, which a 
+    /// single NFT never does)
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == program_config.fee_vault
+            && fee_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub fee_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Party reputations, updated when the escrow completes
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.payer.addr.as_ref()],
+        bump = payer_reputation.bump
+    )]
+    pub payer_reputation: Option<Box<Account<'info, Reputation>>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.receiver.addr.as_ref()],
+        bump = receiver_reputation.bump
+    )]
+    pub receiver_reputation: Option<Box<Account<'info, Reputation>>>,
+    
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// Receipt mint of the escrow (only required if the escrow has a receipt), burned once it completes
+    #[account(
+        mut,
+        address = escrow.receipt_mint @ EscrowError::InvalidToken
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token account holding the escrow's receipt (only required if the escrow has a receipt)
+    #[account(
+        mut,
+        constraint = receipt_token_account.mint == escrow.receipt_mint @ EscrowError::InvalidToken,
+    )]
+    pub receipt_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token-2022 program of the receipt (only required if the escrow has a receipt)
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+    
+    /// CHECK: oracle feed or Pyth price update (only required for USD-denominated escrows), matched against 
+    /// the escrow's feed and parsed by load_oracle_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Wormhole posted VAA (only required for cross-chain releases), parsed by load_posted_vaa
+    pub posted_vaa: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: instructions sysvar (only required for relayed intents), read by verify_relayed_intent
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: parties' attestations (only required for regulated escrows), checked by verify_attestation
+    pub payer_attestation: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: see payer_attestation
+    pub receiver_attestation: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: blocklist entries of the payer and receiver, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: see payer_blocklist_entry
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: blocklist entry of the payout recipient, checked by require_not_blocked
+    #[account(
+        seeds = [seeds::BLOCKED_ADDRESS, receiver.key().as_ref()],
+        bump
+    )]
+    pub payout_blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: statistics of the escrow currency, updated by update_currency_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CURRENCY_STATS, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub currency_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: counters of the escrow category, updated by update_category_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CATEGORY_STATS, &[escrow.category as u8]],
+        bump
+    )]
+    pub category_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: daily volume history of the escrow currency, updated by update_volume_history if kept
+    #[account(
+        mut,
+        seeds = [seeds::VOLUME_HISTORY, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub volume_history: UncheckedAccount<'info>,
+    
+    /// CHECK: global TVL ledger, updated by update_tvl_ledger once it exists
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: UncheckedAccount<'info>,
+    
+    /// CHECK: parties' lifetime counters, updated by update_user_stats if they exist
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: see payer_stats
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: settlement hook program (only required if the escrow has one), called by invoke_settlement_hook
+    #[account(
+        executable,
+        address = escrow.settlement_hook @ EscrowError::SettlementHookNotAllowed
+    )]
+    pub settlement_hook: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: PDA signing the settlement hook call
+    #[account(
+        seeds = [seeds::SETTLEMENT_HOOK_AUTHORITY],
+        bump
+    )]
+    pub settlement_hook_authority: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: state account of the settlement hook, passed through to it unchecked
+    #[account(mut)]
+    pub settlement_hook_state: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: SPL Memo program (only required if the escrow has memos turned on)
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// For programmable NFT transfers through Metaplex
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_token_escrow<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>) -> Result<()> {
+    trigger_token_release(ctx, ReleaseTrigger::Consent)
+}
+
+//how a release is authorized: by both parties' consent (signed or relayed), an HTLC preimage or a Wormhole 
+//message
+enum ReleaseTrigger {
+    Consent,
+    Relayed,
+    Htlc([u8; 32]),
+    Vaa,
+}
+
+//accounts a release trigger is checked against
+struct ReleaseTriggerAccounts<'a, 'info> {
+    signer: Pubkey,
+    price_feed: Option<&'a UncheckedAccount<'info>>,
+    posted_vaa: Option<&'a UncheckedAccount<'info>>,
+    sysvar_instructions: Option<&'a UncheckedAccount<'info>>,
+}
+
+//helper function to check a release trigger, returning the amount to release if the release executes now
+fn authorize_release(
+    escrow: &mut Account<AsymEscrow>,
+    trigger: ReleaseTrigger,
+    accounts: ReleaseTriggerAccounts,
+    emitter: &EventEmitter,
+) -> Result<Option<u64>> {
+    match trigger {
+        ReleaseTrigger::Consent => consent_to_release(escrow, accounts.signer, accounts.price_feed, emitter),
+        ReleaseTrigger::Relayed => {
+            let escrow_key = escrow.key();
+            let amount = escrow.get_amount_remaining();
+            let party = verify_relayed_intent(
+                accounts.sysvar_instructions,
+                &escrow_key,
+                escrow,
+                relay_actions::RELEASE,
+                amount,
+                emitter,
+            )?;
+            consent_to_release(escrow, party, accounts.price_feed, emitter)
+        },
+        ReleaseTrigger::Htlc(preimage) => authorize_htlc_claim(escrow, accounts.signer, preimage, emitter).map(Some),
+        ReleaseTrigger::Vaa => authorize_vaa_release(escrow, accounts.signer, accounts.posted_vaa, emitter).map(Some),
+    }
+}
+
+//helper function to release a native escrow once its trigger checks out
+fn trigger_release<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>,
+    trigger: ReleaseTrigger,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let amount = authorize_release(
+        &mut ctx.accounts.escrow,
+        trigger,
+        ReleaseTriggerAccounts {
+            signer: ctx.accounts.signer.key(),
+            price_feed: ctx.accounts.price_feed.as_ref(),
+            posted_vaa: ctx.accounts.posted_vaa.as_ref(),
+            sysvar_instructions: ctx.accounts.sysvar_instructions.as_ref(),
+        },
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    match amount {
+        Some(amount) => execute_release(ctx, amount),
+        None => Ok(()),
+    }
+}
+
+//helper function to release a token escrow once its trigger checks out
+fn trigger_token_release<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>,
+    trigger: ReleaseTrigger,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let amount = authorize_release(
+        &mut ctx.accounts.escrow,
+        trigger,
+        ReleaseTriggerAccounts {
+            signer: ctx.accounts.signer.key(),
+            price_feed: ctx.accounts.price_feed.as_ref(),
+            posted_vaa: ctx.accounts.posted_vaa.as_ref(),
+            sysvar_instructions: ctx.accounts.sysvar_instructions.as_ref(),
+        },
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    match amount {
+        Some(amount) => execute_token_release(ctx, amount),
+        None => Ok(()),
+    }
+}
+
+//record release consent for a party, returning the amount to release once both parties consent
+fn consent_to_release(
+    escrow: &mut Account<AsymEscrow>,
+    signer: Pubkey,
+    price_feed: Option<&UncheckedAccount>,
+    emitter: &EventEmitter,
+) -> Result<Option<u64>> {
+    //check escrow timing
+    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
+    
+    let remaining_amount = escrow.get_amount_remaining();
+    require!(remaining_amount > 0, EscrowError::InvalidEscrowState);
+    
+    //check authorization (payer or receiver, or their delegates within their limits or session keys); a 
+    //co-signed party acts only through its co-signers
+    let slot = Clock::get()?.slot;
+    let co_signer = escrow.co_signer_index(&signer);
+    let is_payer = if escrow.has_co_signers(&escrow.payer.addr) {
+        co_signer.is_some()
+    } else {
+        escrow.payer.can_act(&signer, remaining_amount, session_permissions::RELEASE, slot)
+    };
+    let is_receiver = if escrow.has_co_signers(&escrow.receiver.addr) {
+        co_signer.is_some()
+    } else {
+        escrow.receiver.can_act(&signer, remaining_amount, session_permissions::RELEASE, slot)
+    };
+    require!(is_payer || is_receiver, EscrowError::Unauthorized);
+    
+    //lent or staked funds must be withdrawn before settlement
+    require!(!escrow.is_lent(), EscrowError::FundsLent);
+    require!(!escrow.is_staked(), EscrowError::FundsStaked);
+    
+    //a USD-denominated escrow is released once; any surplus is left to refund
+    require!(
+        !(escrow.is_usd_denominated() && escrow.released),
+        EscrowError::AlreadyReleased
+    );
+    
+    //the first assent starts the dispute window
+    if escrow.release_requested_at == 0 {
+        escrow.release_requested_at = Clock::get()?.unix_timestamp;
+    }
+    
+    //record consent; a co-signed party consents once enough of its co-signers have assented
+    let threshold_met = match co_signer {
+        Some(index) => record_co_signer_assent(escrow, index, emitter)?,
+        None => true,
+    };
+    
+    if is_payer && threshold_met && !escrow.payer.released {
+        escrow.payer.released = true;
+        emitter.emit(ReleaseAssentGivenEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            assenting_address: signer,
+            assent_type: ReleaseAssentType::Payer,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    
+    if is_receiver && threshold_met && !escrow.receiver.released {
+        escrow.receiver.released = true;
+        emitter.emit(ReleaseAssentGivenEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            assenting_address: signer,
+            assent_type: ReleaseAssentType::Receiver,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    
+    //execute release if both parties consent
+    if !(escrow.payer.released && escrow.receiver.released) {
+        return Ok(None);
+    }
+    
+    //USD-denominated escrows release the USD amount at the current price
+    if !escrow.is_usd_denominated() {
+        return Ok(Some(remaining_amount));
+    }
+    let (quoted_amount, price) = quote_usd_amount(escrow, price_feed, emitter)?;
+    
+    //outside the collar, settlement pauses until both parties consent again to the current rate
+    let usd_price = oracle_price_to_usd(&price)?;
+    if !escrow.collar_breached && !escrow.is_price_in_collar(usd_price) {
+        escrow.collar_breached = true;
+        escrow.payer.released = false;
+        escrow.receiver.released = false;
+        reset_co_signer_assents(escrow);
+        emitter.emit(PriceCollarBreachedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            usd_price,
+            price_floor: escrow.price_floor,
+            price_ceiling: escrow.price_ceiling,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+        return Ok(None);
+    }
+    
+    require!(remaining_amount >= quoted_amount, EscrowError::InsufficientFunds);
+    escrow.payer.amount = quoted_amount;
+    Ok(Some(quoted_amount))
+}
+
+/// Refund escrow to the payer (on the receiver's consent, signed or relayed, or by reclaiming an expired HTLC)
+/// 
+/// Native refunds go to the payer's wallet, token refunds to a token account the payer owns.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundEscrowAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+        constraint = !escrow.external_call_in_progress @ EscrowError::ReentrantCall,
+    )]
+    pub escrow: Box<Account<'info, AsymEscrow>>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+    
+    /// Escrow vault
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: payer account for refunds; may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = escrow.payer.addr @ EscrowError::InvalidPayer
+    )]
+    pub payer: UncheckedAccount<'info>,
+    
+    /// For SPL token refunds
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == escrow_vault.key()
+            && escrow_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub escrow_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ EscrowError::InvalidPayer,
+        constraint = payer_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Party reputations, updated when the escrow completes
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.payer.addr.as_ref()],
+        bump = payer_reputation.bump
+    )]
+    pub payer_reputation: Option<Box<Account<'info, Reputation>>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, escrow.receiver.addr.as_ref()],
+        bump = receiver_reputation.bump
+    )]
+    pub receiver_reputation: Option<Box<Account<'info, Reputation>>>,
+    
+    /// Token mint (only required for SPL token escrows)
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Receipt mint of the escrow (only required if the escrow has a receipt), burned once it completes
+    #[account(
+        mut,
+        address = escrow.receipt_mint @ EscrowError::InvalidToken
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token account holding the escrow's receipt (only required if the escrow has a receipt)
+    #[account(
+        mut,
+        constraint = receipt_token_account.mint == escrow.receipt_mint @ EscrowError::InvalidToken,
+    )]
+    pub receipt_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token-2022 program of the receipt (only required if the escrow has a receipt)
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+    
+    /// CHECK: instructions sysvar (only required for relayed intents), read by verify_relayed_intent
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: statistics of the escrow currency, updated by update_currency_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CURRENCY_STATS, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub currency_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: counters of the escrow category, updated by update_category_stats if tracked
+    #[account(
+        mut,
+        seeds = [seeds::CATEGORY_STATS, &[escrow.category as u8]],
+        bump
+    )]
+    pub category_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: daily volume history of the escrow currency, updated by update_volume_history if kept
+    #[account(
+        mut,
+        seeds = [seeds::VOLUME_HISTORY, escrow.payer.currency.as_ref()],
+        bump
+    )]
+    pub volume_history: UncheckedAccount<'info>,
+    
+    /// CHECK: global TVL ledger, updated by update_tvl_ledger once it exists
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: UncheckedAccount<'info>,
+    
+    /// CHECK: parties' lifetime counters, updated by update_user_stats if they exist
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: see payer_stats
+    #[account(
+        mut,
+        seeds = [seeds::USER_STATS, escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: settlement hook program (only required if the escrow has one), called by invoke_settlement_hook
+    #[account(
+        executable,
+        address = escrow.settlement_hook @ EscrowError::SettlementHookNotAllowed
+    )]
+    pub settlement_hook: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: PDA signing the settlement hook call
+    #[account(
+        seeds = [seeds::SETTLEMENT_HOOK_AUTHORITY],
+        bump
+    )]
+    pub settlement_hook_authority: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: state account of the settlement hook, passed through to it unchecked
+    #[account(mut)]
+    pub settlement_hook_state: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: SPL Memo program (only required if the escrow has memos turned on)
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn refund_escrow<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>, amount: u64) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    approve_refund(ctx, signer, amount)
+}
+
+/// Consent to release for a party from an intent it signed off-chain, submitted by any relayer
+pub fn relay_release<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>) -> Result<()> {
+    trigger_release(ctx, ReleaseTrigger::Relayed)
+}
+
+/// Consent to a token escrow's release from a relayed intent, as `relay_release`
+pub fn relay_token_release<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>) -> Result<()> {
+    trigger_token_release(ctx, ReleaseTrigger::Relayed)
+}
+
+/// Refund the payer from an intent the receiver signed off-chain, submitted by any relayer
+pub fn relay_refund<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>, amount: u64) -> Result<()> {
+    let escrow_key = ctx.accounts.escrow.key();
+    let party = verify_relayed_intent(
+        ctx.accounts.sysvar_instructions.as_ref(),
+        &escrow_key,
+        &mut ctx.accounts.escrow,
+        relay_actions::REFUND,
+        amount,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    approve_refund(ctx, party, amount)
 }
 
-pub fn refund_escrow(ctx: Context<RefundEscrowAsym>, amount: u64) -> Result<()> {
+//refund part of the escrow to the payer on the receiver's approval
+fn approve_refund<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>, signer: Pubkey, amount: u64) -> Result<()> {
     require_not_paused(&ctx.accounts.program_config)?;
     
     let escrow = &mut ctx.accounts.escrow;
     
-    //check authorization (receiver)
-    require!(ctx.accounts.signer.key() == escrow.receiver.addr, EscrowError::Unauthorized);
+    //check authorization (receiver or its session key)
+    require!(
+        signer == escrow.receiver.addr
+            || escrow.receiver.has_session(&signer, session_permissions::REFUND, Clock::get()?.slot),
+        EscrowError::Unauthorized
+    );
     
     //check escrow timing
     require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
@@ -376,23 +2480,164 @@ pub fn refund_escrow(ctx: Context<RefundEscrowAsym>, amount: u64) -> Result<()>
     let remaining_amount = escrow.get_amount_remaining();
     require!(remaining_amount >= amount, EscrowError::AmountExceeded);
     require!(amount > 0, EscrowError::InvalidAmount);
-    require!(!escrow.released, EscrowError::AlreadyReleased);
+    require!(!escrow.is_lent(), EscrowError::FundsLent);
+    require!(!escrow.is_staked(), EscrowError::FundsStaked);
+    //the surplus of a released USD-denominated escrow can still be refunded
+    require!(
+        !escrow.released || escrow.is_usd_denominated(),
+        EscrowError::AlreadyReleased
+    );
 
     //execute refund
-    execute_refund(ctx, amount);
+    execute_refund(ctx, amount)
+}
 
-    Ok(())
+/// Claim an HTLC escrow by revealing the hashlock preimage before the timelock (receiver only)
+pub fn claim_htlc<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>, preimage: [u8; 32]) -> Result<()> {
+    trigger_release(ctx, ReleaseTrigger::Htlc(preimage))
 }
 
-//helper function to execute release
-fn execute_release(ctx: Context<ReleaseEscrowAsym>, amount: u64) -> Result<()> {
-    let escrow = &mut ctx.accounts.escrow;
+/// Claim an HTLC token escrow, as `claim_htlc`
+pub fn claim_token_htlc<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>,
+    preimage: [u8; 32],
+) -> Result<()> {
+    trigger_token_release(ctx, ReleaseTrigger::Htlc(preimage))
+}
+
+//helper function to check an HTLC claim, returning the amount it releases
+fn authorize_htlc_claim(
+    escrow: &mut Account<AsymEscrow>,
+    signer: Pubkey,
+    preimage: [u8; 32],
+    emitter: &EventEmitter,
+) -> Result<u64> {
+    require!(escrow.is_htlc(), EscrowError::InvalidEscrowState);
+    require!(signer == escrow.receiver.addr, EscrowError::Unauthorized);
+    
+    //check timing; the timelock bounds the claim window
+    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
+    require!(Clock::get()?.unix_timestamp <= escrow.timelock, EscrowError::TimelockExpired);
     
-    //calculate fee and amount to transfer
-    let (fee, amount_to_transfer) = calculate_fee_and_amount(amount, escrow.fee_bps)?;
+    //validate preimage
+    require!(
+        anchor_lang::solana_program::hash::hash(&preimage).to_bytes() == escrow.hashlock,
+        EscrowError::InvalidPreimage
+    );
+    
+    let remaining_amount = escrow.get_amount_remaining();
+    require!(remaining_amount > 0, EscrowError::InvalidEscrowState);
+    require!(!escrow.is_lent(), EscrowError::FundsLent);
+    require!(!escrow.is_staked(), EscrowError::FundsStaked);
+    
+    emitter.emit(HtlcClaimedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        preimage,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    Ok(remaining_amount)
+}
+
+/// Release an escrow on a verified Wormhole message from its cross-chain emitter (either party)
+/// 
+/// The message attests to an event on another chain, such as delivery recorded by an EVM contract, and 
+/// stands in for both parties' consent.
+pub fn release_on_vaa<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>) -> Result<()> {
+    trigger_release(ctx, ReleaseTrigger::Vaa)
+}
+
+/// Release a token escrow on a verified Wormhole message, as `release_on_vaa`
+pub fn release_token_on_vaa<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>) -> Result<()> {
+    trigger_token_release(ctx, ReleaseTrigger::Vaa)
+}
+
+//helper function to check a release on a Wormhole message, returning the amount it releases
+fn authorize_vaa_release(
+    escrow: &mut Account<AsymEscrow>,
+    signer: Pubkey,
+    posted_vaa: Option<&UncheckedAccount>,
+    emitter: &EventEmitter,
+) -> Result<u64> {
+    require!(escrow.has_wormhole_trigger(), EscrowError::InvalidEscrowState);
+    
+    //check authorization (payer or receiver)
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+    
+    //check escrow timing
+    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
+    require!(!escrow.released, EscrowError::AlreadyReleased);
+    
+    //validate the message
+    let posted_vaa = posted_vaa.ok_or(EscrowError::InvalidVaa)?;
+    let vaa = load_posted_vaa(posted_vaa)?;
+    validate_release_vaa(escrow, &vaa)?;
+    
+    let remaining_amount = escrow.get_amount_remaining();
+    require!(remaining_amount > 0, EscrowError::InvalidEscrowState);
+    require!(!escrow.is_lent(), EscrowError::FundsLent);
+    require!(!escrow.is_staked(), EscrowError::FundsStaked);
+    
+    emitter.emit(WormholeReleaseEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        emitter_chain: vaa.emitter_chain,
+        sequence: vaa.sequence,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    Ok(remaining_amount)
+}
+
+/// Reclaim an HTLC escrow's funds once the timelock has passed unclaimed (payer only)
+pub fn reclaim_htlc<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &ctx.accounts.escrow;
+    require!(escrow.is_htlc(), EscrowError::InvalidEscrowState);
+    require!(ctx.accounts.signer.key() == escrow.payer.addr, EscrowError::Unauthorized);
+    require!(Clock::get()?.unix_timestamp > escrow.timelock, EscrowError::TimelockNotExpired);
+    require!(!escrow.released, EscrowError::AlreadyReleased);
+    
+    let remaining_amount = escrow.get_amount_remaining();
+    require!(remaining_amount > 0, EscrowError::InvalidEscrowState);
+    require!(!escrow.is_lent(), EscrowError::FundsLent);
+    require!(!escrow.is_staked(), EscrowError::FundsStaked);
+    
+    execute_refund(ctx, remaining_amount)
+}
+
+//helper function to execute a native SOL release
+fn execute_release<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>, amount: u64) -> Result<()> {
+    let (fee, amount_to_transfer) = check_release(
+        &ctx.accounts.escrow,
+        amount,
+        ctx.accounts.payer_attestation.as_ref(),
+        ctx.accounts.receiver_attestation.as_ref(),
+        &[
+            &ctx.accounts.payer_blocklist_entry,
+            &ctx.accounts.receiver_blocklist_entry,
+            &ctx.accounts.payout_blocklist_entry,
+        ],
+    )?;
+    
+    //divert the insurance fund's share of native protocol fees
+    let insurance_share = insurance_fee_share(&ctx.accounts.escrow, fee)?;
+    require!(
+        insurance_share == 0 || ctx.accounts.insurance_fund.is_some(),
+        EscrowError::InvalidConfig
+    );
     
     //generate signer seeds for escrow vault
-    let escrow_key = escrow.key();
+    let escrow_key = ctx.accounts.escrow.key();
     let vault_seeds = &[
         seeds::ESCROW_VAULT,
         escrow_key.as_ref(),
@@ -400,90 +2645,341 @@ fn execute_release(ctx: Context<ReleaseEscrowAsym>, amount: u64) -> Result<()> {
     ];
     let vault_signer = &[&vault_seeds[..]];
     
-    //transfer funds based on currency type
-    match escrow.payer.currency_type {
-        CurrencyType::Native => {
-
-            //transfer to receiver using system program CPI
-            if amount > 0 {
-                let cpi_accounts = system_program::Transfer {
-                    from: ctx.accounts.escrow_vault.to_account_info(),
-                    to: ctx.accounts.receiver.to_account_info(),
-                };
-                let cpi_program = ctx.accounts.system_program.to_account_info();
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, vault_signer);
-                system_program::transfer(cpi_ctx, amount)?;
-            }
-            
-            //transfer fee
-           // if fee > 0 {
-            //    **ctx.accounts.escrow_vault.to_account_info().try_borrow_mut_lamports()? -= fee;
-            //    **ctx.accounts.fee_vault.to_account_info().try_borrow_mut_lamports()? += fee;
-            //}
+    //transfer to receiver, then the fee and the insurance share, leaving the vault's rent reserve
+    let transfers = [
+        (ctx.accounts.receiver.to_account_info(), amount_to_transfer),
+        (ctx.accounts.fee_vault.to_account_info(), fee - insurance_share),
+    ];
+    let insurance_transfer = ctx.accounts.insurance_fund
+        .as_ref()
+        .map(|fund| (fund.to_account_info(), insurance_share));
+    for (to, lamports) in transfers.into_iter().chain(insurance_transfer) {
+        if lamports > 0 {
+            transfer_from_native_vault(
+                ctx.accounts.escrow_vault.to_account_info(),
+                to,
+                lamports,
+                ctx.accounts.system_program.to_account_info(),
+                vault_signer,
+            )?;
+        }
+    }
+    
+    if insurance_share > 0 {
+        if let Some(insurance_fund) = ctx.accounts.insurance_fund.as_mut() {
+            insurance_fund.record_deposit(insurance_share).ok_or(EscrowError::ArithmeticOverflow)?;
+            emit_cpi!(InsuranceFundDepositEvent {
+                version: EVENT_VERSION,
+                source: escrow_key,
+                seq: ctx.accounts.program_config.next_event_seq(),
+                amount: insurance_share,
+                balance: insurance_fund.balance,
+                reserved: [0; EVENT_RESERVED_BYTES],
+            });
+        }
+    }
+    
+    record_release(
+        &mut ctx.accounts.escrow,
+        ReleaseRecordAccounts {
+            program_config: &ctx.accounts.program_config,
+            actor: ctx.accounts.signer.key(),
+            chained_escrow: ctx.accounts.chained_escrow.as_deref_mut(),
+            payer_reputation: ctx.accounts.payer_reputation.as_deref_mut(),
+            receiver_reputation: ctx.accounts.receiver_reputation.as_deref_mut(),
+            receipt: SettlementReceiptAccounts {
+                receipt_mint: ctx.accounts.receipt_mint.as_deref(),
+                receipt_token_account: ctx.accounts.receipt_token_account.as_deref(),
+                token_program: ctx.accounts.receipt_token_program.as_ref(),
+                escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+            },
+            vault_signer,
+            payer_stats: &ctx.accounts.payer_stats,
+            receiver_stats: &ctx.accounts.receiver_stats,
+            currency_stats: &ctx.accounts.currency_stats,
+            category_stats: &ctx.accounts.category_stats,
+            volume_history: &ctx.accounts.volume_history,
+            tvl_ledger: &ctx.accounts.tvl_ledger,
+            settlement_hook: ctx.accounts.settlement_hook.as_ref(),
+            settlement_hook_authority: ctx.accounts.settlement_hook_authority.as_ref(),
+            settlement_hook_authority_bump: ctx.bumps.settlement_hook_authority,
+            settlement_hook_state: ctx.accounts.settlement_hook_state.as_ref(),
+            memo_program: ctx.accounts.memo_program.as_ref(),
         },
-        CurrencyType::SplToken => {
-            let escrow_token_account = ctx.accounts.escrow_token_account
-                .as_ref()
-                .ok_or(EscrowError::InvalidToken)?;
-            let receiver_token_account = ctx.accounts.receiver_token_account
-                .as_ref()
-                .ok_or(EscrowError::InvalidToken)?;
-            let token_program = ctx.accounts.token_program
-                .as_ref()
-                .ok_or(EscrowError::InvalidToken)?;
-            
-            //transfer to receiver
-            if amount_to_transfer > 0 {
-                let cpi_accounts = anchor_spl::token::Transfer {
-                    from: escrow_token_account.to_account_info(),
-                    to: receiver_token_account.to_account_info(),
-                    authority: ctx.accounts.escrow_vault.to_account_info(),
-                };
-                let cpi_program = token_program.to_account_info();
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, vault_signer);
-                anchor_spl::token::transfer(cpi_ctx, amount_to_transfer)?;
-            }
-            
-            //transfer fee
-            if fee > 0 {
-                let fee_token_account = ctx.accounts.fee_token_account
-                    .as_ref()
-                    .ok_or(EscrowError::InvalidToken)?;
-                
-                let cpi_accounts = anchor_spl::token::Transfer {
-                    from: escrow_token_account.to_account_info(),
-                    to: fee_token_account.to_account_info(),
-                    authority: ctx.accounts.escrow_vault.to_account_info(),
-                };
-                let cpi_program = token_program.to_account_info();
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, vault_signer);
-                anchor_spl::token::transfer(cpi_ctx, fee)?;
-            }
+        amount,
+        fee,
+        amount_to_transfer,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
+}
+
+//helper function to execute an SPL token or NFT release
+fn execute_token_release<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let (fee, amount_to_transfer) = check_release(
+        &ctx.accounts.escrow,
+        amount,
+        ctx.accounts.payer_attestation.as_ref(),
+        ctx.accounts.receiver_attestation.as_ref(),
+        &[
+            &ctx.accounts.payer_blocklist_entry,
+            &ctx.accounts.receiver_blocklist_entry,
+            &ctx.accounts.payout_blocklist_entry,
+        ],
+    )?;
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = ctx.accounts.escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    //transfer to receiver, tracking what the receiver actually gets
+    let amount_delivered = if ctx.accounts.escrow.payer.currency_type == CurrencyType::ProgrammableNft {
+        let pnft = ProgrammableNftAccounts::from_remaining_accounts(ctx.remaining_accounts)?;
+        
+        //transfer through Metaplex, subject to the NFT's rule set
+        if amount_to_transfer > 0 {
+            transfer_programmable_nft(
+                &ctx.accounts.escrow_token_account.to_account_info(),
+                &ctx.accounts.receiver_token_account.to_account_info(),
+                &ctx.accounts.receiver.to_account_info(),
+                &ctx.accounts.token_mint.to_account_info(),
+                &ctx.accounts.escrow_vault.to_account_info(),
+                &ctx.accounts.signer.to_account_info(),
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &pnft,
+                vault_signer,
+            )?;
+        }
+        amount_to_transfer
+    } else {
+        //the fee follows the payout
+        let token_mint = pay_out_tokens(
+            SettlementTokenAccounts {
+                escrow_token_account: Some(&*ctx.accounts.escrow_token_account),
+                token_mint: Some(&*ctx.accounts.token_mint),
+                token_program: Some(&ctx.accounts.token_program),
+                escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+            },
+            &[
+                (Some(&*ctx.accounts.receiver_token_account), amount_to_transfer),
+                (ctx.accounts.fee_token_account.as_deref(), fee),
+            ],
+            vault_signer,
+            &ctx.accounts.program_config,
+            ctx.remaining_accounts,
+        )?;
+        
+        amount_to_transfer
+            .checked_sub(calculate_transfer_fee(token_mint, amount_to_transfer)?)
+            .ok_or(EscrowError::ArithmeticOverflow)?
+    };
+    
+    record_release(
+        &mut ctx.accounts.escrow,
+        ReleaseRecordAccounts {
+            program_config: &ctx.accounts.program_config,
+            actor: ctx.accounts.signer.key(),
+            chained_escrow: ctx.accounts.chained_escrow.as_deref_mut(),
+            payer_reputation: ctx.accounts.payer_reputation.as_deref_mut(),
+            receiver_reputation: ctx.accounts.receiver_reputation.as_deref_mut(),
+            receipt: SettlementReceiptAccounts {
+                receipt_mint: ctx.accounts.receipt_mint.as_deref(),
+                receipt_token_account: ctx.accounts.receipt_token_account.as_deref(),
+                token_program: ctx.accounts.receipt_token_program.as_ref(),
+                escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+            },
+            vault_signer,
+            payer_stats: &ctx.accounts.payer_stats,
+            receiver_stats: &ctx.accounts.receiver_stats,
+            currency_stats: &ctx.accounts.currency_stats,
+            category_stats: &ctx.accounts.category_stats,
+            volume_history: &ctx.accounts.volume_history,
+            tvl_ledger: &ctx.accounts.tvl_ledger,
+            settlement_hook: ctx.accounts.settlement_hook.as_ref(),
+            settlement_hook_authority: ctx.accounts.settlement_hook_authority.as_ref(),
+            settlement_hook_authority_bump: ctx.bumps.settlement_hook_authority,
+            settlement_hook_state: ctx.accounts.settlement_hook_state.as_ref(),
+            memo_program: ctx.accounts.memo_program.as_ref(),
         },
-    }
+        amount,
+        fee,
+        amount_delivered,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
+}
+
+//helper function to check a release can be paid out, returning its fee and the amount to transfer
+fn check_release(
+    escrow: &AsymEscrow,
+    amount: u64,
+    payer_attestation: Option<&UncheckedAccount>,
+    receiver_attestation: Option<&UncheckedAccount>,
+    blocklist_entries: &[&UncheckedAccount],
+) -> Result<(u64, u64)> {
+    //a guardian can delay, but never redirect, a large release
+    require!(
+        !escrow.is_release_held(amount, Clock::get()?.unix_timestamp),
+        EscrowError::ReleaseOnHold
+    );
     
-    //update escrow state
+    //regulated escrows settle only between attested parties
+    verify_party_attestations(escrow, payer_attestation, receiver_attestation)?;
+    
+    //releases to or from a sanctioned address are rejected
+    require_not_blocked(blocklist_entries, &escrow.id, BlockedTransfer::Release)?;
+    
+    //calculate fee and amount to transfer
+    release_fee_and_payout(escrow, amount)
+}
+
+//accounts a release is recorded with once paid out, whatever its currency
+struct ReleaseRecordAccounts<'a, 'info> {
+    program_config: &'a ProgramConfig,
+    actor: Pubkey,
+    chained_escrow: Option<&'a mut Account<'info, AsymEscrow>>,
+    payer_reputation: Option<&'a mut Account<'info, Reputation>>,
+    receiver_reputation: Option<&'a mut Account<'info, Reputation>>,
+    receipt: SettlementReceiptAccounts<'a, 'info>,
+    vault_signer: &'a [&'a [&'a [u8]]],
+    payer_stats: &'a UncheckedAccount<'info>,
+    receiver_stats: &'a UncheckedAccount<'info>,
+    currency_stats: &'a UncheckedAccount<'info>,
+    category_stats: &'a UncheckedAccount<'info>,
+    volume_history: &'a UncheckedAccount<'info>,
+    tvl_ledger: &'a UncheckedAccount<'info>,
+    settlement_hook: Option<&'a UncheckedAccount<'info>>,
+    settlement_hook_authority: Option<&'a UncheckedAccount<'info>>,
+    settlement_hook_authority_bump: Option<u8>,
+    settlement_hook_state: Option<&'a UncheckedAccount<'info>>,
+    memo_program: Option<&'a UncheckedAccount<'info>>,
+}
+
+//helper function to record a paid-out release: the escrow's state, its completion and statistics, its 
+//events and the settlement hook; kept out of line so it doesn't add to the stack frame of the release 
+//instructions
+#[inline(never)]
+fn record_release<'info>(
+    escrow: &mut Account<'info, AsymEscrow>,
+    accounts: ReleaseRecordAccounts<'_, 'info>,
+    amount: u64,
+    fee: u64,
+    amount_delivered: u64,
+    emitter: &EventEmitter,
+) -> Result<()> {
+    let escrow_key = escrow.key();
+    let amount_to_transfer = amount.checked_sub(fee).ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    //update escrow state (the fee leaves the vault with the payout, so both count as released)
     escrow.released = true;
     escrow.payer.amount_released = escrow.payer.amount_released
-        .checked_add(amount_to_transfer)
+        .checked_add(amount)
         .ok_or(EscrowError::ArithmeticOverflow)?;
+    escrow.payer.amount_delivered = escrow.payer.amount_delivered
+        .checked_add(amount_delivered)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    //funds released into a chained escrow count as payment there
+    if escrow.is_chained() {
+        let chained_escrow = accounts.chained_escrow.ok_or(EscrowError::InvalidEscrowChain)?;
+        credit_chained_escrow(chained_escrow, escrow_key, amount_delivered, emitter)?;
+    }
     
+    let mut completed = false;
     if escrow.get_amount_remaining() == 0 {
-        escrow.status = EscrowStatus::Completed;
+        if let Some(old) = escrow.set_status(EscrowStatus::Completed) {
+            emitter.emit(EscrowStatusChangedEvent {
+                version: EVENT_VERSION,
+                escrow_id: escrow.id,
+                seq: escrow.next_event_seq(),
+                escrow: escrow_key,
+                old,
+                new: EscrowStatus::Completed,
+                actor: accounts.actor,
+                timestamp: Clock::get()?.unix_timestamp,
+                reserved: [0; EVENT_RESERVED_BYTES],
+            })?;
+            completed = true;
+            update_user_stats(
+                accounts.payer_stats,
+                accounts.receiver_stats,
+                escrow.payer.amount_paid,
+                false,
+            )?;
+        }
+        record_settlement(
+            accounts.payer_reputation,
+            accounts.receiver_reputation,
+            escrow.payer.amount_paid,
+        )?;
+    }
+    
+    //a completed escrow's receipt, and any claim it carries, ends with the payout
+    if completed {
+        burn_settled_receipt(escrow, accounts.receipt, accounts.vault_signer, emitter)?;
     }
     
+    update_currency_stats(accounts.currency_stats, |stats, now| {
+        stats.record_release(amount_to_transfer, fee, completed, now)
+    })?;
+    if completed {
+        update_category_stats(accounts.category_stats, |stats, now| stats.record_settled(false, now))?;
+    }
+    
+    //a release into a chained escrow stays locked, in the downstream vault
+    let relocked = if escrow.is_chained() { amount_delivered } else { 0 };
+    update_tvl_ledger(accounts.tvl_ledger, &escrow.payer.currency, relocked, amount)?;
+    update_volume_history(accounts.volume_history, 0, amount)?;
+    
     //emit event
-    emit!(EscrowReleasedEvent {
+    log_escrow_action(log_actions::RELEASE, &escrow.id, &[
+        ("amount", amount_to_transfer),
+        ("fee", fee),
+        ("delivered", amount_delivered),
+    ]);
+    emit_escrow_memo(escrow, accounts.memo_program, log_actions::RELEASE, amount_to_transfer)?;
+    emitter.emit(EscrowReleasedEvent {
+        version: EVENT_VERSION,
         escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
         amount: amount_to_transfer,
         fee,
-    });
-    
-    Ok(())
+        amount_delivered,
+        decimals: escrow.payer.decimals,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    invoke_settlement_hook(
+        escrow,
+        accounts.program_config,
+        accounts.settlement_hook,
+        accounts.settlement_hook_authority,
+        accounts.settlement_hook_authority_bump,
+        accounts.settlement_hook_state,
+        SettlementOutcome::Release,
+        amount_to_transfer,
+        fee,
+        amount_delivered,
+    )
 }
 
-fn execute_refund(ctx: Context<RefundEscrowAsym>, amount: u64) -> Result<()> {
+fn execute_refund<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>, amount: u64) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
+    
+    //a refund is often the first thing to touch an expired escrow, so it records the expiry
+    mark_expired_asym(
+        escrow,
+        ctx.accounts.signer.key(),
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
 
     // Generate signer seeds for escrow vault
     let escrow_key = escrow.key();
@@ -497,39 +2993,62 @@ fn execute_refund(ctx: Context<RefundEscrowAsym>, amount: u64) -> Result<()> {
     //transfer funds based on currency type
     match escrow.payer.currency_type {
         CurrencyType::Native => {
-            //transfer to payer using system program CPI
+            //transfer to payer, leaving the vault's rent reserve
             if amount > 0 {
-                let cpi_accounts = system_program::Transfer {
-                    from: ctx.accounts.escrow_vault.to_account_info(),
-                    to: ctx.accounts.payer.to_account_info(),
-                };
-                let cpi_program = ctx.accounts.system_program.to_account_info();
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, vault_signer);
-                system_program::transfer(cpi_ctx, amount)?;
+                transfer_from_native_vault(
+                    ctx.accounts.escrow_vault.to_account_info(),
+                    ctx.accounts.payer.to_account_info(),
+                    amount,
+                    ctx.accounts.system_program.to_account_info(),
+                    vault_signer,
+                )?;
             }
         },
 
-        CurrencyType::SplToken => {
+        CurrencyType::SplToken | CurrencyType::Nft => {
+            //transfer to payer
+            pay_out_tokens(
+                SettlementTokenAccounts {
+                    escrow_token_account: ctx.accounts.escrow_token_account.as_deref(),
+                    token_mint: ctx.accounts.token_mint.as_deref(),
+                    token_program: ctx.accounts.token_program.as_ref(),
+                    escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+                },
+                &[(ctx.accounts.payer_token_account.as_deref(), amount)],
+                vault_signer,
+                &ctx.accounts.program_config,
+                ctx.remaining_accounts,
+            )?;
+        },
+        CurrencyType::ProgrammableNft => {
             let escrow_token_account = ctx.accounts.escrow_token_account
                 .as_ref()
                 .ok_or(EscrowError::InvalidToken)?;
             let payer_token_account = ctx.accounts.payer_token_account
                 .as_ref()
                 .ok_or(EscrowError::InvalidToken)?;
+            let token_mint = ctx.accounts.token_mint
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
             let token_program = ctx.accounts.token_program
                 .as_ref()
                 .ok_or(EscrowError::InvalidToken)?;
+            let pnft = ProgrammableNftAccounts::from_remaining_accounts(ctx.remaining_accounts)?;
             
-            //transfer to payer
+            //return the NFT to the payer
             if amount > 0 {
-                let cpi_accounts = anchor_spl::token::Transfer {
-                    from: escrow_token_account.to_account_info(),
-                    to: payer_token_account.to_account_info(),
-                    authority: ctx.accounts.escrow_vault.to_account_info(),
-                };
-                let cpi_program = token_program.to_account_info();
-                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, vault_signer);
-                anchor_spl::token::transfer(cpi_ctx, amount)?;
+                transfer_programmable_nft(
+                    &escrow_token_account.to_account_info(),
+                    &payer_token_account.to_account_info(),
+                    &ctx.accounts.payer.to_account_info(),
+                    &token_mint.to_account_info(),
+                    &ctx.accounts.escrow_vault.to_account_info(),
+                    &ctx.accounts.signer.to_account_info(),
+                    &token_program.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    &pnft,
+                    vault_signer,
+                )?;
             }
         },
     }
@@ -539,68 +3058,513 @@ fn execute_refund(ctx: Context<RefundEscrowAsym>, amount: u64) -> Result<()> {
         .checked_add(amount)
         .ok_or(EscrowError::ArithmeticOverflow)?;
     
+    let mut completed = false;
     if escrow.get_amount_remaining() == 0 {
-        escrow.status = EscrowStatus::Completed;
+        if let Some(old) = escrow.set_status(EscrowStatus::Completed) {
+            emit_cpi!(EscrowStatusChangedEvent {
+                version: EVENT_VERSION,
+                escrow_id: escrow.id,
+                seq: escrow.next_event_seq(),
+                escrow: escrow.key(),
+                old,
+                new: EscrowStatus::Completed,
+                actor: ctx.accounts.signer.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+                reserved: [0; EVENT_RESERVED_BYTES],
+            });
+            completed = true;
+            update_user_stats(
+                &ctx.accounts.payer_stats,
+                &ctx.accounts.receiver_stats,
+                escrow.payer.amount_paid,
+                false,
+            )?;
+        }
+        record_settlement(
+            ctx.accounts.payer_reputation.as_deref_mut(),
+            ctx.accounts.receiver_reputation.as_deref_mut(),
+            escrow.payer.amount_paid,
+        )?;
+    }
+    
+    //a completed escrow's receipt, and any claim it carries, ends with the payout
+    if completed {
+        burn_settled_receipt(
+            escrow,
+            SettlementReceiptAccounts {
+                receipt_mint: ctx.accounts.receipt_mint.as_deref(),
+                receipt_token_account: ctx.accounts.receipt_token_account.as_deref(),
+                token_program: ctx.accounts.receipt_token_program.as_ref(),
+                escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+            },
+            vault_signer,
+            &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+    }
+    
+    update_currency_stats(&ctx.accounts.currency_stats, |stats, now| {
+        stats.record_refund(amount, completed, now)
+    })?;
+    if completed {
+        update_category_stats(&ctx.accounts.category_stats, |stats, now| stats.record_settled(false, now))?;
     }
+    update_tvl_ledger(&ctx.accounts.tvl_ledger, &escrow.payer.currency, 0, amount)?;
+    update_volume_history(&ctx.accounts.volume_history, 0, amount)?;
     
     //emit event
-    emit!(EscrowRefundedEvent {
+    log_escrow_action(log_actions::REFUND, &escrow.id, &[("amount", amount)]);
+    emit_escrow_memo(escrow, ctx.accounts.memo_program.as_ref(), log_actions::REFUND, amount)?;
+    emit_cpi!(EscrowRefundedEvent {
+        version: EVENT_VERSION,
         escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
         amount,
+        decimals: escrow.payer.decimals,
+        reserved: [0; EVENT_RESERVED_BYTES],
     });
     
+    invoke_settlement_hook(
+        escrow,
+        &ctx.accounts.program_config,
+        ctx.accounts.settlement_hook.as_ref(),
+        ctx.accounts.settlement_hook_authority.as_ref(),
+        ctx.bumps.settlement_hook_authority,
+        ctx.accounts.settlement_hook_state.as_ref(),
+        SettlementOutcome::Refund,
+        amount,
+        0,
+        amount,
+    )?;
+    
     Ok(())
 }
 
-//helper function to generate escrow ID
-fn generate_escrow_id(creator: &Pubkey, nonce: u64) -> [u8; 32] {
-    let mut hasher = anchor_lang::solana_program::hash::Hasher::default();
-    hasher.hash(creator.as_ref());
-    hasher.hash(&nonce.to_le_bytes());
-    hasher.result().to_bytes()
+//token accounts a settlement pays out of the escrow with, all required for token escrows
+struct SettlementTokenAccounts<'a, 'info> {
+    escrow_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+    escrow_vault: AccountInfo<'info>,
+}
+
+//helper function to pay tokens out of the escrow to each (recipient, amount), returning the mint; kept out 
+//of line so its transfers don't add to the stack frame of the settlement instructions
+#[inline(never)]
+fn pay_out_tokens<'a, 'info>(
+    accounts: SettlementTokenAccounts<'a, 'info>,
+    payouts: &[(Option<&InterfaceAccount<'info, TokenAccount>>, u64)],
+    vault_signer: &[&[&[u8]]],
+    program_config: &ProgramConfig,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<&'a InterfaceAccount<'info, Mint>> {
+    let escrow_token_account = accounts.escrow_token_account.ok_or(EscrowError::InvalidToken)?;
+    let token_mint = accounts.token_mint.ok_or(EscrowError::InvalidToken)?;
+    let token_program = accounts.token_program.ok_or(EscrowError::InvalidToken)?;
+    validate_transfer_hook(token_mint, program_config, remaining_accounts)?;
+    
+    for (recipient_token_account, amount) in payouts {
+        if *amount == 0 {
+            continue;
+        }
+        transfer_spl_tokens_signed(
+            escrow_token_account,
+            recipient_token_account.ok_or(EscrowError::InvalidToken)?,
+            token_mint,
+            accounts.escrow_vault.clone(),
+            *amount,
+            token_program,
+            vault_signer,
+            remaining_accounts,
+        )?;
+    }
+    
+    Ok(token_mint)
+}
+
+//helper function to validate an escrow currency, returning its currency type and decimals
+fn validate_escrow_currency(
+    currency: Pubkey,
+    token_mint: Option<&InterfaceAccount<Mint>>,
+    program_config: &ProgramConfig,
+) -> Result<(CurrencyType, u8)> {
+    if currency == Pubkey::default() {
+        return Ok((CurrencyType::Native, NATIVE_DECIMALS));
+    }
+    
+    let token_mint = token_mint.ok_or(EscrowError::InvalidToken)?;
+    require!(token_mint.key() == currency, EscrowError::InvalidToken);
+    
+    //transfer hook programs must be allowlisted
+    if let Some(hook_program) = get_transfer_hook_program(token_mint)? {
+        require!(
+            program_config.transfer_hook_allowlist.contains(&hook_program),
+            EscrowError::TransferHookNotAllowed
+        );
+    }
+    Ok((CurrencyType::SplToken, token_mint.decimals))
+}
+
+//helper function to convert the USD obligation of an escrow to its currency at the current price
+fn quote_usd_amount(
+    escrow: &mut Account<AsymEscrow>,
+    price_feed: Option<&UncheckedAccount>,
+    emitter: &EventEmitter,
+) -> Result<(u64, OraclePrice)> {
+    let price_feed = price_feed.ok_or(EscrowError::InvalidPriceFeed)?;
+    let price = load_oracle_price(
+        escrow.oracle_source,
+        &price_feed.to_account_info(),
+        &escrow.price_feed,
+        Clock::get()?.unix_timestamp,
+    )?;
+    
+    //a price older than the last one used could be picked to favor one party
+    require!(price.publish_time >= escrow.quoted_price.publish_time, EscrowError::StalePrice);
+    escrow.quoted_price = price;
+    
+    let amount = usd_to_currency_amount(escrow.usd_amount, &price, escrow.payer.decimals)?;
+    
+    emitter.emit(UsdAmountQuotedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        usd_amount: escrow.usd_amount,
+        price: price.price,
+        expo: price.expo,
+        amount,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    Ok((amount, price))
+}
+
+//helper function to validate terms and initialize a new escrow
+#[allow(clippy::too_many_arguments)]
+fn initialize_escrow(
+    escrow: &mut Account<AsymEscrow>,
+    params: &CreateAsymEscrowParams,
+    creator: Pubkey,
+    bump: u8,
+    program_config: &ProgramConfig,
+    currency_type: CurrencyType,
+    decimals: u8,
+    emitter: &EventEmitter,
+) -> Result<()> {
+    //validate inputs
+    require!(params.payer != Pubkey::default(), EscrowError::InvalidPayer);
+    require!(params.receiver != Pubkey::default(), EscrowError::InvalidReceiver);
+    require!(params.payer != params.receiver, EscrowError::InvalidReceiver);
+    
+    //validate dates
+    validate_escrow_dates(params.start_time, params.end_time)?;
+    
+    //validate arbitration terms
+    validate_arbitration_terms(params)?;
+    let dispute_window = resolve_dispute_window(params.dispute_window, program_config)?;
+    
+    //initialize escrow
+    let escrow_id = generate_escrow_id(&creator, params.nonce);
+    
+    escrow.id = escrow_id;
+    escrow.payer = EscrowParty {
+        addr: params.payer,
+        currency: params.currency,
+        currency_type,
+        amount: params.amount,
+        decimals,
+        ..Default::default()
+    };
+    escrow.receiver = EscrowParty {
+        addr: params.receiver,
+        ..Default::default()
+    };
+    escrow.timestamp = Clock::get()?.unix_timestamp;
+    escrow.start_time = params.start_time;
+    escrow.end_time = params.end_time;
+    escrow.status = EscrowStatus::Pending;
+    escrow.released = false;
+    escrow.fee_bps = program_config.default_fee_bps;
+    escrow.creator = creator;
+    escrow.nonce = params.nonce;
+    escrow.bump = bump;
+    escrow.arbiter = params.arbiter;
+    escrow.arbitration_fee = params.arbitration_fee;
+    escrow.arbitration_fee_paid = 0;
+    escrow.appeal_arbiter = params.appeal_arbiter;
+    escrow.arbiter_kind = params.arbiter_kind;
+    escrow.automated_settler = params.automated_settler;
+    escrow.arbitration_fee_mint = params.arbitration_fee_mint;
+    escrow.dispute_window = dispute_window;
+    escrow.release_requested_at = 0;
+    escrow.amount_commitment = params.amount_commitment;
+    escrow.nft_collection = Pubkey::default();
+    escrow.usd_amount = 0;
+    escrow.price_feed = Pubkey::default();
+    escrow.oracle_source = OracleSource::Pyth;
+    escrow.price_floor = 0;
+    escrow.price_ceiling = 0;
+    escrow.collar_breached = false;
+    escrow.lending_adapter = Pubkey::default();
+    escrow.lent_amount = 0;
+    escrow.yield_payer_bps = 0;
+    escrow.stake_pool = Pubkey::default();
+    escrow.staked_lamports = 0;
+    escrow.lst_amount = 0;
+    escrow.accepted_mints = Vec::new();
+    escrow.platform = Pubkey::default();
+    escrow.category = params.category;
+    escrow.fee_terms = FeeTerms {
+        insurance_fee_bps: program_config.insurance_fee_bps,
+        yield_fee_bps: program_config.yield_fee_bps,
+        dispute_bond: program_config.dispute_bond,
+        platform_fee_share_bps: 0,
+    };
+    escrow.settlement_hook = Pubkey::default();
+    escrow.memo_enabled = false;
+    escrow.client_ref = [0; 32];
+    escrow.quoted_price = OraclePrice::default();
+    escrow.external_call_in_progress = false;
+    
+    //the global guardian covers large native releases
+    if program_config.guardian != Pubkey::default() && currency_type == CurrencyType::Native {
+        escrow.guardian = program_config.guardian;
+        escrow.guardian_threshold = program_config.guardian_threshold;
+    }
+    
+    log_escrow_action(log_actions::CREATE, &escrow_id, &[
+        ("amount", params.amount),
+        ("fee_bps", escrow.fee_bps as u64),
+    ]);
+    emitter.emit(EscrowCreatedEvent {
+        version: EVENT_VERSION,
+        escrow_id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        creator,
+        nonce: params.nonce,
+        payer: params.payer,
+        receiver: params.receiver,
+        currency: params.currency,
+        currency_type,
+        amount: params.amount,
+        decimals,
+        fee_bps: escrow.fee_bps,
+        start_time: params.start_time,
+        end_time: params.end_time,
+        arbiter: params.arbiter,
+        arbitration_fee: params.arbitration_fee,
+        arbitration_fee_mint: params.arbitration_fee_mint,
+        appeal_arbiter: params.appeal_arbiter,
+        arbiter_kind: params.arbiter_kind,
+        automated_settler: params.automated_settler,
+        dispute_window,
+        amount_commitment: params.amount_commitment,
+        guardian: escrow.guardian,
+        guardian_threshold: escrow.guardian_threshold,
+        category: params.category,
+        fee_terms: escrow.fee_terms,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    Ok(())
 }
 
 //events
+#[event]
+pub struct EscrowStatusChangedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub old: EscrowStatus,
+    pub new: EscrowStatus,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
 #[event]
 pub struct EscrowCreatedEvent {
+    pub version: u8,
     pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
     pub creator: Pubkey,
+    pub nonce: u64,
+    pub payer: Pubkey,
+    pub receiver: Pubkey,
+    pub currency: Pubkey,
+    pub currency_type: CurrencyType,
+    pub amount: u64,
+    pub decimals: u8,
+    pub fee_bps: u16,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub arbiter: Pubkey,
+    pub arbitration_fee: ArbitrationFee,
+    pub arbitration_fee_mint: Pubkey,
+    pub appeal_arbiter: Pubkey,
+    pub arbiter_kind: ArbiterKind,
+    pub automated_settler: Pubkey,
+    pub dispute_window: i64,
+    pub amount_commitment: [u8; 32],
+    pub guardian: Pubkey,
+    pub guardian_threshold: u64,
+    pub category: EscrowCategory,
+    pub fee_terms: FeeTerms,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct EscrowCreatedForEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub platform: Pubkey,
     pub payer: Pubkey,
     pub receiver: Pubkey,
     pub amount: u64,
+    pub fee_share_bps: u16,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct NftDepositedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub payer: Pubkey,
+    pub mint: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
 }
 
 #[event]
 pub struct PaymentReceivedEvent {
+    pub version: u8,
     pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
     pub payer: Pubkey,
     pub amount: u64,
+    pub transfer_fee: u64,
     pub total_paid: u64,
     pub fully_paid: bool,
+    pub decimals: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
 }
 
 #[event]
 pub struct EscrowFullyPaidEvent {
+    pub version: u8,
     pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
     pub total_amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct CompactPaymentEvent {
+    pub version: u8,
+    pub action: u8,
+    pub escrow: Pubkey,
+    pub seq: u64,
+    pub amount: u64,
 }
 
 #[event]
 pub struct ReleaseAssentGivenEvent {
+    pub version: u8,
     pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
     pub assenting_address: Pubkey,
     pub assent_type: ReleaseAssentType,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
 }
 
 #[event]
 pub struct EscrowReleasedEvent {
+    pub version: u8,
     pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
     pub amount: u64,
     pub fee: u64,
+    pub amount_delivered: u64,
+    pub decimals: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
 }
 
 #[event]
 pub struct EscrowRefundedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub amount: u64,
+    pub decimals: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct UsdAmountQuotedEvent {
+    pub version: u8,
     pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub usd_amount: u64,
+    pub price: i64,
+    pub expo: i32,
     pub amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
 }
+
+#[event]
+pub struct PriceCollarBreachedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub usd_price: u64,
+    pub price_floor: u64,
+    pub price_ceiling: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct HtlcCreatedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub hashlock: [u8; 32],
+    pub timelock: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct HtlcClaimedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub preimage: [u8; 32],
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct PaymentMintSelectedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+//layout of a Solana Attestation Service attestation account
+const SAS_ATTESTATION_DISCRIMINATOR: u8 = 2;
+const SAS_NONCE_OFFSET: usize = 1;
+const SAS_CREDENTIAL_OFFSET: usize = 33;
+const SAS_SCHEMA_OFFSET: usize = 65;
+const SAS_DATA_OFFSET: usize = 97;
+
+/// Attestation a regulated escrow requires of both parties
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AttestationRequirement {
+    pub kind: AttestationKind,  // SAS attestation or soulbound token
+    pub issuer: Pubkey,         // SAS credential, or soulbound token mint
+    pub schema: Pubkey,         // SAS schema; Pubkey::default() for soulbound tokens
+}
+
+/// Solana Attestation Service attestation fields the escrow checks
+pub struct SasAttestation {
+    pub nonce: Pubkey,
+    pub credential: Pubkey,
+    pub schema: Pubkey,
+    pub expiry: i64,
+}
+
+/// Parse a Solana Attestation Service attestation account
+/// 
+/// Layout: discriminator (1) | nonce (32) | credential (32) | schema (32) | data (4-byte length + bytes) | 
+/// signer (32) | expiry (8) | token account (32)
+pub fn load_sas_attestation(account: &AccountInfo) -> Result<SasAttestation> {
+    require!(*account.owner == SAS_PROGRAM_ID, EscrowError::InvalidAttestation);
+    
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() > SAS_DATA_OFFSET + 4 && data[0] == SAS_ATTESTATION_DISCRIMINATOR,
+        EscrowError::InvalidAttestation
+    );
+    let read_pubkey = |offset: usize| -> Result<Pubkey> {
+        let bytes = data.get(offset..offset + 32).ok_or(EscrowError::InvalidAttestation)?;
+        Ok(Pubkey::try_from(bytes).unwrap())
+    };
+    
+    //skip the variable-length attestation data and the signer to reach the expiry
+    let data_len = u32::from_le_bytes(data[SAS_DATA_OFFSET..SAS_DATA_OFFSET + 4].try_into().unwrap()) as usize;
+    let expiry_offset = SAS_DATA_OFFSET + 4 + data_len + 32;
+    let expiry = data
+        .get(expiry_offset..expiry_offset + 8)
+        .ok_or(EscrowError::InvalidAttestation)?;
+    
+    Ok(SasAttestation {
+        nonce: read_pubkey(SAS_NONCE_OFFSET)?,
+        credential: read_pubkey(SAS_CREDENTIAL_OFFSET)?,
+        schema: read_pubkey(SAS_SCHEMA_OFFSET)?,
+        expiry: i64::from_le_bytes(expiry.try_into().unwrap()),
+    })
+}
+
+/// Verify a party holds the escrow's required attestation
+/// 
+/// A SAS attestation must be issued under the escrow's credential and schema with the party's address as 
+/// its nonce, and not have expired; a soulbound token account must be owned by the party and hold the 
+/// designated mint. The program only checks the attestation exists; no personal data is read or stored.
+pub fn verify_attestation(escrow: &AsymEscrow, party: &Pubkey, attestation: Option<&UncheckedAccount>) -> Result<()> {
+    let attestation = attestation.ok_or(EscrowError::InvalidAttestation)?;
+    
+    match escrow.attestation_kind {
+        AttestationKind::None => {},
+        AttestationKind::Sas => {
+            let sas = load_sas_attestation(&attestation.to_account_info())?;
+            require!(
+                sas.nonce == *party
+                    && sas.credential == escrow.attestation_issuer
+                    && sas.schema == escrow.attestation_schema
+                    && (sas.expiry == 0 || sas.expiry > Clock::get()?.unix_timestamp),
+                EscrowError::InvalidAttestation
+            );
+        },
+        AttestationKind::SoulboundToken => {
+            require!(
+                *attestation.owner == anchor_spl::token::ID || *attestation.owner == anchor_spl::token_2022::ID,
+                EscrowError::InvalidAttestation
+            );
+            let data = attestation.try_borrow_data()?;
+            let token_account = TokenAccount::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(EscrowError::InvalidAttestation))?;
+            require!(
+                token_account.owner == *party
+                    && token_account.mint == escrow.attestation_issuer
+                    && token_account.amount > 0,
+                EscrowError::InvalidAttestation
+            );
+        },
+    }
+    
+    Ok(())
+}
+
+/// Verify both parties of a regulated escrow hold the required attestation
+pub fn verify_party_attestations(
+    escrow: &AsymEscrow,
+    payer_attestation: Option<&UncheckedAccount>,
+    receiver_attestation: Option<&UncheckedAccount>,
+) -> Result<()> {
+    if !escrow.requires_attestation() {
+        return Ok(());
+    }
+    
+    verify_attestation(escrow, &escrow.payer.addr, payer_attestation)?;
+    verify_attestation(escrow, &escrow.receiver.addr, receiver_attestation)
+}
+
+//events
+#[event]
+pub struct AttestationRequiredEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub kind: AttestationKind,
+    pub issuer: Pubkey,
+    pub schema: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,347 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Register a timed escrow action with an allowlisted automation framework (either party)
+///
+/// Accounts required by the framework to create its thread (e.g. its fee payer and config) are passed as
+/// remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: AutomationTriggerKind)]
+pub struct RegisterAutomationTriggerAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = AutomationTrigger::space(),
+        seeds = [seeds::AUTOMATION_TRIGGER, escrow.key().as_ref(), &[kind as u8]],
+        bump
+    )]
+    pub trigger: Account<'info, AutomationTrigger>,
+
+    /// Open dispute of the escrow (only required for dispute timeout triggers)
+    #[account(
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+
+    /// CHECK: automation framework program, checked against the allowlist
+    #[account(executable)]
+    pub automation_program: UncheckedAccount<'info>,
+
+    /// CHECK: framework thread firing the action, created by the automation program
+    #[account(mut)]
+    pub thread: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_automation_trigger<'info>(
+    ctx: Context<'_, '_, '_, 'info, RegisterAutomationTriggerAsym<'info>>,
+    kind: AutomationTriggerKind,
+    interval: i64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    let escrow = &ctx.accounts.escrow;
+
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+
+    //only allowlisted frameworks may crank escrow actions
+    let automation_program = ctx.accounts.automation_program.key();
+    require!(
+        ctx.accounts.program_config.automation_program_allowlist.contains(&automation_program),
+        EscrowError::AutomationProgramNotAllowed
+    );
+
+    let schedule = trigger_schedule(kind, escrow, ctx.accounts.dispute.as_deref(), interval)?;
+
+    //register the crank with the framework, signed by the trigger
+    let escrow_key = escrow.key();
+    let trigger_seeds = &[
+        seeds::AUTOMATION_TRIGGER,
+        escrow_key.as_ref(),
+        &[kind as u8],
+        &[ctx.bumps.trigger],
+    ];
+    let mut crank_data = [0u8; 8];
+    crank_data.copy_from_slice(&hash(format!("global:{}", kind.crank_instruction()).as_bytes()).to_bytes()[..8]);
+    let mut args = Vec::new();
+    (crate::ID, crank_data, schedule).serialize(&mut args)?;
+    invoke_automation_program(
+        &ctx.accounts.automation_program,
+        &ctx.accounts.trigger.to_account_info(),
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.thread,
+        ctx.remaining_accounts,
+        AUTOMATION_REGISTER_INSTRUCTION,
+        &args,
+        &[&trigger_seeds[..]],
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let trigger = &mut ctx.accounts.trigger;
+    trigger.escrow = escrow_key;
+    trigger.kind = kind;
+    trigger.automation_program = automation_program;
+    trigger.thread = ctx.accounts.thread.key();
+    trigger.schedule = schedule;
+    trigger.registered_by = signer;
+    trigger.registered_at = now;
+    trigger.bump = ctx.bumps.trigger;
+
+    let escrow = &mut ctx.accounts.escrow;
+    emit_cpi!(AutomationTriggerRegisteredEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
+        trigger: trigger.key(),
+        kind,
+        automation_program,
+        thread: trigger.thread,
+        start_at: schedule.start_at,
+        interval: schedule.interval,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Cancel a registered timed escrow action, returning the trigger's rent to whoever registered it
+///
+/// The registrant or either party can unregister. Accounts required by the framework to delete its thread
+/// are passed as remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnregisterAutomationTriggerAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        close = registered_by,
+        seeds = [seeds::AUTOMATION_TRIGGER, escrow.key().as_ref(), &[trigger.kind as u8]],
+        bump = trigger.bump
+    )]
+    pub trigger: Account<'info, AutomationTrigger>,
+
+    /// Receives the trigger's rent
+    #[account(
+        mut,
+        address = trigger.registered_by @ EscrowError::Unauthorized
+    )]
+    pub registered_by: SystemAccount<'info>,
+
+    /// CHECK: automation framework program the trigger is registered with
+    #[account(
+        executable,
+        address = trigger.automation_program @ EscrowError::AutomationProgramNotAllowed
+    )]
+    pub automation_program: UncheckedAccount<'info>,
+
+    /// CHECK: framework thread firing the action, deleted by the automation program
+    #[account(
+        mut,
+        address = trigger.thread
+    )]
+    pub thread: UncheckedAccount<'info>,
+}
+
+pub fn unregister_automation_trigger<'info>(
+    ctx: Context<'_, '_, '_, 'info, UnregisterAutomationTriggerAsym<'info>>,
+) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    let trigger = &ctx.accounts.trigger;
+
+    //check authorization (registrant, payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == trigger.registered_by || signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+
+    //delete the framework's thread, signed by the trigger
+    let escrow_key = escrow.key();
+    let kind = trigger.kind;
+    let trigger_seeds = &[
+        seeds::AUTOMATION_TRIGGER,
+        escrow_key.as_ref(),
+        &[kind as u8],
+        &[trigger.bump],
+    ];
+    invoke_automation_program(
+        &ctx.accounts.automation_program,
+        &trigger.to_account_info(),
+        &escrow.to_account_info(),
+        &ctx.accounts.thread,
+        ctx.remaining_accounts,
+        AUTOMATION_UNREGISTER_INSTRUCTION,
+        &[],
+        &[&trigger_seeds[..]],
+    )?;
+
+    let trigger_key = trigger.key();
+    let escrow = &mut ctx.accounts.escrow;
+    emit_cpi!(AutomationTriggerUnregisteredEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
+        trigger: trigger_key,
+        kind,
+        actor: signer,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+//helper function to schedule a trigger from the escrow's own deadlines
+//
+//one-shot actions fire just after their deadline; interest accrues every interval after the due date
+fn trigger_schedule(
+    kind: AutomationTriggerKind,
+    escrow: &AsymEscrow,
+    dispute: Option<&Dispute>,
+    interval: i64,
+) -> Result<AutomationSchedule> {
+    require!(
+        kind.is_recurring() == (interval != 0),
+        EscrowError::InvalidAutomationSchedule
+    );
+
+    let start_at = match kind {
+        AutomationTriggerKind::Expiry => {
+            require!(escrow.end_time > 0, EscrowError::InvalidAutomationSchedule);
+            escrow.end_time
+        },
+        AutomationTriggerKind::DisputeTimeout => {
+            dispute.ok_or(EscrowError::InvalidAutomationSchedule)?.decision_deadline
+        },
+        AutomationTriggerKind::InterestAccrual => {
+            require!(escrow.accrues_interest(), EscrowError::InvalidAutomationSchedule);
+            require!(interval >= MIN_AUTOMATION_INTERVAL, EscrowError::InvalidAutomationSchedule);
+            escrow.payment_due_time
+                .checked_add(interval)
+                .ok_or(EscrowError::ArithmeticOverflow)?
+        },
+    };
+
+    Ok(AutomationSchedule {
+        start_at: start_at.checked_add(1).ok_or(EscrowError::ArithmeticOverflow)?,
+        interval,
+    })
+}
+
+//automation program instructions, named the way Anchor derives their discriminators
+const AUTOMATION_REGISTER_INSTRUCTION: &str = "automation_register";
+const AUTOMATION_UNREGISTER_INSTRUCTION: &str = "automation_unregister";
+
+//helper function to invoke an automation program, signed by the trigger
+//
+//automation programs take (trigger, escrow, thread, ...framework accounts); registration also takes the
+//program, instruction data and schedule of the crank
+#[allow(clippy::too_many_arguments)]
+fn invoke_automation_program<'info>(
+    automation_program: &UncheckedAccount<'info>,
+    trigger: &AccountInfo<'info>,
+    escrow: &AccountInfo<'info>,
+    thread: &UncheckedAccount<'info>,
+    framework_accounts: &[AccountInfo<'info>],
+    instruction_name: &str,
+    args: &[u8],
+    trigger_signer: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = hash(format!("global:{}", instruction_name).as_bytes()).to_bytes()[..8].to_vec();
+    data.extend_from_slice(args);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(trigger.key(), true),
+        AccountMeta::new_readonly(escrow.key(), false),
+        AccountMeta::new(thread.key(), false),
+    ];
+    let mut account_infos = vec![
+        trigger.clone(),
+        escrow.clone(),
+        thread.to_account_info(),
+    ];
+    for account in framework_accounts {
+        accounts.push(if account.is_writable {
+            AccountMeta::new(*account.key, account.is_signer)
+        } else {
+            AccountMeta::new_readonly(*account.key, account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+
+    invoke_signed(
+        &Instruction {
+            program_id: automation_program.key(),
+            accounts,
+            data,
+        },
+        &account_infos,
+        trigger_signer,
+    )?;
+    Ok(())
+}
+
+//events
+#[event]
+pub struct AutomationTriggerRegisteredEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub trigger: Pubkey,
+    pub kind: AutomationTriggerKind,
+    pub automation_program: Pubkey,
+    pub thread: Pubkey,
+    pub start_at: i64,
+    pub interval: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AutomationTriggerUnregisteredEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub trigger: Pubkey,
+    pub kind: AutomationTriggerKind,
+    pub actor: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
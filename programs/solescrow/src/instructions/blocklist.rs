@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Fund movement a blocklist check rejected
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum BlockedTransfer {
+    /// Payer's deposit into the escrow
+    Deposit = 0,
+    /// Release to the receiver
+    Release = 1,
+    /// Arbitration award payout
+    Award = 2,
+}
+
+/// Add an address to the sanctions blocklist (authority only)
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(addr: Pubkey)]
+pub struct BlockAddress<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = BlockedAddress::space(),
+        seeds = [seeds::BLOCKED_ADDRESS, addr.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: Account<'info, BlockedAddress>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn block_address(ctx: Context<BlockAddress>, addr: Pubkey) -> Result<()> {
+    let blocklist_entry = &mut ctx.accounts.blocklist_entry;
+    blocklist_entry.addr = addr;
+    blocklist_entry.blocked_at = Clock::get()?.unix_timestamp;
+    blocklist_entry.bump = ctx.bumps.blocklist_entry;
+
+    emit_cpi!(AddressBlockedEvent {
+        version: EVENT_VERSION,
+        addr,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        timestamp: blocklist_entry.blocked_at,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        &mut ctx.accounts.program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::BlockAddress,
+        &[addr.as_ref()],
+    )?;
+
+    Ok(())
+}
+
+/// Remove an address from the sanctions blocklist (authority only)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnblockAddress<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [seeds::BLOCKED_ADDRESS, blocklist_entry.addr.as_ref()],
+        bump = blocklist_entry.bump
+    )]
+    pub blocklist_entry: Account<'info, BlockedAddress>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn unblock_address(ctx: Context<UnblockAddress>) -> Result<()> {
+    emit_cpi!(AddressUnblockedEvent {
+        version: EVENT_VERSION,
+        addr: ctx.accounts.blocklist_entry.addr,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        timestamp: Clock::get()?.unix_timestamp,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        &mut ctx.accounts.program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::UnblockAddress,
+        &[ctx.accounts.blocklist_entry.addr.as_ref()],
+    )?;
+
+    Ok(())
+}
+
+/// Find the first blocked address among blocklist entry PDAs
+/// 
+/// Entries are passed at their PDA addresses whether or not they exist; only a blocked address has an 
+/// initialized entry.
+pub fn find_blocked_address(entries: &[&UncheckedAccount]) -> Result<Option<Pubkey>> {
+    for entry in entries {
+        if entry.owner == &crate::ID && !entry.data_is_empty() {
+            let data = entry.try_borrow_data()?;
+            let blocked = BlockedAddress::try_deserialize(&mut &data[..])?;
+            return Ok(Some(blocked.addr));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Fail a transfer if any of its parties is blocked
+/// 
+/// The block is logged before the error, so the failed transaction's logs keep it on record.
+pub fn require_not_blocked(
+    entries: &[&UncheckedAccount],
+    escrow_id: &[u8; 32],
+    transfer: BlockedTransfer,
+) -> Result<()> {
+    if let Some(blocked_addr) = find_blocked_address(entries)? {
+        log_escrow_action(log_actions::BLOCK, escrow_id, &[("transfer", transfer as u64)]);
+        msg!("blocked address: {}", blocked_addr);
+        return err!(EscrowError::AddressBlocked);
+    }
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct AddressBlockedEvent {
+    pub version: u8,
+    pub addr: Pubkey,
+    pub seq: u64,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AddressUnblockedEvent {
+    pub version: u8,
+    pub addr: Pubkey,
+    pub seq: u64,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,342 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BundleAssetParams {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Attach a bundle of additional assets to an escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeBundleAsym<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = EscrowBundle::space(),
+        seeds = [seeds::BUNDLE, escrow.key().as_ref()],
+        bump
+    )]
+    pub bundle: Account<'info, EscrowBundle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_bundle(ctx: Context<InitializeBundleAsym>, assets: Vec<BundleAssetParams>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+
+    //check authorization (creator or payer)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.creator || signer == escrow.payer.addr,
+        EscrowError::Unauthorized
+    );
+
+    //validate assets
+    require!(
+        !assets.is_empty() && assets.len() <= MAX_BUNDLE_ASSETS,
+        EscrowError::InvalidBundle
+    );
+    for (i, asset) in assets.iter().enumerate() {
+        require!(asset.amount > 0, EscrowError::InvalidAmount);
+        require!(
+            asset.mint != Pubkey::default() && !assets[..i].iter().any(|a| a.mint == asset.mint),
+            EscrowError::InvalidBundle
+        );
+    }
+
+    let bundle = &mut ctx.accounts.bundle;
+    bundle.escrow = escrow.key();
+    bundle.assets = assets
+        .iter()
+        .map(|a| BundleAsset {
+            mint: a.mint,
+            amount: a.amount,
+            amount_deposited: 0,
+            vault: Pubkey::default(),
+        })
+        .collect();
+    bundle.settled = false;
+    bundle.rent_payer = signer;
+    bundle.bump = ctx.bumps.bundle;
+
+    emit_cpi!(BundleInitializedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        bundle: bundle.key(),
+        asset_count: bundle.assets.len() as u8,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Deposit one bundle asset into its vault-owned token account
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct DepositBundleAssetAsym<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [seeds::BUNDLE, escrow.key().as_ref()],
+        bump = bundle.bump,
+        constraint = !bundle.settled @ EscrowError::BundleAlreadySettled,
+    )]
+    pub bundle: Account<'info, EscrowBundle>,
+
+    /// Escrow vault, owner of the bundle's token accounts
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = asset_mint,
+        token::authority = payer,
+        token::token_program = token_program,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = asset_mint,
+        token::authority = escrow_vault,
+        token::token_program = token_program,
+        seeds = [seeds::BUNDLE_VAULT, escrow.key().as_ref(), asset_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = bundle.assets
+            .get(index as usize)
+            .is_some_and(|a| a.mint == asset_mint.key()) @ EscrowError::InvalidBundle,
+    )]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit_bundle_asset(ctx: Context<DepositBundleAssetAsym>, index: u8) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    //validate payer
+    let escrow = &ctx.accounts.escrow;
+    require!(
+        ctx.accounts.payer.key() == escrow.payer.addr,
+        EscrowError::Unauthorized
+    );
+
+    //check escrow timing
+    require!(escrow.is_active_time(), EscrowError::EscrowNotActive);
+
+    //bundle assets settle in one instruction, which has no room for transfer hook accounts
+    require!(
+        get_transfer_hook_program(&ctx.accounts.asset_mint)?.is_none(),
+        EscrowError::TransferHookNotAllowed
+    );
+
+    let amount = ctx.accounts.bundle.assets[index as usize].amount;
+    transfer_spl_tokens(
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.asset_mint,
+        &ctx.accounts.payer,
+        amount,
+        &ctx.accounts.token_program,
+        &[],
+    )?;
+
+    //transfer fee extensions withhold part of the amount at the destination
+    ctx.accounts.vault_token_account.reload()?;
+    let amount_received = ctx.accounts.vault_token_account.amount;
+    require!(amount_received > 0, EscrowError::InvalidAmount);
+
+    let asset = &mut ctx.accounts.bundle.assets[index as usize];
+    asset.amount_deposited = amount_received;
+    asset.vault = ctx.accounts.vault_token_account.key();
+
+    emit_cpi!(BundleAssetDepositedEvent {
+        version: EVENT_VERSION,
+        escrow_id: ctx.accounts.escrow.id,
+        seq: ctx.accounts.escrow.next_event_seq(),
+        escrow: ctx.accounts.escrow.key(),
+        mint: asset.mint,
+        amount: amount_received,
+        fully_deposited: ctx.accounts.bundle.is_fully_deposited(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Settle a completed escrow's bundle, moving every deposited asset to the party the escrow settled to
+/// 
+/// For each deposited asset, in bundle order, the remaining accounts carry: the asset mint, its vault 
+/// token account, the recipient's token account, and the asset's token program.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleBundleAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        mut,
+        seeds = [seeds::BUNDLE, escrow.key().as_ref()],
+        bump = bundle.bump,
+        constraint = !bundle.settled @ EscrowError::BundleAlreadySettled,
+    )]
+    pub bundle: Account<'info, EscrowBundle>,
+
+    /// Escrow vault, owner of the bundle's token accounts
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+}
+
+pub fn settle_bundle<'info>(ctx: Context<'_, '_, 'info, 'info, SettleBundleAsym<'info>>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+
+    //the bundle follows the escrow's settlement
+    let recipient = if escrow.is_settled_to_receiver() {
+        escrow.receiver.addr
+    } else {
+        escrow.payer.addr
+    };
+
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    let deposited: Vec<&BundleAsset> = ctx.accounts.bundle.assets
+        .iter()
+        .filter(|a| a.vault != Pubkey::default())
+        .collect();
+    require!(
+        ctx.remaining_accounts.len() == deposited.len() * 4,
+        EscrowError::InvalidBundle
+    );
+
+    for (asset, accounts) in deposited.iter().zip(ctx.remaining_accounts.chunks(4)) {
+        let mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+        let vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+        let destination = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+        let token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+        require!(
+            mint.key() == asset.mint && vault.key() == asset.vault,
+            EscrowError::InvalidBundle
+        );
+        require!(
+            destination.mint == asset.mint && destination.owner == recipient,
+            EscrowError::InvalidToken
+        );
+
+        transfer_spl_tokens_signed(
+            &vault,
+            &destination,
+            &mint,
+            ctx.accounts.escrow_vault.to_account_info(),
+            asset.amount_deposited,
+            &token_program,
+            vault_signer,
+            &[],
+        )?;
+    }
+
+    let asset_count = deposited.len() as u8;
+    ctx.accounts.bundle.settled = true;
+
+    emit_cpi!(BundleSettledEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        recipient,
+        asset_count,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct BundleInitializedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub bundle: Pubkey,
+    pub asset_count: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct BundleAssetDepositedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fully_deposited: bool,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct BundleSettledEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+    pub asset_count: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
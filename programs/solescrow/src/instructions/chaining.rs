@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+use crate::instructions::interest::crystallize_interest;
+use crate::instructions::asym_escrow::EscrowStatusChangedEvent;
+
+/// Chain an escrow's release into another escrow (signed by the party linking the two)
+/// 
+/// The receiver of the upstream escrow must be the payer of the downstream escrow, e.g. an agency that is 
+/// paid by a client and pays a contractor. Funds released upstream are deposited straight into the 
+/// downstream escrow's vault and count as the agency's payment there.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ChainEscrowAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.receiver.addr == signer.key() @ EscrowError::Unauthorized,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+        constraint = escrow.status != EscrowStatus::Arbitration @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        mut,
+        constraint = downstream_escrow.key() != escrow.key() @ EscrowError::InvalidEscrowChain,
+        constraint = downstream_escrow.payer.addr == signer.key() @ EscrowError::Unauthorized,
+        constraint = downstream_escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub downstream_escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn chain_escrow(ctx: Context<ChainEscrowAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &ctx.accounts.escrow;
+    let downstream = &ctx.accounts.downstream_escrow;
+    
+    //the release destination can only be set before anything is released
+    require!(!escrow.is_chained(), EscrowError::InvalidEscrowChain);
+    require!(!escrow.released && escrow.payer.amount_released == 0, EscrowError::AlreadyReleased);
+    require!(!escrow.is_claim_tokenized(), EscrowError::InvalidEscrowChain);
+    
+    //both escrows must hold the same fungible currency, fixed for good
+    require!(
+        matches!(escrow.payer.currency_type, CurrencyType::Native | CurrencyType::SplToken)
+            && escrow.payer.currency_type == downstream.payer.currency_type
+            && escrow.payer.currency == downstream.payer.currency,
+        EscrowError::InvalidCurrency
+    );
+    require!(
+        escrow.accepted_mints.is_empty() && downstream.accepted_mints.is_empty(),
+        EscrowError::InvalidCurrency
+    );
+    require!(!downstream.is_usd_denominated(), EscrowError::InvalidEscrowChain);
+    
+    //cap the depth of the chain
+    let depth = escrow.chain_depth
+        .checked_add(1)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    require!(depth <= MAX_ESCROW_CHAIN_DEPTH, EscrowError::EscrowChainTooDeep);
+    
+    let downstream_key = downstream.key();
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.chained_escrow = downstream_key;
+    let downstream = &mut ctx.accounts.downstream_escrow;
+    downstream.chain_depth = downstream.chain_depth.max(depth);
+    
+    emit_cpi!(EscrowChainedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        downstream_escrow: downstream_key,
+        chain_depth: downstream.chain_depth,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Credit funds delivered into a downstream escrow's vault as payment by its payer
+pub fn credit_chained_escrow(
+    downstream: &mut Account<AsymEscrow>,
+    source: Pubkey,
+    amount: u64,
+    emitter: &EventEmitter,
+) -> Result<()> {
+    require!(
+        downstream.status != EscrowStatus::Completed && downstream.status != EscrowStatus::Arbitration,
+        EscrowError::InvalidEscrowState
+    );
+    crystallize_interest(downstream, Clock::get()?.unix_timestamp, emitter)?;
+    
+    //an expired downstream escrow takes the funds but stays expired, leaving them refundable
+    if downstream.status != EscrowStatus::Expired {
+        if let Some(old) = downstream.set_status(EscrowStatus::Active) {
+            emitter.emit(EscrowStatusChangedEvent {
+                version: EVENT_VERSION,
+                escrow_id: downstream.id,
+                seq: downstream.next_event_seq(),
+                escrow: downstream.key(),
+                old,
+                new: EscrowStatus::Active,
+                actor: source,
+                timestamp: Clock::get()?.unix_timestamp,
+                reserved: [0; EVENT_RESERVED_BYTES],
+            })?;
+        }
+    }
+    downstream.payer.amount_paid = downstream.payer.amount_paid
+        .checked_add(amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    emitter.emit(ChainedPaymentReceivedEvent {
+        version: EVENT_VERSION,
+        escrow_id: downstream.id,
+        seq: downstream.next_event_seq(),
+        escrow: downstream.key(),
+        source_escrow: source,
+        amount,
+        total_paid: downstream.payer.amount_paid,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct EscrowChainedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub downstream_escrow: Pubkey,
+    pub chain_depth: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ChainedPaymentReceivedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub source_escrow: Pubkey,
+    pub amount: u64,
+    pub total_paid: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
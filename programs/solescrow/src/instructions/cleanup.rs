@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::{self, CloseAccount, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Close the auxiliary accounts of a completed asymmetric escrow, refunding their rent (permissionless)
+///
+/// The accounts are passed as writable remaining accounts, each followed by the account its rent goes back
+/// to. Resolved disputes, arbiter panels and settled bundles refund the rent payer recorded on them, panel
+/// votes refund their voter, and the native vault's rent reserve and emptied token accounts of the escrow
+/// vault refund the escrow's payer, who funded the deposits. The escrow account itself is kept as the
+/// record of the settlement. Slashing an arbiter reads the resolved dispute, so it must happen before the
+/// dispute is collected.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GarbageCollectAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    /// Escrow vault, owner of the escrow's token accounts
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+
+    /// Token program of the token accounts being closed (only required to close token accounts)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Only required to empty the native vault
+    pub system_program: Option<Program<'info, System>>,
+}
+
+pub fn garbage_collect<'info>(ctx: Context<'_, '_, 'info, 'info, GarbageCollectAsym<'info>>) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() % 2 == 0,
+        EscrowError::InvalidCleanupAccount
+    );
+
+    let escrow = &mut ctx.accounts.escrow;
+    let escrow_key = escrow.key();
+    let dispute_key = Pubkey::find_program_address(&[seeds::DISPUTE, escrow_key.as_ref()], &crate::ID).0;
+
+    //generate signer seeds for escrow vault
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    let mut rent_reclaimed = 0u64;
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        let (account, rent_recipient) = (&accounts[0], &accounts[1]);
+        require!(
+            account.is_writable && rent_recipient.is_writable,
+            EscrowError::InvalidCleanupAccount
+        );
+        let lamports = account.lamports();
+
+        if account.owner == &crate::ID {
+            let rent_payer = program_account_rent_payer(account, &escrow_key, &dispute_key)?;
+            require_keys_eq!(rent_recipient.key(), rent_payer, EscrowError::InvalidCleanupAccount);
+            transfer_lamports_from_program_account(account, rent_recipient, lamports)?;
+            account.assign(&anchor_lang::system_program::ID);
+            account.resize(0)?;
+        } else if account.key() == ctx.accounts.escrow_vault.key() {
+            //the native vault holds only its rent reserve once the escrow completes
+            let system_program = ctx.accounts.system_program
+                .as_ref()
+                .ok_or(EscrowError::InvalidCleanupAccount)?;
+            require_keys_eq!(rent_recipient.key(), escrow.payer.addr, EscrowError::InvalidCleanupAccount);
+            transfer_native_sol_signed(
+                account.clone(),
+                rent_recipient.clone(),
+                lamports,
+                system_program.to_account_info(),
+                vault_signer,
+            )?;
+        } else {
+            //anything else must be an emptied token account of the escrow vault
+            let token_program = ctx.accounts.token_program
+                .as_ref()
+                .ok_or(EscrowError::InvalidToken)?;
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(account)?;
+            require!(
+                account.owner == &token_program.key()
+                    && token_account.owner == ctx.accounts.escrow_vault.key()
+                    && token_account.amount == 0,
+                EscrowError::InvalidCleanupAccount
+            );
+            require_keys_eq!(rent_recipient.key(), escrow.payer.addr, EscrowError::InvalidCleanupAccount);
+
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                CloseAccount {
+                    account: account.clone(),
+                    destination: rent_recipient.clone(),
+                    authority: ctx.accounts.escrow_vault.to_account_info(),
+                },
+                vault_signer,
+            ))?;
+        }
+
+        rent_reclaimed = rent_reclaimed
+            .checked_add(lamports)
+            .ok_or(EscrowError::ArithmeticOverflow)?;
+    }
+
+    emit_cpi!(EscrowGarbageCollectedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
+        accounts_closed: (ctx.remaining_accounts.len() / 2) as u16,
+        rent_reclaimed,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+//helper function to check a program account belongs to the escrow and is done with, returning its rent payer
+fn program_account_rent_payer(account: &AccountInfo, escrow: &Pubkey, dispute: &Pubkey) -> Result<Pubkey> {
+    let data = account.try_borrow_data()?;
+    let discriminator = data.get(..8).ok_or(EscrowError::InvalidCleanupAccount)?;
+
+    if discriminator == Dispute::DISCRIMINATOR {
+        //bonds and fee deposits must have been paid out before the dispute goes
+        let dispute = Dispute::try_deserialize(&mut &data[..])?;
+        require!(
+            dispute.escrow == *escrow
+                && dispute.status == DisputeStatus::Resolved
+                && dispute.payer_bond == 0
+                && dispute.receiver_bond == 0
+                && dispute.appeal_bond == 0
+                && dispute.payer_fee_deposit == 0
+                && dispute.receiver_fee_deposit == 0,
+            EscrowError::InvalidCleanupAccount
+        );
+        Ok(dispute.rent_payer)
+    } else if discriminator == ArbiterPanel::DISCRIMINATOR {
+        let arbiter_panel = ArbiterPanel::try_deserialize(&mut &data[..])?;
+        require!(arbiter_panel.escrow == *escrow, EscrowError::InvalidCleanupAccount);
+        Ok(arbiter_panel.rent_payer)
+    } else if discriminator == ArbitrationVote::DISCRIMINATOR {
+        let vote = ArbitrationVote::try_deserialize(&mut &data[..])?;
+        require!(vote.dispute == *dispute, EscrowError::InvalidCleanupAccount);
+        Ok(vote.voter)
+    } else if discriminator == EscrowBundle::DISCRIMINATOR {
+        //a bundle still tracking deposited assets has to be settled first
+        let bundle = EscrowBundle::try_deserialize(&mut &data[..])?;
+        require!(
+            bundle.escrow == *escrow
+                && (bundle.settled || bundle.assets.iter().all(|a| a.vault == Pubkey::default())),
+            EscrowError::InvalidCleanupAccount
+        );
+        Ok(bundle.rent_payer)
+    } else {
+        err!(EscrowError::InvalidCleanupAccount)
+    }
+}
+
+//events
+#[event]
+pub struct EscrowGarbageCollectedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub accounts_closed: u16,
+    pub rent_reclaimed: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Require a threshold of co-signers (e.g. 2 of 3 company officers) to give a party's release consent
+/// 
+/// Once set, the party's own key, delegate and session key no longer consent to release; each co-signer 
+/// records its assent on the escrow and the party consents when the threshold is reached. Only one party 
+/// can be co-signed, and co-signers can only be changed before the escrow is funded. An empty list removes 
+/// the requirement.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCoSignersAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn set_co_signers(ctx: Context<SetCoSignersAsym>, co_signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    
+    //only the party's own key can set its co-signers, and only one party can be co-signed
+    let is_payer = signer == escrow.payer.addr;
+    require!(is_payer || signer == escrow.receiver.addr, EscrowError::Unauthorized);
+    require!(
+        escrow.co_signed_party == Pubkey::default() || escrow.co_signed_party == signer,
+        EscrowError::InvalidCoSigners
+    );
+    
+    //validate the threshold
+    if co_signers.is_empty() {
+        require!(threshold == 0, EscrowError::InvalidCoSigners);
+    } else {
+        require!(co_signers.len() <= MAX_CO_SIGNERS, EscrowError::InvalidCoSigners);
+        require!(
+            threshold > 0 && threshold as usize <= co_signers.len(),
+            EscrowError::InvalidCoSigners
+        );
+    }
+    
+    //co-signers must be distinct, and never able to act for the other side
+    let counterparty = if is_payer { &escrow.receiver } else { &escrow.payer };
+    for (i, key) in co_signers.iter().enumerate() {
+        require!(
+            *key != Pubkey::default()
+                && !co_signers[..i].contains(key)
+                && *key != counterparty.addr
+                && *key != counterparty.delegate
+                && *key != counterparty.session_key
+                && !escrow.is_observer(key),
+            EscrowError::InvalidCoSigners
+        );
+    }
+    
+    escrow.co_signed_party = if co_signers.is_empty() { Pubkey::default() } else { signer };
+    escrow.co_signer_threshold = threshold;
+    escrow.co_signers = co_signers
+        .iter()
+        .map(|key| CoSigner { key: *key, assented: false })
+        .collect();
+    
+    emit_cpi!(CoSignersSetEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        party: signer,
+        co_signers,
+        threshold,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Record a co-signer's assent to release, returning whether the co-signed party's threshold is now met
+pub fn record_co_signer_assent(escrow: &mut Account<AsymEscrow>, index: usize, emitter: &EventEmitter) -> Result<bool> {
+    if !escrow.co_signers[index].assented {
+        escrow.co_signers[index].assented = true;
+        emitter.emit(CoSignerAssentEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            party: escrow.co_signed_party,
+            co_signer: escrow.co_signers[index].key,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    
+    Ok(escrow.is_co_signer_threshold_met())
+}
+
+/// Clear all co-signer assents, e.g. when the parties must consent again
+pub fn reset_co_signer_assents(escrow: &mut AsymEscrow) {
+    for co_signer in escrow.co_signers.iter_mut() {
+        co_signer.assented = false;
+    }
+}
+
+//events
+#[event]
+pub struct CoSignersSetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub co_signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct CoSignerAssentEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub co_signer: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+use crate::instructions::expiry::mark_expired_asym;
+use crate::instructions::interest::crystallize_interest;
+
+/// Apply the timed actions due on a batch of asymmetric escrows (permissionless crank)
+/// 
+/// The escrows are passed as writable remaining accounts. Past-due escrows are marked expired and late 
+/// invoices have their interest crystallized, so keepers tending many small escrows pay for one transaction 
+/// instead of one per escrow. Escrows with nothing due are skipped rather than failing the batch, since 
+/// another keeper may have cranked them first.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CrankEscrowsAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn crank_escrows_asym<'info>(ctx: Context<'_, '_, 'info, 'info, CrankEscrowsAsym<'info>>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_CRANK_BATCH,
+        EscrowError::InvalidCrankBatch
+    );
+    
+    let signer = ctx.accounts.signer.key();
+    let emitter = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    let now = Clock::get()?.unix_timestamp;
+    let mut cranked = 0u16;
+    for escrow_info in ctx.remaining_accounts {
+        require!(escrow_info.is_writable, EscrowError::InvalidCrankBatch);
+        let mut escrow = Account::<AsymEscrow>::try_from(escrow_info)?;
+        if escrow.status == EscrowStatus::Completed || escrow.external_call_in_progress {
+            continue;
+        }
+        
+        //interest runs up to the moment the escrow expires
+        let interest_due = escrow.pending_interest(now) > 0;
+        crystallize_interest(&mut escrow, now, &emitter)?;
+        let expired = mark_expired_asym(&mut escrow, signer, &emitter)?;
+        if interest_due || expired {
+            escrow.exit(&crate::ID)?;
+            cranked += 1;
+        }
+    }
+    
+    let program_config = &mut ctx.accounts.program_config;
+    emit_cpi!(EscrowsCrankedEvent {
+        version: EVENT_VERSION,
+        seq: program_config.next_event_seq(),
+        signer,
+        escrows: ctx.remaining_accounts.len() as u16,
+        cranked,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct EscrowsCrankedEvent {
+    pub version: u8,
+    pub seq: u64,
+    pub signer: Pubkey,
+    pub escrows: u16,
+    pub cranked: u16,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
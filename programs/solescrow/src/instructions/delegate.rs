@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Authorize a hot wallet to deposit and consent to release for a party (payer or receiver)
+/// 
+/// The delegate acts only up to its per-action limit; everything else, including changing the delegate, 
+/// stays with the party's own key. Pubkey::default() revokes the delegate.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDelegateAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn set_delegate(ctx: Context<SetDelegateAsym>, delegate: Pubkey, delegate_limit: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    
+    //only the party's own key can change its delegate
+    let is_payer = signer == escrow.payer.addr;
+    require!(is_payer || signer == escrow.receiver.addr, EscrowError::Unauthorized);
+    
+    //one key must never be able to act for both sides
+    let counterparty = if is_payer { &escrow.receiver } else { &escrow.payer };
+    require!(
+        delegate == Pubkey::default()
+            || (delegate != signer
+                && delegate != counterparty.addr
+                && delegate != counterparty.delegate
+                && delegate != counterparty.session_key
+                && escrow.co_signer_index(&delegate).is_none()
+                && !escrow.is_observer(&delegate)),
+        EscrowError::InvalidDelegate
+    );
+    
+    let party = if is_payer { &mut escrow.payer } else { &mut escrow.receiver };
+    
+    party.delegate = delegate;
+    party.delegate_limit = if delegate == Pubkey::default() { 0 } else { delegate_limit };
+    
+    emit_cpi!(DelegateSetEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        party: signer,
+        delegate,
+        delegate_limit,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Register or revoke a party's short-lived session key
+/// 
+/// The session key can perform the permitted actions until its expiry slot, so a front-end can act for 
+/// the party without prompting its wallet each time. Registering a new key replaces the previous one.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ManageSessionKeyAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Register a session key for a party (payer or receiver)
+pub fn register_session_key(
+    ctx: Context<ManageSessionKeyAsym>,
+    session_key: Pubkey,
+    permissions: u8,
+    expiry_slot: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    
+    //only the party's own key can register a session key
+    let is_payer = signer == escrow.payer.addr;
+    require!(is_payer || signer == escrow.receiver.addr, EscrowError::Unauthorized);
+    
+    //validate permissions and expiry; only the receiver can refund
+    let allowed = if is_payer {
+        session_permissions::DEPOSIT | session_permissions::RELEASE
+    } else {
+        session_permissions::RELEASE | session_permissions::REFUND
+    };
+    require!(
+        permissions != 0 && permissions & !allowed == 0,
+        EscrowError::InvalidSessionKey
+    );
+    let slot = Clock::get()?.slot;
+    require!(
+        expiry_slot > slot && expiry_slot <= slot.saturating_add(MAX_SESSION_SLOTS),
+        EscrowError::InvalidSessionKey
+    );
+    
+    //one key must never be able to act for both sides
+    let counterparty = if is_payer { &escrow.receiver } else { &escrow.payer };
+    require!(
+        session_key != Pubkey::default()
+            && session_key != signer
+            && session_key != counterparty.addr
+            && session_key != counterparty.delegate
+            && session_key != counterparty.session_key
+            && escrow.co_signer_index(&session_key).is_none()
+            && !escrow.is_observer(&session_key),
+        EscrowError::InvalidSessionKey
+    );
+    
+    let party = if is_payer { &mut escrow.payer } else { &mut escrow.receiver };
+    party.session_key = session_key;
+    party.session_permissions = permissions;
+    party.session_expiry_slot = expiry_slot;
+    
+    emit_cpi!(SessionKeyRegisteredEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        party: signer,
+        session_key,
+        permissions,
+        expiry_slot,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Revoke a party's session key before it expires (the party itself or the session key)
+pub fn revoke_session_key(ctx: Context<ManageSessionKeyAsym>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    
+    let party = if signer == escrow.payer.addr || signer == escrow.payer.session_key {
+        &mut escrow.payer
+    } else if signer == escrow.receiver.addr || signer == escrow.receiver.session_key {
+        &mut escrow.receiver
+    } else {
+        return err!(EscrowError::Unauthorized);
+    };
+    require!(party.session_key != Pubkey::default(), EscrowError::InvalidSessionKey);
+    
+    let session_key = party.session_key;
+    party.session_key = Pubkey::default();
+    party.session_permissions = 0;
+    party.session_expiry_slot = 0;
+    
+    emit_cpi!(SessionKeyRevokedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        session_key,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct DelegateSetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub delegate: Pubkey,
+    pub delegate_limit: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct SessionKeyRegisteredEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub session_key: Pubkey,
+    pub permissions: u8,
+    pub expiry_slot: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct SessionKeyRevokedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub session_key: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
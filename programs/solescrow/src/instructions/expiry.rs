@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+use crate::instructions::asym_escrow::EscrowStatusChangedEvent;
+
+/// Mark an asymmetric escrow expired once its end time has passed (permissionless crank)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireEscrowAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub escrow: Account<'info, AsymEscrow>,
+}
+
+pub fn expire_escrow_asym(ctx: Context<ExpireEscrowAsym>) -> Result<()> {
+    let expired = mark_expired_asym(
+        &mut ctx.accounts.escrow,
+        ctx.accounts.signer.key(),
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    require!(expired, EscrowError::EscrowNotExpired);
+
+    Ok(())
+}
+
+/// Mark a symmetric escrow expired once its end time has passed (permissionless crank)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireEscrowSym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub escrow: Account<'info, SymEscrow>,
+}
+
+pub fn expire_escrow_sym(ctx: Context<ExpireEscrowSym>) -> Result<()> {
+    let expired = mark_expired_sym(
+        &mut ctx.accounts.escrow,
+        ctx.accounts.signer.key(),
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    require!(expired, EscrowError::EscrowNotExpired);
+
+    Ok(())
+}
+
+/// Set an open, past-due asymmetric escrow to `Expired`, returning whether it was
+/// 
+/// Called by the crank and by any instruction that can proceed on an expired escrow, so the expiry is 
+/// recorded by whichever comes first.
+pub fn mark_expired_asym(escrow: &mut Account<AsymEscrow>, actor: Pubkey, emitter: &EventEmitter) -> Result<bool> {
+    let now = Clock::get()?.unix_timestamp;
+    if !escrow.can_expire(now) {
+        return Ok(false);
+    }
+
+    if let Some(old) = escrow.set_status(EscrowStatus::Expired) {
+        emitter.emit(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Expired,
+            actor,
+            timestamp: now,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    log_escrow_action(log_actions::EXPIRE, &escrow.id, &[]);
+    emitter.emit(EscrowExpiredEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        end_time: escrow.end_time,
+        timestamp: now,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+
+    Ok(true)
+}
+
+/// Set an open, past-due symmetric escrow to `Expired`, returning whether it was
+pub fn mark_expired_sym(escrow: &mut Account<SymEscrow>, actor: Pubkey, emitter: &EventEmitter) -> Result<bool> {
+    let now = Clock::get()?.unix_timestamp;
+    if !escrow.can_expire(now) {
+        return Ok(false);
+    }
+
+    if let Some(old) = escrow.set_status(EscrowStatus::Expired) {
+        emitter.emit(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Expired,
+            actor,
+            timestamp: now,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        })?;
+    }
+    log_escrow_action(log_actions::EXPIRE, &escrow.id, &[]);
+    emitter.emit(EscrowExpiredEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        end_time: escrow.end_time,
+        timestamp: now,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+
+    Ok(true)
+}
+
+//events
+#[event]
+pub struct EscrowExpiredEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub end_time: i64,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Assign a guardian that can hold large releases (both parties must sign)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetGuardianAsym<'info> {
+    pub payer: Signer<'info>,
+    
+    pub receiver: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.addr == payer.key() @ EscrowError::InvalidPayer,
+        constraint = escrow.receiver.addr == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn set_guardian(ctx: Context<SetGuardianAsym>, guardian: Pubkey, guardian_threshold: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //a guardian already in place (e.g. the global one) cannot be replaced by the parties
+    require!(!escrow.has_guardian(), EscrowError::InvalidGuardian);
+    require!(!escrow.is_htlc(), EscrowError::InvalidGuardian);
+    
+    //the guardian must be independent of both parties
+    require!(guardian != Pubkey::default(), EscrowError::InvalidGuardian);
+    require!(
+        guardian != escrow.payer.addr && guardian != escrow.receiver.addr,
+        EscrowError::InvalidGuardian
+    );
+    
+    escrow.guardian = guardian;
+    escrow.guardian_threshold = guardian_threshold;
+    
+    emit_cpi!(GuardianSetEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        guardian,
+        guardian_threshold,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Hold or lift a hold on a large release (guardian only)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GuardianHoldAsym<'info> {
+    pub guardian: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.has_guardian() @ EscrowError::InvalidGuardian,
+        constraint = escrow.guardian == guardian.key() @ EscrowError::InvalidGuardian,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn hold_release(ctx: Context<GuardianHoldAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let now = Clock::get()?.unix_timestamp;
+    
+    //only releases above the threshold can be held, and only once per escrow
+    require!(
+        escrow.get_amount_remaining() > escrow.guardian_threshold,
+        EscrowError::InvalidGuardian
+    );
+    require!(escrow.guardian_hold_until == 0, EscrowError::InvalidGuardian);
+    
+    //the hold only delays the release; it cannot redirect funds
+    escrow.guardian_hold_until = now
+        .checked_add(GUARDIAN_HOLD_WINDOW)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    emit_cpi!(ReleaseHeldEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        guardian: escrow.guardian,
+        hold_until: escrow.guardian_hold_until,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+pub fn lift_release_hold(ctx: Context<GuardianHoldAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < escrow.guardian_hold_until, EscrowError::InvalidGuardian);
+    
+    escrow.guardian_hold_until = now;
+    
+    emit_cpi!(ReleaseHoldLiftedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        guardian: escrow.guardian,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct GuardianSetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub guardian: Pubkey,
+    pub guardian_threshold: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ReleaseHeldEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub guardian: Pubkey,
+    pub hold_until: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ReleaseHoldLiftedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub guardian: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct InitializeProgramParams {
@@ -8,6 +10,32 @@ pub struct InitializeProgramParams {
     pub default_fee_bps: u16,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct UpdateProgramConfigParams {
+    pub fee_vault: Option<Pubkey>,
+    pub default_fee_bps: Option<u16>,
+    pub paused: Option<bool>,
+    pub evidence_window: Option<i64>,
+    pub decision_window: Option<i64>,
+    pub default_resolution: Option<DefaultResolution>,
+    pub dispute_bond: Option<u64>,
+    pub insurance_fee_bps: Option<u16>,
+    pub min_dispute_window: Option<i64>,
+    pub max_dispute_window: Option<i64>,
+    pub transfer_hook_allowlist: Option<Vec<Pubkey>>,
+    pub yield_fee_bps: Option<u16>,
+    pub lending_adapter_allowlist: Option<Vec<Pubkey>>,
+    pub stake_pool_allowlist: Option<Vec<Pubkey>>,
+    pub guardian: Option<Pubkey>,
+    pub guardian_threshold: Option<u64>,
+    pub settlement_hook_allowlist: Option<Vec<Pubkey>>,
+    pub automation_program_allowlist: Option<Vec<Pubkey>>,
+    pub swap_router_allowlist: Option<Vec<Pubkey>>,
+    pub max_swap_slippage_bps: Option<u16>,
+    pub max_swap_price_impact_bps: Option<u16>,
+    pub compact_events: Option<bool>,
+}
+
 /// Initialize program configuration
 #[derive(Accounts)]
 pub struct InitializeProgram<'info> {
@@ -36,6 +64,259 @@ pub fn initialize_program(
     program_config.default_fee_bps = params.default_fee_bps;
     program_config.paused = false;
     program_config.bump = ctx.bumps.program_config;
+    program_config.evidence_window = DEFAULT_EVIDENCE_WINDOW;
+    program_config.decision_window = DEFAULT_DECISION_WINDOW;
+    program_config.default_resolution = DefaultResolution::default();
+    program_config.dispute_bond = DEFAULT_DISPUTE_BOND;
+    program_config.insurance_fee_bps = 0;
+    program_config.min_dispute_window = DEFAULT_MIN_DISPUTE_WINDOW;
+    program_config.max_dispute_window = DEFAULT_MAX_DISPUTE_WINDOW;
+    program_config.transfer_hook_allowlist = Vec::new();
+    program_config.yield_fee_bps = 0;
+    program_config.lending_adapter_allowlist = Vec::new();
+    program_config.stake_pool_allowlist = Vec::new();
+    program_config.guardian = Pubkey::default();
+    program_config.guardian_threshold = 0;
+    program_config.event_seq = 0;
+    program_config.admin_action_count = 0;
+    program_config.settlement_hook_allowlist = Vec::new();
+    program_config.automation_program_allowlist = Vec::new();
+    program_config.swap_router_allowlist = vec![JUPITER_PROGRAM_ID];
+    program_config.max_swap_slippage_bps = DEFAULT_MAX_SWAP_SLIPPAGE_BPS;
+    program_config.max_swap_price_impact_bps = DEFAULT_MAX_SWAP_PRICE_IMPACT_BPS;
+    program_config.governance = Pubkey::default();
+    program_config.lookup_table = Pubkey::default();
+    program_config.compact_events = false;
+    
+    Ok(())
+}
+
+/// Update program configuration
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_program_config(
+    ctx: Context<UpdateProgramConfig>,
+    params: UpdateProgramConfigParams,
+) -> Result<()> {
+    //classify and hash the update for the audit trail before the params are consumed
+    let action = match params.paused {
+        Some(true) => AdminActionKind::Pause,
+        Some(false) => AdminActionKind::Unpause,
+        None => AdminActionKind::ConfigUpdate,
+    };
+    let params_data = params.try_to_vec()?;
+    
+    let program_config = &mut ctx.accounts.program_config;
+    
+    if let Some(fee_vault) = params.fee_vault {
+        program_config.fee_vault = fee_vault;
+    }
+    if let Some(default_fee_bps) = params.default_fee_bps {
+        require!(default_fee_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidConfig);
+        program_config.default_fee_bps = default_fee_bps;
+    }
+    if let Some(paused) = params.paused {
+        program_config.paused = paused;
+    }
+    if let Some(evidence_window) = params.evidence_window {
+        validate_dispute_phase_window(evidence_window)?;
+        program_config.evidence_window = evidence_window;
+    }
+    if let Some(decision_window) = params.decision_window {
+        validate_dispute_phase_window(decision_window)?;
+        program_config.decision_window = decision_window;
+    }
+    if let Some(default_resolution) = params.default_resolution {
+        program_config.default_resolution = default_resolution;
+    }
+    if let Some(dispute_bond) = params.dispute_bond {
+        program_config.dispute_bond = dispute_bond;
+    }
+    if let Some(insurance_fee_bps) = params.insurance_fee_bps {
+        require!(insurance_fee_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidConfig);
+        program_config.insurance_fee_bps = insurance_fee_bps;
+    }
+    if let Some(min_dispute_window) = params.min_dispute_window {
+        validate_dispute_phase_window(min_dispute_window)?;
+        program_config.min_dispute_window = min_dispute_window;
+    }
+    if let Some(max_dispute_window) = params.max_dispute_window {
+        validate_dispute_phase_window(max_dispute_window)?;
+        program_config.max_dispute_window = max_dispute_window;
+    }
+    require!(
+        program_config.min_dispute_window <= program_config.max_dispute_window,
+        EscrowError::InvalidConfig
+    );
+    if let Some(transfer_hook_allowlist) = params.transfer_hook_allowlist {
+        require!(
+            transfer_hook_allowlist.len() <= MAX_TRANSFER_HOOK_PROGRAMS,
+            EscrowError::InvalidConfig
+        );
+        program_config.transfer_hook_allowlist = transfer_hook_allowlist;
+    }
+    if let Some(yield_fee_bps) = params.yield_fee_bps {
+        require!(yield_fee_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidConfig);
+        program_config.yield_fee_bps = yield_fee_bps;
+    }
+    if let Some(lending_adapter_allowlist) = params.lending_adapter_allowlist {
+        require!(
+            lending_adapter_allowlist.len() <= MAX_LENDING_ADAPTERS,
+            EscrowError::InvalidConfig
+        );
+        program_config.lending_adapter_allowlist = lending_adapter_allowlist;
+    }
+    if let Some(stake_pool_allowlist) = params.stake_pool_allowlist {
+        require!(
+            stake_pool_allowlist.len() <= MAX_STAKE_POOLS,
+            EscrowError::InvalidConfig
+        );
+        program_config.stake_pool_allowlist = stake_pool_allowlist;
+    }
+    if let Some(guardian) = params.guardian {
+        program_config.guardian = guardian;
+    }
+    if let Some(guardian_threshold) = params.guardian_threshold {
+        program_config.guardian_threshold = guardian_threshold;
+    }
+    if let Some(settlement_hook_allowlist) = params.settlement_hook_allowlist {
+        require!(
+            settlement_hook_allowlist.len() <= MAX_SETTLEMENT_HOOKS,
+            EscrowError::InvalidConfig
+        );
+        program_config.settlement_hook_allowlist = settlement_hook_allowlist;
+    }
+    if let Some(automation_program_allowlist) = params.automation_program_allowlist {
+        require!(
+            automation_program_allowlist.len() <= MAX_AUTOMATION_PROGRAMS,
+            EscrowError::InvalidConfig
+        );
+        program_config.automation_program_allowlist = automation_program_allowlist;
+    }
+    if let Some(swap_router_allowlist) = params.swap_router_allowlist {
+        require!(
+            swap_router_allowlist.len() <= MAX_SWAP_ROUTERS,
+            EscrowError::InvalidConfig
+        );
+        program_config.swap_router_allowlist = swap_router_allowlist;
+    }
+    if let Some(max_swap_slippage_bps) = params.max_swap_slippage_bps {
+        require!(max_swap_slippage_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidConfig);
+        program_config.max_swap_slippage_bps = max_swap_slippage_bps;
+    }
+    if let Some(max_swap_price_impact_bps) = params.max_swap_price_impact_bps {
+        require!(max_swap_price_impact_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidConfig);
+        program_config.max_swap_price_impact_bps = max_swap_price_impact_bps;
+    }
+    if let Some(compact_events) = params.compact_events {
+        program_config.compact_events = compact_events;
+    }
+    
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        program_config,
+        ctx.accounts.authority.key(),
+        action,
+        &[&params_data],
+    )?;
+    
+    Ok(())
+}
+
+/// Hand the program authority to a new key, or to a Realms governance (authority only)
+/// 
+/// A key must co-sign to accept. A governance takes the authority through its native treasury PDA, which 
+/// signs (and pays audit rent) when a passed proposal is executed, so every authority-only instruction then 
+/// runs by token-holder vote.
+#[derive(Accounts)]
+pub struct SetProgramAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+    
+    /// CHECK: new authority; must sign unless it is the native treasury of `governance`
+    pub new_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Realms governance account taking the authority (only for governance handovers)
+    #[account(
+        owner = SPL_GOVERNANCE_PROGRAM_ID @ EscrowError::InvalidGovernance
+    )]
+    pub governance: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_program_authority(ctx: Context<SetProgramAuthority>) -> Result<()> {
+    let new_authority = ctx.accounts.new_authority.key();
+    
+    //a governance holds the authority through its treasury; a key accepts by signing
+    let governance = match ctx.accounts.governance.as_ref() {
+        Some(governance) => {
+            require_keys_eq!(
+                new_authority,
+                realms_native_treasury(&governance.key()),
+                EscrowError::InvalidGovernance
+            );
+            governance.key()
+        },
+        None => {
+            require!(ctx.accounts.new_authority.is_signer, EscrowError::Unauthorized);
+            Pubkey::default()
+        },
+    };
+    
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.authority = new_authority;
+    program_config.governance = governance;
+    
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::TransferAuthority,
+        &[new_authority.as_ref(), governance.as_ref()],
+    )?;
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Initialize the protocol insurance fund
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = InsuranceFund::space(),
+        seeds = [seeds::INSURANCE_FUND],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.balance = 0;
+    insurance_fund.total_deposited = 0;
+    insurance_fund.total_claimed = 0;
+    insurance_fund.bump = ctx.bumps.insurance_fund;
+
+    Ok(())
+}
+
+/// Pay an approved insurance claim out of the fund
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PayInsuranceClaim<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    #[account(
+        mut,
+        seeds = [seeds::INSURANCE_FUND],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    /// CHECK: party being compensated; may be a program-owned multisig vault
+    #[account(mut)]
+    pub claimant: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn pay_insurance_claim(
+    ctx: Context<PayInsuranceClaim>,
+    amount: u64,
+    claim_hash: [u8; 32],
+) -> Result<()> {
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+
+    //validate amount
+    require!(amount > 0, EscrowError::InvalidAmount);
+    require!(amount <= insurance_fund.balance, EscrowError::InsufficientFunds);
+
+    transfer_lamports_from_program_account(
+        &insurance_fund.to_account_info(),
+        &ctx.accounts.claimant.to_account_info(),
+        amount,
+    )?;
+
+    insurance_fund.balance -= amount;
+    insurance_fund.total_claimed = insurance_fund.total_claimed
+        .checked_add(amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+
+    emit_cpi!(InsuranceClaimPaidEvent {
+        version: EVENT_VERSION,
+        claimant: ctx.accounts.claimant.key(),
+        seq: ctx.accounts.program_config.next_event_seq(),
+        amount,
+        claim_hash,
+        remaining_balance: insurance_fund.balance,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        &mut ctx.accounts.program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::InsuranceClaim,
+        &[ctx.accounts.claimant.key().as_ref(), &amount.to_le_bytes(), &claim_hash],
+    )?;
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct InsuranceFundDepositEvent {
+    pub version: u8,
+    pub source: Pubkey,
+    pub seq: u64,
+    pub amount: u64,
+    pub balance: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct InsuranceClaimPaidEvent {
+    pub version: u8,
+    pub claimant: Pubkey,
+    pub seq: u64,
+    pub amount: u64,
+    pub claim_hash: [u8; 32],
+    pub remaining_balance: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Interest terms for an invoice-mode escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InterestTerms {
+    pub rate_bps: u16,           // annual rate on the unpaid balance
+    pub payment_due_time: i64,   // interest accrues after this timestamp
+    pub interest_cap: u64,       // maximum total interest, in the escrow currency
+}
+
+/// Set late-payment interest terms on an escrow (both parties must sign)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetInterestTermsAsym<'info> {
+    pub payer: Signer<'info>,
+    
+    pub receiver: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.addr == payer.key() @ EscrowError::InvalidPayer,
+        constraint = escrow.receiver.addr == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn set_interest_terms(ctx: Context<SetInterestTermsAsym>, terms: InterestTerms) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //interest is charged on a fixed fungible obligation in the escrow currency
+    require!(
+        matches!(escrow.payer.currency_type, CurrencyType::Native | CurrencyType::SplToken),
+        EscrowError::InvalidCurrency
+    );
+    require!(
+        !escrow.is_usd_denominated() && !escrow.has_hidden_terms() && escrow.accepted_mints.is_empty(),
+        EscrowError::InvalidInterestTerms
+    );
+    
+    //validate terms
+    require!(
+        terms.rate_bps > 0 && terms.rate_bps <= MAX_INTEREST_RATE_BPS,
+        EscrowError::InvalidInterestTerms
+    );
+    require!(terms.interest_cap > 0, EscrowError::InvalidInterestTerms);
+    require!(terms.payment_due_time > escrow.start_time, EscrowError::InvalidInterestTerms);
+    require!(
+        escrow.end_time == 0 || terms.payment_due_time < escrow.end_time,
+        EscrowError::InvalidInterestTerms
+    );
+    
+    escrow.interest_rate_bps = terms.rate_bps;
+    escrow.payment_due_time = terms.payment_due_time;
+    escrow.interest_cap = terms.interest_cap;
+    
+    emit_cpi!(InterestTermsSetEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        rate_bps: terms.rate_bps,
+        payment_due_time: terms.payment_due_time,
+        interest_cap: terms.interest_cap,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Crystallize accrued interest into the payer's obligation (callable by anyone)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccrueInterestAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn accrue_interest(ctx: Context<AccrueInterestAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    require!(escrow.accrues_interest(), EscrowError::InvalidInterestTerms);
+    
+    crystallize_interest(
+        escrow,
+        Clock::get()?.unix_timestamp,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    
+    Ok(())
+}
+
+/// Add interest accrued since the last crystallization to the payer's obligation
+pub fn crystallize_interest(escrow: &mut Account<AsymEscrow>, now: i64, emitter: &EventEmitter) -> Result<()> {
+    if !escrow.accrues_interest() || now <= escrow.payment_due_time {
+        return Ok(());
+    }
+    
+    let interest = escrow.pending_interest(now);
+    escrow.interest_accrued_at = now;
+    if interest == 0 {
+        return Ok(());
+    }
+    
+    escrow.accrued_interest = escrow.accrued_interest
+        .checked_add(interest)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    escrow.payer.amount = escrow.payer.amount
+        .checked_add(interest)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    emitter.emit(InterestAccruedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        interest,
+        accrued_interest: escrow.accrued_interest,
+        amount_due: escrow.payer.amount,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct InterestTermsSetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub rate_bps: u16,
+    pub payment_due_time: i64,
+    pub interest_cap: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct InterestAccruedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub interest: u64,
+    pub accrued_interest: u64,
+    pub amount_due: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,446 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Opt an escrow into earning yield on its idle funds (both parties must sign)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OptInLendingAsym<'info> {
+    pub payer: Signer<'info>,
+    
+    pub receiver: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.addr == payer.key() @ EscrowError::InvalidPayer,
+        constraint = escrow.receiver.addr == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn opt_in_lending(ctx: Context<OptInLendingAsym>, lending_adapter: Pubkey, yield_payer_bps: u16) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //only fungible SPL deposits can be lent
+    require!(escrow.payer.currency_type == CurrencyType::SplToken, EscrowError::InvalidToken);
+    require!(!escrow.is_lent(), EscrowError::FundsLent);
+    
+    //validate adapter and yield split
+    require!(
+        ctx.accounts.program_config.lending_adapter_allowlist.contains(&lending_adapter),
+        EscrowError::LendingAdapterNotAllowed
+    );
+    require!(yield_payer_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidAmount);
+    
+    escrow.lending_adapter = lending_adapter;
+    escrow.yield_payer_bps = yield_payer_bps;
+    
+    emit_cpi!(LendingOptInEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        lending_adapter,
+        yield_payer_bps,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Supply idle escrowed funds to the escrow's lending adapter
+/// 
+/// Accounts required by the lending protocol behind the adapter are passed as remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SupplyEscrowFundsAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Active @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, owner of the escrow token account
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow_vault,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: lending adapter program the escrow opted into
+    #[account(
+        executable,
+        address = escrow.lending_adapter @ EscrowError::LendingAdapterNotAllowed
+    )]
+    pub lending_adapter: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn supply_escrow_funds<'info>(
+    ctx: Context<'_, '_, '_, 'info, SupplyEscrowFundsAsym<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &ctx.accounts.escrow;
+    
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+    
+    //the adapter must still be allowlisted
+    require!(
+        ctx.accounts.program_config.lending_adapter_allowlist.contains(&escrow.lending_adapter),
+        EscrowError::LendingAdapterNotAllowed
+    );
+    
+    //only funds not already lent can be supplied
+    require!(amount > 0, EscrowError::InvalidAmount);
+    let idle_amount = escrow.get_amount_remaining().saturating_sub(escrow.lent_amount);
+    require!(amount <= idle_amount, EscrowError::AmountExceeded);
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    let balance_before = ctx.accounts.escrow_token_account.amount;
+    guarded_external_call(&mut ctx.accounts.escrow, || {
+        invoke_lending_adapter(
+            &ctx.accounts.lending_adapter,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            ctx.remaining_accounts,
+            LENDING_DEPOSIT_INSTRUCTION,
+            amount,
+            vault_signer,
+        )
+    })?;
+    
+    //the adapter must have taken exactly the supplied amount
+    ctx.accounts.escrow_token_account.reload()?;
+    require!(
+        balance_before.checked_sub(ctx.accounts.escrow_token_account.amount) == Some(amount),
+        EscrowError::InvalidAmount
+    );
+    
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.lent_amount = escrow.lent_amount
+        .checked_add(amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    emit_cpi!(EscrowFundsSuppliedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        lending_adapter: escrow.lending_adapter,
+        amount,
+        lent_amount: escrow.lent_amount,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Withdraw lent funds with their yield, splitting the yield between the protocol and the parties
+/// 
+/// Lent funds must be withdrawn before the escrow can be released, refunded or awarded.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawEscrowFundsAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(mut)]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, owner of the escrow token account
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow_vault,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Yield recipients (only required when their share is non-zero)
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == escrow.payer.addr @ EscrowError::InvalidPayer
+    )]
+    pub payer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == escrow.receiver.addr @ EscrowError::InvalidReceiver
+    )]
+    pub receiver_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == program_config.fee_vault @ EscrowError::InvalidConfig
+    )]
+    pub fee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: lending adapter program the escrow opted into
+    #[account(
+        executable,
+        address = escrow.lending_adapter @ EscrowError::LendingAdapterNotAllowed
+    )]
+    pub lending_adapter: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn withdraw_escrow_funds<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawEscrowFundsAsym<'info>>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+    require!(escrow.is_lent(), EscrowError::InvalidEscrowState);
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    let lent_amount = escrow.lent_amount;
+    let balance_before = ctx.accounts.escrow_token_account.amount;
+    guarded_external_call(&mut ctx.accounts.escrow, || {
+        invoke_lending_adapter(
+            &ctx.accounts.lending_adapter,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            ctx.remaining_accounts,
+            LENDING_WITHDRAW_INSTRUCTION,
+            lent_amount,
+            vault_signer,
+        )
+    })?;
+    
+    //the adapter may return less than the principal if its market took a loss
+    ctx.accounts.escrow_token_account.reload()?;
+    let amount_returned = ctx.accounts.escrow_token_account.amount
+        .checked_sub(balance_before)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    let loss = lent_amount.saturating_sub(amount_returned);
+    
+    //split the yield: protocol share first, then the parties' shares
+    let yield_amount = amount_returned.saturating_sub(lent_amount);
+    let (protocol_share, parties_share) =
+        calculate_fee_and_amount(yield_amount, ctx.accounts.escrow.fee_terms.yield_fee_bps)?;
+    let (payer_share, receiver_share) =
+        calculate_fee_and_amount(parties_share, ctx.accounts.escrow.yield_payer_bps)?;
+    
+    let yield_transfers = [
+        (ctx.accounts.fee_token_account.as_ref(), protocol_share),
+        (ctx.accounts.payer_token_account.as_ref(), payer_share),
+        (ctx.accounts.receiver_token_account.as_ref(), receiver_share),
+    ];
+    for (destination, share) in yield_transfers {
+        if share > 0 {
+            transfer_spl_tokens_signed(
+                &ctx.accounts.escrow_token_account,
+                destination.ok_or(EscrowError::InvalidToken)?,
+                &ctx.accounts.token_mint,
+                ctx.accounts.escrow_vault.to_account_info(),
+                share,
+                &ctx.accounts.token_program,
+                vault_signer,
+                &[],
+            )?;
+        }
+    }
+    
+    //the escrowed amount absorbs any shortfall, as unstaking absorbs pool fees
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.payer.amount_paid = escrow.payer.amount_paid
+        .checked_sub(loss)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    escrow.lent_amount = 0;
+    
+    emit_cpi!(EscrowFundsWithdrawnEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        lending_adapter: escrow.lending_adapter,
+        principal: lent_amount,
+        yield_amount,
+        loss,
+        protocol_share,
+        payer_share,
+        receiver_share,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//adapter instructions, named the way Anchor derives their discriminators
+const LENDING_DEPOSIT_INSTRUCTION: &str = "lending_deposit";
+const LENDING_WITHDRAW_INSTRUCTION: &str = "lending_withdraw";
+
+//helper function to invoke a lending adapter, signed by the escrow vault
+//
+//adapters take (vault authority, vault token account, mint, token program, ...protocol accounts) and an amount
+#[allow(clippy::too_many_arguments)]
+fn invoke_lending_adapter<'info>(
+    lending_adapter: &UncheckedAccount<'info>,
+    escrow_vault: &SystemAccount<'info>,
+    escrow_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    protocol_accounts: &[AccountInfo<'info>],
+    instruction_name: &str,
+    amount: u64,
+    vault_signer: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = hash(format!("global:{}", instruction_name).as_bytes()).to_bytes()[..8].to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    
+    let mut accounts = vec![
+        AccountMeta::new_readonly(escrow_vault.key(), true),
+        AccountMeta::new(escrow_token_account.key(), false),
+        AccountMeta::new_readonly(token_mint.key(), false),
+        AccountMeta::new_readonly(token_program.key(), false),
+    ];
+    let mut account_infos = vec![
+        escrow_vault.to_account_info(),
+        escrow_token_account.to_account_info(),
+        token_mint.to_account_info(),
+        token_program.to_account_info(),
+    ];
+    for account in protocol_accounts {
+        accounts.push(if account.is_writable {
+            AccountMeta::new(*account.key, account.is_signer)
+        } else {
+            AccountMeta::new_readonly(*account.key, account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+    
+    invoke_signed(
+        &Instruction {
+            program_id: lending_adapter.key(),
+            accounts,
+            data,
+        },
+        &account_infos,
+        vault_signer,
+    )?;
+    Ok(())
+}
+
+//events
+#[event]
+pub struct LendingOptInEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub lending_adapter: Pubkey,
+    pub yield_payer_bps: u16,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct EscrowFundsSuppliedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub lending_adapter: Pubkey,
+    pub amount: u64,
+    pub lent_amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct EscrowFundsWithdrawnEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub lending_adapter: Pubkey,
+    pub principal: u64,
+    pub yield_amount: u64,
+    pub loss: u64,
+    pub protocol_share: u64,
+    pub payer_share: u64,
+    pub receiver_share: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
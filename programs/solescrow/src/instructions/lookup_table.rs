@@ -0,0 +1,307 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Create the program's address lookup table of hot accounts, replacing any previous one (authority only)
+///
+/// The table is owned by the lookup table authority PDA and starts out with the program config, fee vault
+/// and event authority. Clients composing large settlement transactions (splits, swaps) load these accounts
+/// through the table to fit within the transaction size limit.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    /// CHECK: PDA owning the lookup table, signing its creation and extensions
+    #[account(
+        seeds = [seeds::LOOKUP_TABLE_AUTHORITY],
+        bump
+    )]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    /// CHECK: lookup table being created, checked against the address derived from the recent slot
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: address lookup table program
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+    let (lookup_table, lookup_table_bump) = lookup_table_address(recent_slot);
+    require_keys_eq!(ctx.accounts.lookup_table.key(), lookup_table, EscrowError::InvalidLookupTable);
+
+    let authority_seeds = &[seeds::LOOKUP_TABLE_AUTHORITY, &[ctx.bumps.lookup_table_authority]];
+    let lookup_table_authority = &ctx.accounts.lookup_table_authority;
+    let account_infos = [
+        ctx.accounts.lookup_table.to_account_info(),
+        lookup_table_authority.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    ];
+
+    //create the table, then seed it with the accounts every escrow instruction loads
+    let mut data = CREATE_LOOKUP_TABLE_VARIANT.to_le_bytes().to_vec();
+    data.extend_from_slice(&recent_slot.to_le_bytes());
+    data.push(lookup_table_bump);
+    invoke_signed(
+        &Instruction {
+            program_id: ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+            accounts: lookup_table_accounts(&lookup_table, lookup_table_authority.key, ctx.accounts.authority.key, false),
+            data,
+        },
+        &account_infos,
+        &[&authority_seeds[..]],
+    )?;
+
+    let addresses = vec![
+        ctx.accounts.program_config.key(),
+        ctx.accounts.program_config.fee_vault,
+        ctx.accounts.event_authority.key(),
+    ];
+    invoke_signed(
+        &Instruction {
+            program_id: ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+            accounts: lookup_table_accounts(&lookup_table, lookup_table_authority.key, ctx.accounts.authority.key, true),
+            data: extend_lookup_table_data(&addresses),
+        },
+        &account_infos,
+        &[&authority_seeds[..]],
+    )?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.lookup_table = lookup_table;
+
+    emit_cpi!(LookupTableExtendedEvent {
+        version: EVENT_VERSION,
+        lookup_table,
+        seq: program_config.next_event_seq(),
+        addresses,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::LookupTableUpdate,
+        &[lookup_table.as_ref(), &recent_slot.to_le_bytes()],
+    )?;
+
+    Ok(())
+}
+
+/// Add addresses to the program's lookup table (authority only)
+///
+/// Used for common mints, the fee vault's token accounts and partner platform accounts. Addresses already
+/// in the table are skipped.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExtendLookupTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    /// CHECK: PDA owning the lookup table
+    #[account(
+        seeds = [seeds::LOOKUP_TABLE_AUTHORITY],
+        bump
+    )]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    /// CHECK: the program's lookup table
+    #[account(
+        mut,
+        owner = ADDRESS_LOOKUP_TABLE_PROGRAM_ID @ EscrowError::InvalidLookupTable,
+        address = program_config.lookup_table @ EscrowError::InvalidLookupTable
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: address lookup table program
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+    require!(
+        !addresses.is_empty() && addresses.len() <= MAX_LOOKUP_TABLE_EXTENSION,
+        EscrowError::InvalidLookupTable
+    );
+
+    //only add what the table doesn't hold yet, within its capacity
+    let (new_addresses, table_len) = {
+        let table_data = ctx.accounts.lookup_table.try_borrow_data()?;
+        let table_len = lookup_table_addresses(&table_data)?.count();
+        (new_lookup_table_addresses(&table_data, &addresses)?, table_len)
+    };
+    require!(!new_addresses.is_empty(), EscrowError::InvalidLookupTable);
+    require!(
+        table_len + new_addresses.len() <= MAX_LOOKUP_TABLE_ADDRESSES,
+        EscrowError::InvalidLookupTable
+    );
+
+    let lookup_table = ctx.accounts.lookup_table.key();
+    let authority_seeds = &[seeds::LOOKUP_TABLE_AUTHORITY, &[ctx.bumps.lookup_table_authority]];
+    invoke_signed(
+        &Instruction {
+            program_id: ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+            accounts: lookup_table_accounts(
+                &lookup_table,
+                ctx.accounts.lookup_table_authority.key,
+                ctx.accounts.authority.key,
+                true,
+            ),
+            data: extend_lookup_table_data(&new_addresses),
+        },
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.lookup_table_authority.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&authority_seeds[..]],
+    )?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    emit_cpi!(LookupTableExtendedEvent {
+        version: EVENT_VERSION,
+        lookup_table,
+        seq: program_config.next_event_seq(),
+        addresses: new_addresses.clone(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    let params: Vec<&[u8]> = new_addresses.iter().map(|address| address.as_ref()).collect();
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::LookupTableUpdate,
+        &params,
+    )?;
+
+    Ok(())
+}
+
+/// Address (and bump) of the lookup table the program creates at a recent slot
+pub fn lookup_table_address(recent_slot: u64) -> (Pubkey, u8) {
+    let lookup_table_authority = Pubkey::find_program_address(&[seeds::LOOKUP_TABLE_AUTHORITY], &crate::ID).0;
+    Pubkey::find_program_address(
+        &[lookup_table_authority.as_ref(), &recent_slot.to_le_bytes()],
+        &ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+    )
+}
+
+/// Addresses of `addresses` not yet held by a lookup table, given the table's account data, without duplicates
+pub fn new_lookup_table_addresses(table_data: &[u8], addresses: &[Pubkey]) -> Result<Vec<Pubkey>> {
+    let mut new_addresses: Vec<Pubkey> = Vec::new();
+    for address in addresses {
+        let held = lookup_table_addresses(table_data)?.any(|held| held == *address);
+        if !held && !new_addresses.contains(address) {
+            new_addresses.push(*address);
+        }
+    }
+    Ok(new_addresses)
+}
+
+//the lookup table program's data: a fixed-size header, then the addresses packed back to back
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+//helper function to read the addresses held by a lookup table
+fn lookup_table_addresses(table_data: &[u8]) -> Result<impl Iterator<Item = Pubkey> + '_> {
+    let addresses = table_data
+        .get(LOOKUP_TABLE_META_SIZE..)
+        .ok_or(EscrowError::InvalidLookupTable)?;
+    require!(addresses.len() % 32 == 0, EscrowError::InvalidLookupTable);
+    Ok(addresses.chunks_exact(32).map(|address| {
+        let mut key = [0u8; 32];
+        key.copy_from_slice(address);
+        Pubkey::new_from_array(key)
+    }))
+}
+
+//lookup table program instructions are bincode-encoded: a u32 variant index, with u64 vector lengths
+const CREATE_LOOKUP_TABLE_VARIANT: u32 = 0;
+const EXTEND_LOOKUP_TABLE_VARIANT: u32 = 2;
+
+/// Build the instruction data extending a lookup table with addresses
+pub fn extend_lookup_table_data(addresses: &[Pubkey]) -> Vec<u8> {
+    let mut data = EXTEND_LOOKUP_TABLE_VARIANT.to_le_bytes().to_vec();
+    data.extend_from_slice(&(addresses.len() as u64).to_le_bytes());
+    for address in addresses {
+        data.extend_from_slice(address.as_ref());
+    }
+    data
+}
+
+//helper function to list the accounts of a create or extend call: (table, authority, payer, system program)
+fn lookup_table_accounts(
+    lookup_table: &Pubkey,
+    lookup_table_authority: &Pubkey,
+    payer: &Pubkey,
+    authority_signs: bool,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*lookup_table, false),
+        AccountMeta::new_readonly(*lookup_table_authority, authority_signs),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+    ]
+}
+
+//events
+#[event]
+pub struct LookupTableExtendedEvent {
+    pub version: u8,
+    pub lookup_table: Pubkey,
+    pub seq: u64,
+    pub addresses: Vec<Pubkey>,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+/// Open the mediation log for a dispute
+#[derive(Accounts)]
+pub struct InitializeMediationLog<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status != DisputeStatus::Resolved @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = MediationLog::space(),
+        seeds = [seeds::MEDIATION_LOG, dispute.key().as_ref()],
+        bump
+    )]
+    pub mediation_log: Account<'info, MediationLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_mediation_log(ctx: Context<InitializeMediationLog>) -> Result<()> {
+    //check authorization (payer, receiver or arbiter)
+    require!(
+        is_mediation_participant(&ctx.accounts.escrow, &ctx.accounts.dispute, &ctx.accounts.signer.key()),
+        EscrowError::Unauthorized
+    );
+
+    let mediation_log = &mut ctx.accounts.mediation_log;
+    mediation_log.dispute = ctx.accounts.dispute.key();
+    mediation_log.entries = Vec::new();
+    mediation_log.bump = ctx.bumps.mediation_log;
+
+    Ok(())
+}
+
+/// Append a note or settlement offer to a dispute's mediation log
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AppendMediationEntry<'info> {
+    pub author: Signer<'info>,
+
+    #[account(mut)]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        seeds = [seeds::DISPUTE, escrow.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.is_open() @ EscrowError::DisputeResolved,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [seeds::MEDIATION_LOG, dispute.key().as_ref()],
+        bump = mediation_log.bump
+    )]
+    pub mediation_log: Account<'info, MediationLog>,
+}
+
+pub fn append_mediation_entry(
+    ctx: Context<AppendMediationEntry>,
+    kind: MediationEntryKind,
+    content_hash: [u8; 32],
+    payer_amount: u64,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let author = ctx.accounts.author.key();
+
+    //check authorization (payer, receiver or arbiter)
+    require!(
+        is_mediation_participant(escrow, &ctx.accounts.dispute, &author),
+        EscrowError::Unauthorized
+    );
+
+    //settlement offers split what is left in escrow; notes carry no amount
+    let payer_amount = match kind {
+        MediationEntryKind::SettlementOffer => {
+            require!(
+                payer_amount <= escrow.get_amount_remaining(),
+                EscrowError::AmountExceeded
+            );
+            payer_amount
+        },
+        MediationEntryKind::Note => 0,
+    };
+
+    let mediation_log = &mut ctx.accounts.mediation_log;
+    require!(
+        mediation_log.entries.len() < MAX_MEDIATION_ENTRIES,
+        EscrowError::MediationLogFull
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    mediation_log.entries.push(MediationEntry {
+        author,
+        kind,
+        content_hash,
+        payer_amount,
+        timestamp: now,
+    });
+
+    emit_cpi!(MediationEntryAppendedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        dispute: ctx.accounts.dispute.key(),
+        author,
+        kind,
+        content_hash,
+        payer_amount,
+        index: (mediation_log.entries.len() - 1) as u8,
+        timestamp: now,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+//helper function to check whether an address takes part in a dispute's mediation
+fn is_mediation_participant(escrow: &AsymEscrow, dispute: &Dispute, addr: &Pubkey) -> bool {
+    *addr == escrow.payer.addr || *addr == escrow.receiver.addr || *addr == dispute.arbiter
+}
+
+//events
+#[event]
+pub struct MediationEntryAppendedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub dispute: Pubkey,
+    pub author: Pubkey,
+    pub kind: MediationEntryKind,
+    pub content_hash: [u8; 32],
+    pub payer_amount: u64,
+    pub index: u8,
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Turn SPL Memo emission on or off for an escrow and set the client reference it carries (either party)
+///
+/// Exchanges and accounting pipelines match the escrow's transfers by the memo, so the party whose books
+/// need it sets the reference.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMemoOptionsAsym<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+}
+
+pub fn set_memo_options(ctx: Context<SetMemoOptionsAsym>, memo_enabled: bool, client_ref: [u8; 32]) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+
+    //memos must be valid UTF-8
+    client_ref_str(&client_ref)?;
+
+    escrow.memo_enabled = memo_enabled;
+    escrow.client_ref = client_ref;
+
+    emit_cpi!(MemoOptionsSetEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        memo_enabled,
+        client_ref,
+        actor: signer,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Format an escrow memo: the escrow's log line for the action, followed by ` ref=<client ref>` if it has one
+pub fn format_escrow_memo(action: &str, escrow_id: &[u8; 32], client_ref: &[u8; 32], amount: u64) -> Result<String> {
+    let mut memo = format_escrow_log(action, escrow_id, &[("amount", amount)]);
+    let client_ref = client_ref_str(client_ref)?;
+    if !client_ref.is_empty() {
+        memo.push_str(" ref=");
+        memo.push_str(client_ref);
+    }
+    Ok(memo)
+}
+
+/// Write a memo for an escrow action through the SPL Memo program, if the escrow has memos turned on
+pub fn emit_escrow_memo<'info>(
+    escrow: &AsymEscrow,
+    memo_program: Option<&UncheckedAccount<'info>>,
+    action: &str,
+    amount: u64,
+) -> Result<()> {
+    if !escrow.memo_enabled {
+        return Ok(());
+    }
+    let memo_program = memo_program.ok_or(EscrowError::MemoProgramRequired)?;
+
+    invoke(
+        &Instruction {
+            program_id: memo_program.key(),
+            accounts: vec![],
+            data: format_escrow_memo(action, &escrow.id, &escrow.client_ref, amount)?.into_bytes(),
+        },
+        &[memo_program.to_account_info()],
+    )?;
+    Ok(())
+}
+
+//helper function to read a client reference as text, ignoring its zero padding
+fn client_ref_str(client_ref: &[u8; 32]) -> Result<&str> {
+    let len = client_ref.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    std::str::from_utf8(&client_ref[..len]).map_err(|_| error!(EscrowError::InvalidClientRef))
+}
+
+//events
+#[event]
+pub struct MemoOptionsSetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub memo_enabled: bool,
+    pub client_ref: [u8; 32],
+    pub actor: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
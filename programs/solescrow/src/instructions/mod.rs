@@ -1,7 +1,75 @@
 pub mod initialize;
 pub mod asym_escrow;
+pub mod sym_escrow;
+pub mod arbitration;
+pub mod arbiter;
+pub mod reputation;
+pub mod mediation;
+pub mod insurance;
+pub mod bundle;
+pub mod oracle;
+pub mod lending;
+pub mod swap;
+pub mod settlement_hook;
+pub mod staking;
+pub mod interest;
+pub mod wormhole;
+pub mod receipt;
+pub mod chaining;
+pub mod delegate;
+pub mod co_signers;
+pub mod relay;
+pub mod memo;
+pub mod recovery;
+pub mod allowlist;
+pub mod attestation;
+pub mod blocklist;
+pub mod guardian;
+pub mod platform;
+pub mod observers;
+pub mod stats;
+pub mod expiry;
+pub mod automation;
+pub mod lookup_table;
+pub mod crank;
+pub mod cleanup;
+pub mod views;
 pub mod utils;
 
 pub use initialize::*;
 pub use asym_escrow::*;
+pub use sym_escrow::*;
+pub use arbitration::*;
+pub use arbiter::*;
+pub use reputation::*;
+pub use mediation::*;
+pub use insurance::*;
+pub use bundle::*;
+pub use oracle::*;
+pub use lending::*;
+pub use swap::*;
+pub use settlement_hook::*;
+pub use staking::*;
+pub use interest::*;
+pub use wormhole::*;
+pub use receipt::*;
+pub use chaining::*;
+pub use delegate::*;
+pub use co_signers::*;
+pub use relay::*;
+pub use memo::*;
+pub use recovery::*;
+pub use allowlist::*;
+pub use attestation::*;
+pub use blocklist::*;
+pub use guardian::*;
+pub use platform::*;
+pub use observers::*;
+pub use stats::*;
+pub use expiry::*;
+pub use automation::*;
+pub use lookup_table::*;
+pub use crank::*;
+pub use cleanup::*;
+pub use views::*;
 pub use utils::*;
\ No newline at end of file
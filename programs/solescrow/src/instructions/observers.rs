@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Point-in-time view of an escrow, returned to observers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EscrowSnapshot {
+    pub escrow_id: [u8; 32],
+    pub status: EscrowStatus,
+    pub payer: Pubkey,
+    pub receiver: Pubkey,
+    pub currency: Pubkey,
+    pub amount: u64,
+    pub amount_paid: u64,
+    pub amount_released: u64,
+    pub amount_refunded: u64,
+    pub dispute_window_open: bool, // whether a requested release can still be disputed
+    pub timestamp: i64, // time of the snapshot
+}
+
+/// Add or remove an escrow's observer keys (both parties must sign)
+/// 
+/// Observers are compliance or audit keys that can snapshot the escrow and append audit notes. They are 
+/// never parties, delegates or co-signers, so no observer can move funds.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ManageObserversAsym<'info> {
+    pub payer: Signer<'info>,
+    
+    pub receiver: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.addr == payer.key() @ EscrowError::InvalidPayer,
+        constraint = escrow.receiver.addr == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn add_observer(ctx: Context<ManageObserversAsym>, key: Pubkey, name: [u8; 32], permissions: u8) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //validate the observer
+    require!(escrow.observers.len() < MAX_OBSERVERS, EscrowError::InvalidObserver);
+    require!(
+        permissions != 0 && permissions & !observer_permissions::ALL == 0,
+        EscrowError::InvalidObserver
+    );
+    require!(
+        key != Pubkey::default() && !escrow.is_observer(&key),
+        EscrowError::InvalidObserver
+    );
+    
+    //an observer can never act for either party
+    require!(
+        key != escrow.payer.addr
+            && key != escrow.receiver.addr
+            && key != escrow.payer.delegate
+            && key != escrow.receiver.delegate
+            && key != escrow.payer.session_key
+            && key != escrow.receiver.session_key
+            && escrow.co_signer_index(&key).is_none(),
+        EscrowError::InvalidObserver
+    );
+    
+    escrow.observers.push(Observer { key, name, permissions });
+    
+    emit_cpi!(ObserverAddedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        observer: key,
+        name,
+        permissions,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+pub fn remove_observer(ctx: Context<ManageObserversAsym>, key: Pubkey) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let index = escrow.observers
+        .iter()
+        .position(|o| o.key == key)
+        .ok_or(EscrowError::InvalidObserver)?;
+    escrow.observers.remove(index);
+    
+    emit_cpi!(ObserverRemovedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        observer: key,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Snapshot an escrow into the return data (observers with the snapshot permission)
+#[derive(Accounts)]
+pub struct SnapshotEscrowAsym<'info> {
+    pub observer: Signer<'info>,
+    
+    #[account(
+        constraint = escrow.can_observe(&observer.key(), observer_permissions::SNAPSHOT) @ EscrowError::InvalidObserver,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+}
+
+pub fn snapshot_escrow(ctx: Context<SnapshotEscrowAsym>) -> Result<EscrowSnapshot> {
+    let escrow = &ctx.accounts.escrow;
+    let now = Clock::get()?.unix_timestamp;
+    
+    Ok(EscrowSnapshot {
+        escrow_id: escrow.id,
+        status: escrow.status,
+        payer: escrow.payer.addr,
+        receiver: escrow.receiver.addr,
+        currency: escrow.payer.currency,
+        amount: escrow.payer.amount,
+        amount_paid: escrow.payer.amount_paid,
+        amount_released: escrow.payer.amount_released,
+        amount_refunded: escrow.payer.amount_refunded,
+        dispute_window_open: escrow.is_dispute_window_open(now),
+        timestamp: now,
+    })
+}
+
+/// Append an audit note to an escrow's event log (observers with the audit note permission)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AppendAuditNoteAsym<'info> {
+    pub observer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.can_observe(&observer.key(), observer_permissions::AUDIT_NOTE) @ EscrowError::InvalidObserver,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+}
+
+pub fn append_audit_note(ctx: Context<AppendAuditNoteAsym>, content_hash: [u8; 32]) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //notes are numbered so gaps in an indexed audit trail are detectable
+    let index = escrow.audit_note_count;
+    escrow.audit_note_count = index
+        .checked_add(1)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    emit_cpi!(AuditNoteEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        observer: ctx.accounts.observer.key(),
+        index,
+        content_hash,
+        timestamp: Clock::get()?.unix_timestamp,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct ObserverAddedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub observer: Pubkey,
+    pub name: [u8; 32],
+    pub permissions: u8,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct ObserverRemovedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub observer: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct AuditNoteEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub observer: Pubkey,
+    pub index: u32,
+    pub content_hash: [u8; 32],
+    pub timestamp: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+//layout of a Pyth price account
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+//layout of a Pyth receiver price update: discriminator, write authority, verification level, price message
+const PYTH_PRICE_UPDATE_ACCOUNT: &str = "PriceUpdateV2";
+const PYTH_VERIFICATION_FULL: u8 = 1;
+const PYTH_PRICE_MESSAGE_OFFSET: usize = 8 + 32 + 1;
+const PYTH_PRICE_UPDATE_MIN_LEN: usize = PYTH_PRICE_MESSAGE_OFFSET + 32 + 8 + 8 + 4 + 8;
+
+//layout of a Switchboard on-demand pull feed: discriminator, 32 oracle submissions and the feed's settings,
+//then its current result (value, std dev, ..., submission index at 97, slot at 104), with the timestamps of
+//the submissions closing the account
+const SWITCHBOARD_PULL_FEED_ACCOUNT: &str = "PullFeedAccountData";
+const SWITCHBOARD_RESULT_OFFSET: usize = 2264;
+const SWITCHBOARD_SUBMISSIONS: usize = 32;
+const SWITCHBOARD_TIMESTAMPS_OFFSET: usize = 2952;
+const SWITCHBOARD_PULL_FEED_MIN_LEN: usize = SWITCHBOARD_TIMESTAMPS_OFFSET + 8 * SWITCHBOARD_SUBMISSIONS;
+
+//Switchboard results carry 18 decimals, normalized to a 12-decimal exponent
+const SWITCHBOARD_PRECISION: u32 = 18;
+const SWITCHBOARD_PRICE_EXPO: i32 = -12;
+
+/// Read the price of an oracle feed, rejecting halted, stale or imprecise prices
+/// 
+/// `feed` is the feed recorded on the escrow (see `oracle_feed_id`), which the account must carry.
+pub fn load_oracle_price(source: OracleSource, price_feed: &AccountInfo, feed: &Pubkey, now: i64) -> Result<OraclePrice> {
+    let (feed_id, price) = match source {
+        OracleSource::Pyth => (price_feed.key(), load_pyth_price(price_feed)?),
+        OracleSource::Switchboard => (price_feed.key(), load_switchboard_price(price_feed)?),
+        OracleSource::PythPull => load_pyth_price_update(price_feed)?,
+    };
+    require_keys_eq!(feed_id, *feed, EscrowError::InvalidPriceFeed);
+    validate_oracle_price(&price, now)?;
+    Ok(price)
+}
+
+/// Feed an escrow records for an oracle account: the account itself for price feeds, or the feed id of a 
+/// Pyth pull oracle price update, whose accounts are posted anew for each transaction
+pub fn oracle_feed_id(source: OracleSource, price_feed: &AccountInfo) -> Result<Pubkey> {
+    match source {
+        OracleSource::PythPull => Ok(load_pyth_price_update(price_feed)?.0),
+        _ => {
+            validate_oracle_feed(source, price_feed)?;
+            Ok(price_feed.key())
+        },
+    }
+}
+
+/// Check the owner of an oracle feed matches its source
+pub fn validate_oracle_feed(source: OracleSource, price_feed: &AccountInfo) -> Result<()> {
+    let oracle_program = match source {
+        OracleSource::Pyth => PYTH_PROGRAM_ID,
+        OracleSource::Switchboard => SWITCHBOARD_PROGRAM_ID,
+        OracleSource::PythPull => PYTH_RECEIVER_PROGRAM_ID,
+    };
+    require_keys_eq!(*price_feed.owner, oracle_program, EscrowError::InvalidPriceFeed);
+    Ok(())
+}
+
+//helper function to read the aggregate price of a Pyth price account
+fn load_pyth_price(price_feed: &AccountInfo) -> Result<OraclePrice> {
+    validate_oracle_feed(OracleSource::Pyth, price_feed)?;
+    
+    let data = price_feed.try_borrow_data()?;
+    require!(data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN, EscrowError::InvalidPriceFeed);
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    
+    //header: magic, version, account type; aggregate price info starts at 208
+    require!(
+        read_u32(0) == PYTH_MAGIC && read_u32(8) == PYTH_PRICE_ACCOUNT_TYPE,
+        EscrowError::InvalidPriceFeed
+    );
+    require!(read_u32(224) == PYTH_STATUS_TRADING, EscrowError::InvalidPriceFeed);
+    
+    Ok(OraclePrice {
+        price: read_u64(208) as i64,
+        conf: read_u64(216),
+        expo: read_u32(20) as i32,
+        publish_time: read_u64(96) as i64,
+    })
+}
+
+//helper function to read the feed id and price of a fully verified Pyth pull oracle price update
+fn load_pyth_price_update(price_feed: &AccountInfo) -> Result<(Pubkey, OraclePrice)> {
+    validate_oracle_feed(OracleSource::PythPull, price_feed)?;
+    
+    let data = price_feed.try_borrow_data()?;
+    parse_pyth_price_update(&data)
+}
+
+/// Parse the feed id and price of a Pyth pull oracle price update account
+/// 
+/// Only updates the receiver verified against the full Wormhole guardian set are accepted.
+pub fn parse_pyth_price_update(data: &[u8]) -> Result<(Pubkey, OraclePrice)> {
+    require!(data.len() >= PYTH_PRICE_UPDATE_MIN_LEN, EscrowError::InvalidPriceFeed);
+    let discriminator = hash(format!("account:{}", PYTH_PRICE_UPDATE_ACCOUNT).as_bytes()).to_bytes();
+    require!(data[..8] == discriminator[..8], EscrowError::InvalidPriceFeed);
+    require!(data[40] == PYTH_VERIFICATION_FULL, EscrowError::InvalidPriceFeed);
+    
+    //price message: feed id, price, conf, exponent, publish time
+    let read = |offset: usize, len: usize| &data[PYTH_PRICE_MESSAGE_OFFSET + offset..PYTH_PRICE_MESSAGE_OFFSET + offset + len];
+    let mut feed_id = [0u8; 32];
+    feed_id.copy_from_slice(read(0, 32));
+    let mut word = [0u8; 8];
+    let mut read_u64 = |offset: usize| {
+        word.copy_from_slice(read(offset, 8));
+        u64::from_le_bytes(word)
+    };
+    let price = read_u64(32) as i64;
+    let conf = read_u64(40);
+    let publish_time = read_u64(52) as i64;
+    let mut expo = [0u8; 4];
+    expo.copy_from_slice(read(48, 4));
+    
+    Ok((
+        Pubkey::new_from_array(feed_id),
+        OraclePrice {
+            price,
+            conf,
+            expo: i32::from_le_bytes(expo),
+            publish_time,
+        },
+    ))
+}
+
+//helper function to read the current result of a Switchboard on-demand pull feed
+fn load_switchboard_price(price_feed: &AccountInfo) -> Result<OraclePrice> {
+    validate_oracle_feed(OracleSource::Switchboard, price_feed)?;
+    
+    let data = price_feed.try_borrow_data()?;
+    parse_switchboard_pull_feed(&data)
+}
+
+/// Parse the current result of a Switchboard on-demand pull feed account
+/// 
+/// The result's standard deviation stands in for the confidence interval, and the timestamp of the 
+/// submission it was taken from for the publish time.
+pub fn parse_switchboard_pull_feed(data: &[u8]) -> Result<OraclePrice> {
+    require!(data.len() >= SWITCHBOARD_PULL_FEED_MIN_LEN, EscrowError::InvalidPriceFeed);
+    let discriminator = hash(format!("account:{}", SWITCHBOARD_PULL_FEED_ACCOUNT).as_bytes()).to_bytes();
+    require!(data[..8] == discriminator[..8], EscrowError::InvalidPriceFeed);
+    
+    let read = |offset: usize, len: usize| &data[SWITCHBOARD_RESULT_OFFSET + offset..SWITCHBOARD_RESULT_OFFSET + offset + len];
+    let read_i128 = |offset: usize| i128::from_le_bytes(read(offset, 16).try_into().unwrap());
+    let slot = u64::from_le_bytes(read(104, 8).try_into().unwrap());
+    require!(slot > 0, EscrowError::InvalidPriceFeed);
+    let submission = read(97, 1)[0] as usize;
+    require!(submission < SWITCHBOARD_SUBMISSIONS, EscrowError::InvalidPriceFeed);
+    let timestamp = SWITCHBOARD_TIMESTAMPS_OFFSET + 8 * submission;
+    
+    let scale = 10i128.pow(SWITCHBOARD_PRECISION - SWITCHBOARD_PRICE_EXPO.unsigned_abs());
+    Ok(OraclePrice {
+        price: i64::try_from(read_i128(0) / scale).map_err(|_| error!(EscrowError::InvalidPriceFeed))?,
+        conf: u64::try_from(read_i128(16) / scale).map_err(|_| error!(EscrowError::InvalidPriceFeed))?,
+        expo: SWITCHBOARD_PRICE_EXPO,
+        publish_time: i64::from_le_bytes(data[timestamp..timestamp + 8].try_into().unwrap()),
+    })
+}
+
+/// Check an oracle price is positive, fresh and has a narrow enough confidence interval
+pub fn validate_oracle_price(price: &OraclePrice, now: i64) -> Result<()> {
+    require!(price.price > 0, EscrowError::InvalidPriceFeed);
+    require!(
+        now.saturating_sub(price.publish_time) <= MAX_PRICE_AGE,
+        EscrowError::StalePrice
+    );
+    require!(
+        (price.conf as u128) * (BPS_DENOMINATOR as u128)
+            <= (price.price as u128) * (MAX_PRICE_CONFIDENCE_BPS as u128),
+        EscrowError::PriceConfidenceTooWide
+    );
+    Ok(())
+}
+
+/// Express an oracle price as the USD value (USD_DECIMALS) of one whole token
+pub fn oracle_price_to_usd(price: &OraclePrice) -> Result<u64> {
+    let scale = price.expo + USD_DECIMALS as i32;
+    let pow10 = 10u128.checked_pow(scale.unsigned_abs()).ok_or(EscrowError::ArithmeticOverflow)?;
+    let usd_price = if scale >= 0 {
+        (price.price as u128).checked_mul(pow10).ok_or(EscrowError::ArithmeticOverflow)?
+    } else {
+        price.price as u128 / pow10
+    };
+    u64::try_from(usd_price).map_err(|_| error!(EscrowError::ArithmeticOverflow))
+}
+
+/// Convert a USD amount (USD_DECIMALS) into base units of a currency with the given decimals, rounding up
+pub fn usd_to_currency_amount(usd_amount: u64, price: &OraclePrice, decimals: u8) -> Result<u64> {
+    //amount = usd * 10^decimals / (price * 10^expo * 10^USD_DECIMALS)
+    let scale = decimals as i32 - price.expo - USD_DECIMALS as i32;
+    let pow10 = |exp: i32| 10u128.checked_pow(exp.unsigned_abs()).ok_or(EscrowError::ArithmeticOverflow);
+    let (numerator, denominator) = if scale >= 0 {
+        (
+            (usd_amount as u128).checked_mul(pow10(scale)?).ok_or(EscrowError::ArithmeticOverflow)?,
+            price.price as u128,
+        )
+    } else {
+        (
+            usd_amount as u128,
+            (price.price as u128).checked_mul(pow10(scale)?).ok_or(EscrowError::ArithmeticOverflow)?,
+        )
+    };
+    require!(denominator > 0, EscrowError::InvalidPriceFeed);
+    
+    let amount = numerator.div_ceil(denominator);
+    u64::try_from(amount).map_err(|_| error!(EscrowError::ArithmeticOverflow))
+}
\ No newline at end of file
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Register a custodial platform (authority only)
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(platform_authority: Pubkey)]
+pub struct RegisterPlatform<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Platform::space(),
+        seeds = [seeds::PLATFORM, platform_authority.as_ref()],
+        bump
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_platform(ctx: Context<RegisterPlatform>, platform_authority: Pubkey, fee_share_bps: u16) -> Result<()> {
+    require!(platform_authority != Pubkey::default(), EscrowError::InvalidPlatform);
+    require!(fee_share_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidPlatform);
+
+    let platform = &mut ctx.accounts.platform;
+    platform.authority = platform_authority;
+    platform.fee_share_bps = fee_share_bps;
+    platform.registered_at = Clock::get()?.unix_timestamp;
+    platform.bump = ctx.bumps.platform;
+    platform.program = Pubkey::default();
+
+    emit_cpi!(PlatformRegisteredEvent {
+        version: EVENT_VERSION,
+        platform: platform_authority,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        fee_share_bps,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        &mut ctx.accounts.program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::RegisterPlatform,
+        &[platform_authority.as_ref(), &fee_share_bps.to_le_bytes()],
+    )?;
+
+    Ok(())
+}
+
+/// Register a partner marketplace program as a platform (authority only)
+/// 
+/// The platform is keyed by the program's partner authority PDA, which the program signs 
+/// `create_escrow_via_partner` with when it creates escrows through CPI. It is deregistered like any platform.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(partner_program_id: Pubkey)]
+pub struct RegisterPartnerProgram<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Platform::space(),
+        seeds = [seeds::PLATFORM, Platform::partner_authority(&partner_program_id).as_ref()],
+        bump
+    )]
+    pub platform: Account<'info, Platform>,
+
+    /// CHECK: partner marketplace program
+    #[account(
+        executable,
+        address = partner_program_id @ EscrowError::InvalidPlatform
+    )]
+    pub partner_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_partner_program(ctx: Context<RegisterPartnerProgram>, partner_program_id: Pubkey, fee_share_bps: u16) -> Result<()> {
+    require!(fee_share_bps as u64 <= BPS_DENOMINATOR, EscrowError::InvalidPlatform);
+
+    let partner_authority = Platform::partner_authority(&partner_program_id);
+    let platform = &mut ctx.accounts.platform;
+    platform.authority = partner_authority;
+    platform.fee_share_bps = fee_share_bps;
+    platform.registered_at = Clock::get()?.unix_timestamp;
+    platform.bump = ctx.bumps.platform;
+    platform.program = partner_program_id;
+
+    emit_cpi!(PlatformRegisteredEvent {
+        version: EVENT_VERSION,
+        platform: partner_authority,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        fee_share_bps,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        &mut ctx.accounts.program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::RegisterPlatform,
+        &[partner_authority.as_ref(), &fee_share_bps.to_le_bytes(), partner_program_id.as_ref()],
+    )?;
+
+    Ok(())
+}
+
+/// Deregister a custodial platform (authority only)
+/// 
+/// Escrows the platform already created keep their attribution.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeregisterPlatform<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminAuditEntry::space(),
+        seeds = [seeds::ADMIN_AUDIT, &program_config.admin_action_count.to_le_bytes()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AdminAuditEntry>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [seeds::PLATFORM, platform.authority.as_ref()],
+        bump = platform.bump
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deregister_platform(ctx: Context<DeregisterPlatform>) -> Result<()> {
+    emit_cpi!(PlatformDeregisteredEvent {
+        version: EVENT_VERSION,
+        platform: ctx.accounts.platform.authority,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    record_admin_action(
+        &mut ctx.accounts.audit_entry,
+        ctx.bumps.audit_entry,
+        &mut ctx.accounts.program_config,
+        ctx.accounts.authority.key(),
+        AdminActionKind::DeregisterPlatform,
+        &[ctx.accounts.platform.authority.as_ref()],
+    )?;
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct PlatformRegisteredEvent {
+    pub version: u8,
+    pub platform: Pubkey,
+    pub seq: u64,
+    pub fee_share_bps: u16,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct PlatformDeregisteredEvent {
+    pub version: u8,
+    pub platform: Pubkey,
+    pub seq: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,459 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::associated_token::{self, AssociatedToken, get_associated_token_address_with_program_id};
+use anchor_spl::token_2022::{self, spl_token_2022, Burn, InitializeMint2, MintTo, SetAuthority, Token2022};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_2022_extensions::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, permanent_delegate_initialize,
+    token_metadata_initialize, token_metadata_update_field,
+    MetadataPointerInitialize, NonTransferableMintInitialize, PermanentDelegateInitialize,
+    TokenMetadataInitialize, TokenMetadataUpdateField,
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+//receipt token metadata
+const RECEIPT_NAME: &str = "Escrow Receipt";
+const RECEIPT_SYMBOL: &str = "ESCROW";
+const RECEIPT_ESCROW_FIELD: &str = "escrow";
+
+/// Mint a receipt NFT representing the receiver's claim on the escrow (creator only)
+/// 
+/// The receipt is a Token-2022 NFT whose metadata names the escrow account. The escrow vault stays its 
+/// permanent delegate so the receipt can be burned at settlement wherever it is held. A transferable 
+/// receipt is a claim token: it can be sold, and the receiver's payout goes to its current holder.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintEscrowReceiptAsym<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.creator == creator.key() @ EscrowError::Unauthorized,
+        constraint = escrow.status == EscrowStatus::Pending @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, authority of the receipt mint
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: receipt mint, created and initialized by this instruction
+    #[account(
+        mut,
+        seeds = [seeds::RECEIPT_MINT, escrow.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: receiver, owner of the receipt token account; may be a program-owned multisig vault
+    #[account(
+        address = escrow.receiver.addr @ EscrowError::InvalidReceiver
+    )]
+    pub receiver: UncheckedAccount<'info>,
+    
+    /// CHECK: receiver's associated token account for the receipt, created by this instruction
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &receiver.key(),
+            &receipt_mint.key(),
+            &token_program.key(),
+        ) @ EscrowError::InvalidToken
+    )]
+    pub receiver_receipt_account: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mint_escrow_receipt(ctx: Context<MintEscrowReceiptAsym>, uri: String, transferable: bool) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let emitter = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    mint_receipt(
+        &mut ctx.accounts.escrow,
+        ReceiptMintAccounts {
+            creator: &ctx.accounts.creator,
+            escrow_vault: &ctx.accounts.escrow_vault,
+            vault_bump: ctx.bumps.escrow_vault,
+            receipt_mint: &ctx.accounts.receipt_mint,
+            mint_bump: ctx.bumps.receipt_mint,
+            receiver: &ctx.accounts.receiver,
+            receiver_receipt_account: &ctx.accounts.receiver_receipt_account,
+            token_program: &ctx.accounts.token_program,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        uri,
+        transferable,
+        &emitter,
+    )
+}
+
+/// Accounts an escrow's receipt is minted with
+pub struct ReceiptMintAccounts<'a, 'info> {
+    pub creator: &'a Signer<'info>,
+    pub escrow_vault: &'a SystemAccount<'info>,
+    pub vault_bump: u8,
+    pub receipt_mint: &'a UncheckedAccount<'info>,
+    pub mint_bump: u8,
+    pub receiver: &'a UncheckedAccount<'info>,
+    pub receiver_receipt_account: &'a UncheckedAccount<'info>,
+    pub token_program: &'a Program<'info, Token2022>,
+    pub associated_token_program: &'a Program<'info, AssociatedToken>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+/// Create an escrow's receipt mint and mint its single token to the receiver
+pub fn mint_receipt<'info>(
+    escrow: &mut Account<'info, AsymEscrow>,
+    accounts: ReceiptMintAccounts<'_, 'info>,
+    uri: String,
+    transferable: bool,
+    emitter: &EventEmitter,
+) -> Result<()> {
+    require!(!escrow.has_receipt(), EscrowError::ReceiptAlreadyMinted);
+    //a chained escrow's payout already belongs to the downstream escrow
+    require!(!(transferable && escrow.is_chained()), EscrowError::InvalidEscrowChain);
+    //a transferable claim would pay out to an unattested holder
+    require!(!(transferable && escrow.requires_attestation()), EscrowError::InvalidAttestation);
+    
+    let escrow_key = escrow.key();
+    let vault_key = accounts.escrow_vault.key();
+    let mint_key = accounts.receipt_mint.key();
+    let token_program = accounts.token_program.to_account_info();
+    let mint = accounts.receipt_mint.to_account_info();
+    let vault = accounts.escrow_vault.to_account_info();
+    
+    //generate signer seeds for escrow vault and receipt mint
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[accounts.vault_bump],
+    ];
+    let mint_seeds = &[
+        seeds::RECEIPT_MINT,
+        escrow_key.as_ref(),
+        &[accounts.mint_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    //size the mint for its extensions, funding the metadata written after initialization
+    let mut extensions = vec![ExtensionType::MetadataPointer, ExtensionType::PermanentDelegate];
+    if !transferable {
+        extensions.push(ExtensionType::NonTransferable);
+    }
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+    let metadata = TokenMetadata {
+        name: RECEIPT_NAME.to_string(),
+        symbol: RECEIPT_SYMBOL.to_string(),
+        uri: uri.clone(),
+        additional_metadata: vec![(RECEIPT_ESCROW_FIELD.to_string(), escrow_key.to_string())],
+        ..Default::default()
+    };
+    let metadata_space = metadata.tlv_size_of()?;
+    let lamports = Rent::get()?.minimum_balance(space + metadata_space);
+    
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: accounts.creator.to_account_info(),
+                to: mint.clone(),
+            },
+            &[&mint_seeds[..]],
+        ),
+        lamports,
+        space as u64,
+        &token_program.key(),
+    )?;
+    
+    //extensions must be initialized before the mint itself
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize { token_program_id: token_program.clone(), mint: mint.clone() },
+        ),
+        Some(vault_key),
+        Some(mint_key),
+    )?;
+    permanent_delegate_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            PermanentDelegateInitialize { token_program_id: token_program.clone(), mint: mint.clone() },
+        ),
+        &vault_key,
+    )?;
+    if !transferable {
+        non_transferable_mint_initialize(CpiContext::new(
+            token_program.clone(),
+            NonTransferableMintInitialize { token_program_id: token_program.clone(), mint: mint.clone() },
+        ))?;
+    }
+    token_2022::initialize_mint2(
+        CpiContext::new(token_program.clone(), InitializeMint2 { mint: mint.clone() }),
+        0,
+        &vault_key,
+        None,
+    )?;
+    
+    //write metadata pointing at the escrow
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: vault.clone(),
+                mint_authority: vault.clone(),
+                mint: mint.clone(),
+            },
+            vault_signer,
+        ),
+        RECEIPT_NAME.to_string(),
+        RECEIPT_SYMBOL.to_string(),
+        uri,
+    )?;
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataUpdateField {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: vault.clone(),
+            },
+            vault_signer,
+        ),
+        Field::Key(RECEIPT_ESCROW_FIELD.to_string()),
+        escrow_key.to_string(),
+    )?;
+    
+    //mint the single receipt to the receiver, then fix the supply
+    associated_token::create(CpiContext::new(
+        accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: accounts.creator.to_account_info(),
+            associated_token: accounts.receiver_receipt_account.to_account_info(),
+            authority: accounts.receiver.to_account_info(),
+            mint: mint.clone(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: mint.clone(),
+                to: accounts.receiver_receipt_account.to_account_info(),
+                authority: vault.clone(),
+            },
+            vault_signer,
+        ),
+        1,
+    )?;
+    token_2022::set_authority(
+        CpiContext::new_with_signer(
+            token_program,
+            SetAuthority { current_authority: vault, account_or_mint: mint },
+            vault_signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+    
+    escrow.receipt_mint = mint_key;
+    escrow.claim_transferable = transferable;
+    
+    emitter.emit(EscrowReceiptMintedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        receipt_mint: mint_key,
+        receiver: escrow.receiver.addr,
+        transferable,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })
+}
+
+/// Burn an escrow's receipt NFT once the escrow is settled (callable by anyone)
+/// 
+/// Settlements burn the receipt as they complete the escrow; this burns a receipt that outlived its escrow, 
+/// such as one completed before settlements did.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BurnEscrowReceiptAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    /// Escrow vault, permanent delegate of the receipt
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: receipt mint recorded on the escrow
+    #[account(
+        mut,
+        address = escrow.receipt_mint @ EscrowError::InvalidToken
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+    
+    /// Token account currently holding the receipt
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::token_program = token_program,
+    )]
+    pub receipt_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn burn_escrow_receipt(ctx: Context<BurnEscrowReceiptAsym>) -> Result<()> {
+    require!(ctx.accounts.escrow.has_receipt(), EscrowError::InvalidToken);
+    require!(ctx.accounts.receipt_token_account.amount > 0, EscrowError::InvalidAmount);
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = ctx.accounts.escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    
+    burn_receipt(
+        &mut ctx.accounts.escrow,
+        ctx.accounts.receipt_mint.to_account_info(),
+        &ctx.accounts.receipt_token_account,
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.escrow_vault.to_account_info(),
+        &[&vault_seeds[..]],
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
+}
+
+/// Receipt accounts a settlement burns the escrow's receipt with, required once an escrow with a receipt 
+/// completes
+pub struct SettlementReceiptAccounts<'a, 'info> {
+    pub receipt_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    pub receipt_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<&'a Program<'info, Token2022>>,
+    pub escrow_vault: AccountInfo<'info>,
+}
+
+/// Burn the receipt of an escrow a settlement has just completed, signed by the vault as permanent delegate
+/// 
+/// A transferable receipt is a claim on the escrow, so it must not outlive the payout. A receipt its holder 
+/// has already burned is skipped; otherwise the token account holding it must be passed.
+#[inline(never)]
+pub fn burn_settled_receipt<'info>(
+    escrow: &mut Account<'info, AsymEscrow>,
+    accounts: SettlementReceiptAccounts<'_, 'info>,
+    vault_signer: &[&[&[u8]]],
+    emitter: &EventEmitter,
+) -> Result<()> {
+    if !escrow.has_receipt() {
+        return Ok(());
+    }
+    
+    let receipt_mint = accounts.receipt_mint.ok_or(EscrowError::InvalidToken)?;
+    if receipt_mint.supply == 0 {
+        return Ok(());
+    }
+    let receipt_token_account = accounts.receipt_token_account.ok_or(EscrowError::InvalidToken)?;
+    let token_program = accounts.token_program.ok_or(EscrowError::InvalidToken)?;
+    require!(receipt_token_account.amount == receipt_mint.supply, EscrowError::InvalidAmount);
+    
+    burn_receipt(
+        escrow,
+        receipt_mint.to_account_info(),
+        receipt_token_account,
+        token_program.to_account_info(),
+        accounts.escrow_vault,
+        vault_signer,
+        emitter,
+    )
+}
+
+//helper function to burn the receipt held in a token account, signed by the vault as permanent delegate
+fn burn_receipt<'info>(
+    escrow: &mut Account<'info, AsymEscrow>,
+    receipt_mint: AccountInfo<'info>,
+    receipt_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    escrow_vault: AccountInfo<'info>,
+    vault_signer: &[&[&[u8]]],
+    emitter: &EventEmitter,
+) -> Result<()> {
+    token_2022::burn(
+        CpiContext::new_with_signer(
+            token_program,
+            Burn {
+                mint: receipt_mint,
+                from: receipt_token_account.to_account_info(),
+                authority: escrow_vault,
+            },
+            vault_signer,
+        ),
+        receipt_token_account.amount,
+    )?;
+    
+    emitter.emit(EscrowReceiptBurnedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        receipt_mint: escrow.receipt_mint,
+        holder: receipt_token_account.owner,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })
+}
+
+//events
+#[event]
+pub struct EscrowReceiptMintedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub receipt_mint: Pubkey,
+    pub receiver: Pubkey,
+    pub transferable: bool,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct EscrowReceiptBurnedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub receipt_mint: Pubkey,
+    pub holder: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Manage social recovery of a party's key (payer or receiver)
+/// 
+/// A party registers recovery addresses and a delay. If its key is lost, a recovery address proposes a 
+/// new key; once the delay has passed without the current key vetoing, anyone can execute the rotation, 
+/// replacing the party's address on the escrow and dropping the old key's delegate and session key.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ManageRecoveryAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Register a party's recovery addresses and delay; an empty list removes recovery
+pub fn set_recovery(ctx: Context<ManageRecoveryAsym>, recovery_addresses: Vec<Pubkey>, delay: i64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    let escrow_id = escrow.id;
+    let escrow_key = escrow.key();
+    
+    //only the party's own key can set its recovery addresses
+    let is_payer = signer == escrow.payer.addr;
+    require!(is_payer || signer == escrow.receiver.addr, EscrowError::Unauthorized);
+    
+    //validate the delay
+    require!(
+        recovery_addresses.len() <= MAX_RECOVERY_ADDRESSES,
+        EscrowError::InvalidRecovery
+    );
+    require!(
+        recovery_addresses.is_empty() || (MIN_RECOVERY_DELAY..=MAX_RECOVERY_DELAY).contains(&delay),
+        EscrowError::InvalidRecovery
+    );
+    
+    //recovery addresses must be distinct, and never able to act for the other side
+    let (counterparty, counterparty_recovery) = if is_payer {
+        (&escrow.receiver, &escrow.receiver_recovery)
+    } else {
+        (&escrow.payer, &escrow.payer_recovery)
+    };
+    for (i, key) in recovery_addresses.iter().enumerate() {
+        require!(
+            *key != Pubkey::default()
+                && *key != signer
+                && !recovery_addresses[..i].contains(key)
+                && *key != counterparty.addr
+                && *key != counterparty.delegate
+                && *key != counterparty.session_key
+                && !counterparty_recovery.recovery_addresses.contains(key),
+            EscrowError::InvalidRecovery
+        );
+    }
+    
+    //replacing the settings cancels any pending rotation
+    let recovery = if is_payer { &mut escrow.payer_recovery } else { &mut escrow.receiver_recovery };
+    *recovery = PartyRecovery {
+        recovery_addresses: recovery_addresses.clone(),
+        delay: if recovery_addresses.is_empty() { 0 } else { delay },
+        pending_addr: Pubkey::default(),
+        executable_at: 0,
+    };
+    
+    emit_cpi!(RecoverySetEvent {
+        version: EVENT_VERSION,
+        escrow_id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
+        party: signer,
+        recovery_addresses,
+        delay,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Propose rotating a party's key to a new address (one of the party's recovery addresses)
+pub fn propose_recovery(ctx: Context<ManageRecoveryAsym>, new_addr: Pubkey) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    let escrow_id = escrow.id;
+    let escrow_key = escrow.key();
+    
+    //keys can't change under an arbiter
+    require!(escrow.status != EscrowStatus::Arbitration, EscrowError::InvalidEscrowState);
+    
+    //find the party the signer is a recovery address for
+    let is_payer = escrow.payer_recovery.recovery_addresses.contains(&signer);
+    require!(
+        is_payer || escrow.receiver_recovery.recovery_addresses.contains(&signer),
+        EscrowError::Unauthorized
+    );
+    
+    //the new key must not already act for the other side
+    let (party, counterparty) = if is_payer {
+        (&escrow.payer, &escrow.receiver)
+    } else {
+        (&escrow.receiver, &escrow.payer)
+    };
+    let party_addr = party.addr;
+    require!(
+        new_addr != Pubkey::default()
+            && new_addr != party_addr
+            && new_addr != counterparty.addr
+            && new_addr != counterparty.delegate
+            && new_addr != counterparty.session_key,
+        EscrowError::InvalidRecovery
+    );
+    
+    //one rotation at a time; the current key vetoes a proposal it doesn't want
+    let recovery = if is_payer { &mut escrow.payer_recovery } else { &mut escrow.receiver_recovery };
+    require!(!recovery.is_pending(), EscrowError::InvalidRecovery);
+    
+    recovery.pending_addr = new_addr;
+    recovery.executable_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(recovery.delay)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    let executable_at = recovery.executable_at;
+    
+    emit_cpi!(RecoveryProposedEvent {
+        version: EVENT_VERSION,
+        escrow_id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
+        party: party_addr,
+        proposed_by: signer,
+        new_addr,
+        executable_at,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Veto a pending rotation of a party's key (the party's current key)
+pub fn veto_recovery(ctx: Context<ManageRecoveryAsym>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    let escrow_id = escrow.id;
+    let escrow_key = escrow.key();
+    
+    let recovery = if signer == escrow.payer.addr {
+        &mut escrow.payer_recovery
+    } else if signer == escrow.receiver.addr {
+        &mut escrow.receiver_recovery
+    } else {
+        return err!(EscrowError::Unauthorized);
+    };
+    require!(recovery.is_pending(), EscrowError::InvalidRecovery);
+    
+    let vetoed_addr = recovery.pending_addr;
+    recovery.pending_addr = Pubkey::default();
+    recovery.executable_at = 0;
+    
+    emit_cpi!(RecoveryVetoedEvent {
+        version: EVENT_VERSION,
+        escrow_id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
+        party: signer,
+        vetoed_addr,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Execute a pending rotation of a party's key once its delay has passed (anyone)
+pub fn execute_recovery(ctx: Context<ManageRecoveryAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow_key = ctx.accounts.escrow.key();
+    let escrow: &mut AsymEscrow = &mut ctx.accounts.escrow;
+    let now = Clock::get()?.unix_timestamp;
+    require!(escrow.status != EscrowStatus::Arbitration, EscrowError::InvalidEscrowState);
+    
+    //rotate whichever party has a matured proposal
+    let is_payer = escrow.payer_recovery.is_pending() && now >= escrow.payer_recovery.executable_at;
+    let is_receiver = escrow.receiver_recovery.is_pending() && now >= escrow.receiver_recovery.executable_at;
+    require!(is_payer || is_receiver, EscrowError::RecoveryDelayNotElapsed);
+    
+    let (party, recovery) = if is_payer {
+        (&mut escrow.payer, &mut escrow.payer_recovery)
+    } else {
+        (&mut escrow.receiver, &mut escrow.receiver_recovery)
+    };
+    let old_addr = party.addr;
+    let new_addr = recovery.pending_addr;
+    
+    //the old key's grants go with it
+    party.addr = new_addr;
+    party.delegate = Pubkey::default();
+    party.delegate_limit = 0;
+    party.session_key = Pubkey::default();
+    party.session_permissions = 0;
+    party.session_expiry_slot = 0;
+    recovery.pending_addr = Pubkey::default();
+    recovery.executable_at = 0;
+    
+    if escrow.co_signed_party == old_addr {
+        escrow.co_signed_party = new_addr;
+    }
+    
+    emit_cpi!(PartyKeyRotatedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow_key,
+        old_addr,
+        new_addr,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct RecoverySetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub recovery_addresses: Vec<Pubkey>,
+    pub delay: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct RecoveryProposedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub proposed_by: Pubkey,
+    pub new_addr: Pubkey,
+    pub executable_at: i64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct RecoveryVetoedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub vetoed_addr: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct PartyKeyRotatedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub old_addr: Pubkey,
+    pub new_addr: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::EventEmitter;
+
+//Ed25519 program instruction data: signature count, padding, then one 14-byte offsets entry per signature
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+//instruction index meaning "this instruction" in an Ed25519 offsets entry
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Build the message a party signs off-chain to authorize a relayed action on an escrow
+/// 
+/// Layout: escrow address (32) | action (1) | amount (8, LE) | party's relay nonce (8, LE)
+pub fn relay_intent_message(escrow: &Pubkey, action: u8, amount: u64, nonce: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(49);
+    message.extend_from_slice(escrow.as_ref());
+    message.push(action);
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// Verify an intent a party signed off-chain and consume its relay nonce, returning the party's address
+/// 
+/// The signature itself is checked by an Ed25519 program instruction placed immediately before this one, 
+/// which must verify exactly one signature with its key, signature and message all inline. This checks 
+/// that the signing key is the payer or receiver and that the message is the intent expected for the 
+/// action, amount and party's current nonce, so a signed intent can be submitted only once.
+pub fn verify_relayed_intent(
+    sysvar_instructions: Option<&UncheckedAccount>,
+    escrow_key: &Pubkey,
+    escrow: &mut AsymEscrow,
+    action: u8,
+    amount: u64,
+    emitter: &EventEmitter,
+) -> Result<Pubkey> {
+    let sysvar_instructions = sysvar_instructions.ok_or(EscrowError::InvalidRelayedIntent)?;
+    
+    //load the Ed25519 verification preceding this instruction
+    let current_index = load_current_index_checked(&sysvar_instructions.to_account_info())?;
+    require!(current_index > 0, EscrowError::InvalidRelayedIntent);
+    let verification = load_instruction_at_checked(
+        (current_index - 1) as usize,
+        &sysvar_instructions.to_account_info(),
+    )?;
+    require!(
+        verification.program_id == ed25519_program::ID,
+        EscrowError::InvalidRelayedIntent
+    );
+    
+    //parse the single offsets entry; everything must be inline in the verification instruction
+    let data = &verification.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        EscrowError::InvalidRelayedIntent
+    );
+    let offset = |i: usize| {
+        let at = ED25519_OFFSETS_START + i * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    let (signature_index, pubkey_offset, pubkey_index) = (offset(1), offset(2) as usize, offset(3));
+    let (message_offset, message_size, message_index) = (offset(4) as usize, offset(5) as usize, offset(6));
+    require!(
+        signature_index == ED25519_CURRENT_INSTRUCTION
+            && pubkey_index == ED25519_CURRENT_INSTRUCTION
+            && message_index == ED25519_CURRENT_INSTRUCTION,
+        EscrowError::InvalidRelayedIntent
+    );
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + 32)
+        .ok_or(EscrowError::InvalidRelayedIntent)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(EscrowError::InvalidRelayedIntent)?;
+    
+    //the signing key must be one of the parties' own keys
+    let signer = Pubkey::try_from(pubkey).map_err(|_| error!(EscrowError::InvalidRelayedIntent))?;
+    let party = if signer == escrow.payer.addr {
+        &mut escrow.payer
+    } else if signer == escrow.receiver.addr {
+        &mut escrow.receiver
+    } else {
+        return err!(EscrowError::Unauthorized);
+    };
+    
+    //the message must be this intent at the party's current nonce
+    let nonce = party.relay_nonce;
+    require!(
+        message == relay_intent_message(escrow_key, action, amount, nonce).as_slice(),
+        EscrowError::InvalidRelayedIntent
+    );
+    party.relay_nonce = nonce.checked_add(1).ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    emitter.emit(RelayedIntentEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: *escrow_key,
+        party: signer,
+        action,
+        amount,
+        nonce,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    })?;
+    
+    Ok(signer)
+}
+
+//events
+#[event]
+pub struct RelayedIntentEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub party: Pubkey,
+    pub action: u8,
+    pub amount: u64,
+    pub nonce: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
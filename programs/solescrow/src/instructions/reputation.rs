@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::stats::update_if_initialized;
+
+/// Initialize a reputation account for an address
+#[derive(Accounts)]
+#[instruction(addr: Pubkey)]
+pub struct InitializeReputation<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Reputation::space(),
+        seeds = [seeds::REPUTATION, addr.as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_reputation(ctx: Context<InitializeReputation>, addr: Pubkey) -> Result<()> {
+    let reputation = &mut ctx.accounts.reputation;
+    reputation.addr = addr;
+    reputation.settlements_completed = 0;
+    reputation.disputes_opened = 0;
+    reputation.disputes_lost = 0;
+    reputation.volume = 0;
+    reputation.ratings_count = 0;
+    reputation.ratings_sum = 0;
+    reputation.rated_volume = 0;
+    reputation.last_updated = Clock::get()?.unix_timestamp;
+    reputation.bump = ctx.bumps.reputation;
+
+    Ok(())
+}
+
+/// Initialize a rating aggregate for an address
+#[derive(Accounts)]
+#[instruction(addr: Pubkey)]
+pub struct InitializeRatingAggregate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RatingAggregate::space(),
+        seeds = [seeds::RATING_AGGREGATE, addr.as_ref()],
+        bump
+    )]
+    pub rating_aggregate: Account<'info, RatingAggregate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_rating_aggregate(ctx: Context<InitializeRatingAggregate>, addr: Pubkey) -> Result<()> {
+    let rating_aggregate = &mut ctx.accounts.rating_aggregate;
+    rating_aggregate.addr = addr;
+    rating_aggregate.bump = ctx.bumps.rating_aggregate;
+
+    Ok(())
+}
+
+/// Rate the counterparty of a completed escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RateCounterparty<'info> {
+    #[account(mut)]
+    pub rater: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        init,
+        payer = rater,
+        space = Rating::space(),
+        seeds = [seeds::RATING, escrow.key().as_ref(), rater.key().as_ref()],
+        bump
+    )]
+    pub rating: Account<'info, Rating>,
+
+    /// Counterparty's reputation
+    #[account(
+        mut,
+        seeds = [seeds::REPUTATION, counterparty_reputation.addr.as_ref()],
+        bump = counterparty_reputation.bump
+    )]
+    pub counterparty_reputation: Account<'info, Reputation>,
+
+    /// CHECK: counterparty's rating aggregate, updated by update_if_initialized if it exists
+    #[account(
+        mut,
+        seeds = [seeds::RATING_AGGREGATE, counterparty_reputation.addr.as_ref()],
+        bump
+    )]
+    pub counterparty_ratings: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn rate_counterparty(
+    ctx: Context<RateCounterparty>,
+    score: u8,
+    comment_hash: [u8; 32],
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let rater = ctx.accounts.rater.key();
+
+    //check authorization (payer or receiver), and rate the other party
+    let ratee = if rater == escrow.payer.addr {
+        escrow.receiver.addr
+    } else if rater == escrow.receiver.addr {
+        escrow.payer.addr
+    } else {
+        return err!(EscrowError::Unauthorized);
+    };
+    require!(
+        ctx.accounts.counterparty_reputation.addr == ratee,
+        EscrowError::InvalidPartyAddress
+    );
+
+    //validate score
+    require!(
+        (MIN_RATING_SCORE..=MAX_RATING_SCORE).contains(&score),
+        EscrowError::InvalidRating
+    );
+
+    //only funded escrows carry a rating
+    let volume = escrow.payer.amount_paid;
+    require!(volume > 0, EscrowError::InvalidEscrowState);
+
+    let now = Clock::get()?.unix_timestamp;
+    let rating = &mut ctx.accounts.rating;
+    rating.escrow = escrow.key();
+    rating.rater = rater;
+    rating.ratee = ratee;
+    rating.score = score;
+    rating.comment_hash = comment_hash;
+    rating.timestamp = now;
+    rating.bump = ctx.bumps.rating;
+
+    ctx.accounts.counterparty_reputation.record_rating(score, volume, now);
+    update_if_initialized(&ctx.accounts.counterparty_ratings, |ratings: &mut RatingAggregate, now| {
+        ratings.record(score, now)
+    })?;
+
+    emit_cpi!(CounterpartyRatedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        rater,
+        ratee,
+        score,
+        comment_hash,
+        volume,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct CounterpartyRatedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub rater: Pubkey,
+    pub ratee: Pubkey,
+    pub score: u8,
+    pub comment_hash: [u8; 32],
+    pub volume: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Attach a settlement hook program to an escrow, or detach it with the default pubkey (both parties must sign)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSettlementHookAsym<'info> {
+    pub payer: Signer<'info>,
+
+    pub receiver: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow.payer.addr == payer.key() @ EscrowError::InvalidPayer,
+        constraint = escrow.receiver.addr == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn set_settlement_hook(ctx: Context<SetSettlementHookAsym>, settlement_hook: Pubkey) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+
+    //only allowlisted programs can be attached; detaching is always allowed
+    require!(
+        settlement_hook == Pubkey::default()
+            || ctx.accounts.program_config.settlement_hook_allowlist.contains(&settlement_hook),
+        EscrowError::SettlementHookNotAllowed
+    );
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.settlement_hook = settlement_hook;
+
+    emit_cpi!(SettlementHookSetEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        settlement_hook,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// How an escrow settlement moved funds, as reported to settlement hooks
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SettlementOutcome {
+    /// Funds were released to the receiver
+    #[default]
+    Release,
+    /// Funds were refunded to the payer
+    Refund,
+}
+
+/// Hook instruction, named the way Anchor derives its discriminator
+///
+/// Hooks implement `on_escrow_settled(escrow: Pubkey, outcome: SettlementOutcome, amount: u64, fee: u64,
+/// amount_delivered: u64)` taking (hook authority, escrow, hook state) accounts. The hook authority is a
+/// PDA of this program that signs only hook calls, so a hook can tell a real settlement from a spoofed one.
+pub const SETTLEMENT_HOOK_INSTRUCTION: &str = "on_escrow_settled";
+
+/// Build the instruction data of a settlement hook call
+pub fn settlement_hook_data(
+    escrow: &Pubkey,
+    outcome: SettlementOutcome,
+    amount: u64,
+    fee: u64,
+    amount_delivered: u64,
+) -> Result<Vec<u8>> {
+    let mut data = hash(format!("global:{}", SETTLEMENT_HOOK_INSTRUCTION).as_bytes()).to_bytes()[..8].to_vec();
+    (*escrow, outcome, amount, fee, amount_delivered).serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Call the escrow's settlement hook, if it has one that is still allowlisted
+///
+/// A hook removed from the allowlist is skipped, so a failing hook can be delisted to unblock settlements. 
+/// The escrow is locked while the hook runs, so the hook cannot re-enter release or refund.
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_settlement_hook<'info>(
+    escrow: &mut Account<'info, AsymEscrow>,
+    program_config: &ProgramConfig,
+    settlement_hook: Option<&UncheckedAccount<'info>>,
+    hook_authority: Option<&UncheckedAccount<'info>>,
+    hook_authority_bump: Option<u8>,
+    hook_state: Option<&UncheckedAccount<'info>>,
+    outcome: SettlementOutcome,
+    amount: u64,
+    fee: u64,
+    amount_delivered: u64,
+) -> Result<()> {
+    if !escrow.has_settlement_hook()
+        || !program_config.settlement_hook_allowlist.contains(&escrow.settlement_hook)
+    {
+        return Ok(());
+    }
+
+    let settlement_hook = settlement_hook.ok_or(EscrowError::SettlementHookNotAllowed)?;
+    let hook_authority = hook_authority.ok_or(EscrowError::SettlementHookNotAllowed)?;
+    let hook_authority_bump = hook_authority_bump.ok_or(EscrowError::SettlementHookNotAllowed)?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(hook_authority.key(), true),
+        AccountMeta::new_readonly(escrow.key(), false),
+    ];
+    let mut account_infos = vec![
+        hook_authority.to_account_info(),
+        escrow.to_account_info(),
+    ];
+    if let Some(hook_state) = hook_state {
+        accounts.push(AccountMeta::new(hook_state.key(), false));
+        account_infos.push(hook_state.to_account_info());
+    }
+
+    let data = settlement_hook_data(&escrow.key(), outcome, amount, fee, amount_delivered)?;
+    guarded_external_call(escrow, || {
+        invoke_signed(
+            &Instruction {
+                program_id: settlement_hook.key(),
+                accounts,
+                data,
+            },
+            &account_infos,
+            &[&[seeds::SETTLEMENT_HOOK_AUTHORITY, &[hook_authority_bump]]],
+        )?;
+        Ok(())
+    })
+}
+
+//events
+#[event]
+pub struct SettlementHookSetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub settlement_hook: Pubkey,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
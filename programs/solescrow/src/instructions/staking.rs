@@ -0,0 +1,385 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    sysvar::stake_history,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Convert the SOL held by a native escrow into an allowlisted liquid staking token (both parties must sign)
+/// 
+/// The SOL is deposited into an SPL stake pool and the pool tokens are held by the escrow vault until 
+/// the position is unwound at settlement.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeEscrowSolAsym<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub receiver: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.addr == payer.key() @ EscrowError::InvalidPayer,
+        constraint = escrow.receiver.addr == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = escrow.status == EscrowStatus::Active @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault holding the SOL
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// Vault's liquid staking token account
+    #[account(
+        init,
+        payer = payer,
+        token::mint = pool_mint,
+        token::authority = escrow_vault,
+        token::token_program = token_program,
+        seeds = [seeds::LST_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub lst_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: allowlisted stake pool, validated by the stake pool program
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+    
+    /// CHECK: stake pool withdraw authority, validated by the stake pool program
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: stake pool reserve, validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+    
+    /// CHECK: stake pool manager fee account, validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: SPL stake pool program
+    #[account(
+        address = SPL_STAKE_POOL_PROGRAM_ID @ EscrowError::StakePoolNotAllowed
+    )]
+    pub stake_pool_program: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn stake_escrow_sol(ctx: Context<StakeEscrowSolAsym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &ctx.accounts.escrow;
+    
+    //only native escrows with no settlement yet can be staked
+    require!(escrow.payer.currency_type == CurrencyType::Native, EscrowError::InvalidToken);
+    require!(!escrow.is_staked(), EscrowError::FundsStaked);
+    require!(
+        escrow.payer.amount_released == 0 && escrow.payer.amount_refunded == 0,
+        EscrowError::InvalidEscrowState
+    );
+    require!(
+        ctx.accounts.program_config.stake_pool_allowlist.contains(&ctx.accounts.stake_pool.key()),
+        EscrowError::StakePoolNotAllowed
+    );
+    
+    let lamports = escrow.get_amount_remaining();
+    require!(lamports > 0, EscrowError::InvalidAmount);
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    //deposit the SOL into the stake pool; the vault doubles as the referrer
+    invoke_stake_pool(
+        STAKE_POOL_DEPOSIT_SOL,
+        lamports,
+        vec![
+            AccountMeta::new(ctx.accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.stake_pool_withdraw_authority.key(), false),
+            AccountMeta::new(ctx.accounts.reserve_stake.key(), false),
+            AccountMeta::new(ctx.accounts.escrow_vault.key(), true),
+            AccountMeta::new(ctx.accounts.lst_vault.key(), false),
+            AccountMeta::new(ctx.accounts.manager_fee_account.key(), false),
+            AccountMeta::new(ctx.accounts.lst_vault.key(), false),
+            AccountMeta::new(ctx.accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+        ],
+        &[
+            ctx.accounts.stake_pool.to_account_info(),
+            ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+            ctx.accounts.reserve_stake.to_account_info(),
+            ctx.accounts.escrow_vault.to_account_info(),
+            ctx.accounts.lst_vault.to_account_info(),
+            ctx.accounts.manager_fee_account.to_account_info(),
+            ctx.accounts.pool_mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.stake_pool_program.to_account_info(),
+        ],
+        vault_signer,
+    )?;
+    
+    //record the position at the exchange rate the pool applied
+    ctx.accounts.lst_vault.reload()?;
+    let lst_amount = ctx.accounts.lst_vault.amount;
+    require!(lst_amount > 0, EscrowError::InvalidAmount);
+    
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.stake_pool = ctx.accounts.stake_pool.key();
+    escrow.staked_lamports = lamports;
+    escrow.lst_amount = lst_amount;
+    
+    emit_cpi!(EscrowSolStakedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        stake_pool: escrow.stake_pool,
+        lamports,
+        lst_amount,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Unwind a staked escrow back into SOL, crediting staking rewards (or pool fees) to the escrowed amount
+/// 
+/// Staked escrows must be unwound before they can be released, refunded or awarded.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakeEscrowSolAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(mut)]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault receiving the SOL
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LST_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub lst_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: the escrow's stake pool, validated by the stake pool program
+    #[account(
+        mut,
+        address = escrow.stake_pool @ EscrowError::StakePoolNotAllowed
+    )]
+    pub stake_pool: UncheckedAccount<'info>,
+    
+    /// CHECK: stake pool withdraw authority, validated by the stake pool program
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: stake pool reserve, validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+    
+    /// CHECK: stake pool manager fee account, validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = lst_vault.mint @ EscrowError::InvalidToken
+    )]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: SPL stake pool program
+    #[account(
+        address = SPL_STAKE_POOL_PROGRAM_ID @ EscrowError::StakePoolNotAllowed
+    )]
+    pub stake_pool_program: UncheckedAccount<'info>,
+    
+    /// CHECK: stake history sysvar
+    #[account(
+        address = stake_history::ID
+    )]
+    pub stake_history: UncheckedAccount<'info>,
+    
+    /// CHECK: native stake program
+    #[account(
+        address = STAKE_PROGRAM_ID
+    )]
+    pub stake_program: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn unstake_escrow_sol(ctx: Context<UnstakeEscrowSolAsym>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    
+    //check authorization (payer or receiver)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+    require!(escrow.is_staked(), EscrowError::InvalidEscrowState);
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    //redeem the whole position for SOL from the pool reserve
+    let lst_amount = escrow.lst_amount;
+    let lamports_before = ctx.accounts.escrow_vault.lamports();
+    invoke_stake_pool(
+        STAKE_POOL_WITHDRAW_SOL,
+        lst_amount,
+        vec![
+            AccountMeta::new(ctx.accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.stake_pool_withdraw_authority.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.escrow_vault.key(), true),
+            AccountMeta::new(ctx.accounts.lst_vault.key(), false),
+            AccountMeta::new(ctx.accounts.reserve_stake.key(), false),
+            AccountMeta::new(ctx.accounts.escrow_vault.key(), false),
+            AccountMeta::new(ctx.accounts.manager_fee_account.key(), false),
+            AccountMeta::new(ctx.accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.stake_history.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.stake_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+        ],
+        &[
+            ctx.accounts.stake_pool.to_account_info(),
+            ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+            ctx.accounts.escrow_vault.to_account_info(),
+            ctx.accounts.lst_vault.to_account_info(),
+            ctx.accounts.reserve_stake.to_account_info(),
+            ctx.accounts.manager_fee_account.to_account_info(),
+            ctx.accounts.pool_mint.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.stake_pool_program.to_account_info(),
+        ],
+        vault_signer,
+    )?;
+    let lamports_received = ctx.accounts.escrow_vault.lamports()
+        .checked_sub(lamports_before)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    //the escrowed amount follows the exchange rate: rewards add to it, pool fees reduce it
+    let escrow = &mut ctx.accounts.escrow;
+    let staked_lamports = escrow.staked_lamports;
+    escrow.payer.amount_paid = escrow.payer.amount_paid
+        .checked_sub(staked_lamports)
+        .and_then(|amount| amount.checked_add(lamports_received))
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    escrow.staked_lamports = 0;
+    escrow.lst_amount = 0;
+    
+    emit_cpi!(EscrowSolUnstakedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        stake_pool: escrow.stake_pool,
+        lst_amount,
+        staked_lamports,
+        lamports_received,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//SPL stake pool instruction indices
+const STAKE_POOL_DEPOSIT_SOL: u8 = 14;
+const STAKE_POOL_WITHDRAW_SOL: u8 = 16;
+
+//helper function to invoke the SPL stake pool program, signed by the escrow vault
+fn invoke_stake_pool<'info>(
+    instruction: u8,
+    amount: u64,
+    accounts: Vec<AccountMeta>,
+    account_infos: &[AccountInfo<'info>],
+    vault_signer: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = vec![instruction];
+    data.extend_from_slice(&amount.to_le_bytes());
+    
+    invoke_signed(
+        &Instruction {
+            program_id: SPL_STAKE_POOL_PROGRAM_ID,
+            accounts,
+            data,
+        },
+        account_infos,
+        vault_signer,
+    )?;
+    Ok(())
+}
+
+//events
+#[event]
+pub struct EscrowSolStakedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub stake_pool: Pubkey,
+    pub lamports: u64,
+    pub lst_amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct EscrowSolUnstakedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub stake_pool: Pubkey,
+    pub lst_amount: u64,
+    pub staked_lamports: u64,
+    pub lamports_received: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,386 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+/// Start tracking statistics for a currency (authority only)
+/// 
+/// Native SOL is tracked under the default pubkey.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(currency: Pubkey)]
+pub struct InitializeCurrencyStats<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = CurrencyStats::space(),
+        seeds = [seeds::CURRENCY_STATS, currency.as_ref()],
+        bump
+    )]
+    pub currency_stats: Account<'info, CurrencyStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_currency_stats(ctx: Context<InitializeCurrencyStats>, currency: Pubkey) -> Result<()> {
+    let currency_stats = &mut ctx.accounts.currency_stats;
+    currency_stats.currency = currency;
+    currency_stats.updated_at = Clock::get()?.unix_timestamp;
+    currency_stats.bump = ctx.bumps.currency_stats;
+
+    emit_cpi!(CurrencyStatsInitializedEvent {
+        version: EVENT_VERSION,
+        currency,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Create the global TVL ledger (authority only)
+#[derive(Accounts)]
+pub struct InitializeTvlLedger<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TvlLedger::space(),
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: Account<'info, TvlLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_tvl_ledger(ctx: Context<InitializeTvlLedger>) -> Result<()> {
+    let tvl_ledger = &mut ctx.accounts.tvl_ledger;
+    tvl_ledger.updated_at = Clock::get()?.unix_timestamp;
+    tvl_ledger.bump = ctx.bumps.tvl_ledger;
+
+    Ok(())
+}
+
+/// Start tracking a currency's TVL (authority only)
+/// 
+/// Escrows already funded in the currency are not counted until `audit_tvl` shows the gap; tracking is 
+/// best started before a currency is first used.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TrackTvlCurrency<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump,
+        has_one = authority @ EscrowError::Unauthorized,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump = tvl_ledger.bump
+    )]
+    pub tvl_ledger: Account<'info, TvlLedger>,
+}
+
+pub fn track_tvl_currency(ctx: Context<TrackTvlCurrency>, currency: Pubkey) -> Result<()> {
+    let tvl_ledger = &mut ctx.accounts.tvl_ledger;
+    require!(tvl_ledger.entries.len() < MAX_TVL_CURRENCIES, EscrowError::InvalidTvlCurrency);
+    require!(tvl_ledger.track(currency), EscrowError::InvalidTvlCurrency);
+
+    emit_cpi!(TvlCurrencyTrackedEvent {
+        version: EVENT_VERSION,
+        currency,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Reconcile a currency's TVL against escrow vault balances (permissionless)
+/// 
+/// Escrows are passed in the remaining accounts as pairs of the escrow and its vault: the vault PDA itself 
+/// for native SOL, or the vault's token account. Every escrow's vault must hold at least its locked funds, 
+/// counting funds out on loan or staked. The totals are emitted for auditors to sum across pages and 
+/// compare with the ledger.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AuditTvl<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [seeds::TVL_LEDGER],
+        bump = tvl_ledger.bump
+    )]
+    pub tvl_ledger: Account<'info, TvlLedger>,
+}
+
+pub fn audit_tvl<'info>(ctx: Context<'_, '_, 'info, 'info, AuditTvl<'info>>, currency: Pubkey) -> Result<()> {
+    let ledger_locked = ctx.accounts.tvl_ledger
+        .locked(&currency)
+        .ok_or(EscrowError::InvalidTvlCurrency)?;
+    require!(ctx.remaining_accounts.len() % 2 == 0, EscrowError::InvalidEscrow);
+
+    let mut audited: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+    let mut escrow_locked: u64 = 0;
+    let mut vault_balance: u64 = 0;
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        let escrow = Account::<AsymEscrow>::try_from(&accounts[0])?;
+        require!(escrow.payer.currency == currency, EscrowError::InvalidTvlCurrency);
+        require!(!audited.contains(&escrow.key()), EscrowError::InvalidEscrow);
+
+        //the vault is either the escrow's vault PDA or a token account it owns
+        let (vault, _) = Pubkey::find_program_address(
+            &[seeds::ESCROW_VAULT, escrow.key().as_ref()],
+            &crate::ID,
+        );
+        let balance = if escrow.payer.currency_type == CurrencyType::Native {
+            require_keys_eq!(accounts[1].key(), vault, EscrowError::InvalidEscrow);
+            accounts[1].lamports()
+        } else {
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+            require!(
+                token_account.owner == vault && token_account.mint == currency,
+                EscrowError::InvalidToken
+            );
+            token_account.amount
+        };
+
+        //lent and staked funds are held outside the vault
+        let held = balance
+            .saturating_add(escrow.lent_amount)
+            .saturating_add(escrow.staked_lamports);
+        let locked = escrow.get_amount_remaining();
+        require!(held >= locked, EscrowError::TvlInvariantViolated);
+
+        escrow_locked = escrow_locked.checked_add(locked).ok_or(EscrowError::ArithmeticOverflow)?;
+        vault_balance = vault_balance.checked_add(held).ok_or(EscrowError::ArithmeticOverflow)?;
+        audited.push(escrow.key());
+    }
+
+    emit_cpi!(TvlAuditedEvent {
+        version: EVENT_VERSION,
+        currency,
+        seq: ctx.accounts.program_config.next_event_seq(),
+        escrows: audited.len() as u32,
+        escrow_locked,
+        vault_balance,
+        ledger_locked,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+
+    Ok(())
+}
+
+/// Start keeping lifetime counters for an address (permissionless)
+#[derive(Accounts)]
+#[instruction(addr: Pubkey)]
+pub struct InitializeUserStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = UserStats::space(),
+        seeds = [seeds::USER_STATS, addr.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_user_stats(ctx: Context<InitializeUserStats>, addr: Pubkey) -> Result<()> {
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.addr = addr;
+    user_stats.bump = ctx.bumps.user_stats;
+
+    Ok(())
+}
+
+/// Start tracking counters for an escrow category
+#[derive(Accounts)]
+#[instruction(category: EscrowCategory)]
+pub struct InitializeCategoryStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = CategoryStats::space(),
+        seeds = [seeds::CATEGORY_STATS, &[category as u8]],
+        bump
+    )]
+    pub category_stats: Account<'info, CategoryStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_category_stats(ctx: Context<InitializeCategoryStats>, category: EscrowCategory) -> Result<()> {
+    let category_stats = &mut ctx.accounts.category_stats;
+    category_stats.category = category;
+    category_stats.updated_at = Clock::get()?.unix_timestamp;
+    category_stats.bump = ctx.bumps.category_stats;
+
+    Ok(())
+}
+
+/// Start keeping a daily volume history for a currency
+/// 
+/// Native SOL is tracked under the default pubkey.
+#[derive(Accounts)]
+#[instruction(currency: Pubkey)]
+pub struct InitializeVolumeHistory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = VolumeHistory::space(),
+        seeds = [seeds::VOLUME_HISTORY, currency.as_ref()],
+        bump
+    )]
+    pub volume_history: Account<'info, VolumeHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_volume_history(ctx: Context<InitializeVolumeHistory>, currency: Pubkey) -> Result<()> {
+    let volume_history = &mut ctx.accounts.volume_history;
+    volume_history.currency = currency;
+    volume_history.updated_at = Clock::get()?.unix_timestamp;
+    volume_history.bump = ctx.bumps.volume_history;
+
+    Ok(())
+}
+
+/// Apply an update to a currency's statistics, if they are tracked
+/// 
+/// The stats account is always passed at its PDA address, so it can't be left out to skip an update.
+pub fn update_currency_stats(
+    currency_stats: &UncheckedAccount,
+    update: impl FnOnce(&mut CurrencyStats, i64),
+) -> Result<()> {
+    update_if_initialized(currency_stats, update)
+}
+
+/// Record funds entering and leaving a currency's escrow vaults on the TVL ledger, once it exists
+pub fn update_tvl_ledger(tvl_ledger: &UncheckedAccount, currency: &Pubkey, deposited: u64, withdrawn: u64) -> Result<()> {
+    update_if_initialized(tvl_ledger, |ledger: &mut TvlLedger, now| {
+        ledger.record(currency, deposited, withdrawn, now)
+    })
+}
+
+/// Record a settled escrow on both parties' lifetime counters, where they exist
+pub fn update_user_stats(
+    payer_stats: &UncheckedAccount,
+    receiver_stats: &UncheckedAccount,
+    volume: u64,
+    disputed: bool,
+) -> Result<()> {
+    update_if_initialized(payer_stats, |stats: &mut UserStats, now| {
+        stats.record_settlement(true, volume, disputed, now)
+    })?;
+    update_if_initialized(receiver_stats, |stats: &mut UserStats, now| {
+        stats.record_settlement(false, volume, disputed, now)
+    })
+}
+
+/// Apply an update to an escrow category's counters, if they are tracked
+pub fn update_category_stats(
+    category_stats: &UncheckedAccount,
+    update: impl FnOnce(&mut CategoryStats, i64),
+) -> Result<()> {
+    update_if_initialized(category_stats, update)
+}
+
+/// Record volume entering and leaving a currency's escrows in today's bucket, if the history is kept
+pub fn update_volume_history(volume_history: &UncheckedAccount, volume_in: u64, volume_settled: u64) -> Result<()> {
+    update_if_initialized(volume_history, |history: &mut VolumeHistory, now| {
+        history.record(volume_in, volume_settled, now)
+    })
+}
+
+/// Apply an update to an opt-in tracking account, skipping it if the account hasn't been created
+pub fn update_if_initialized<T: AccountSerialize + AccountDeserialize>(
+    account: &UncheckedAccount,
+    update: impl FnOnce(&mut T, i64),
+) -> Result<()> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    let mut state = T::try_deserialize(&mut &data[..])?;
+    update(&mut state, Clock::get()?.unix_timestamp);
+
+    let mut writer: &mut [u8] = &mut data[..];
+    state.try_serialize(&mut writer)?;
+
+    Ok(())
+}
+
+//events
+#[event]
+pub struct CurrencyStatsInitializedEvent {
+    pub version: u8,
+    pub currency: Pubkey,
+    pub seq: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct TvlCurrencyTrackedEvent {
+    pub version: u8,
+    pub currency: Pubkey,
+    pub seq: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct TvlAuditedEvent {
+    pub version: u8,
+    pub currency: Pubkey,
+    pub seq: u64,
+    pub escrows: u32,
+    pub escrow_locked: u64,
+    pub vault_balance: u64,
+    pub ledger_locked: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+use crate::instructions::stats::update_tvl_ledger;
+
+/// Swap a multi-mint escrow's deposit into another of its accepted mints (receiver only)
+/// 
+/// The receiver may take the payout in any mint the escrow accepts. The whole deposit is swapped before any 
+/// of it is settled, and the output is held to the escrow's own terms: converted at the ratio of the two 
+/// accepted amounts, it may fall short by no more than the protocol's price impact limit. The route comes 
+/// from the router's quote and its accounts are passed as remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SwapEscrowMintAsym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.receiver.addr == signer.key() @ EscrowError::Unauthorized,
+        constraint = escrow.status == EscrowStatus::Active @ EscrowError::InvalidEscrowState,
+        constraint = !escrow.external_call_in_progress @ EscrowError::ReentrantCall,
+    )]
+    pub escrow: Box<Account<'info, AsymEscrow>>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+    
+    /// Escrow vault, owner of both token accounts, signs the route
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// Vault's token account of the deposited mint
+    #[account(
+        mut,
+        constraint = source_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Vault's token account of the mint swapped into
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == target_mint.key() @ EscrowError::InvalidToken,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Accepted mint the deposit is swapped into
+    pub target_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: swap router, checked against the config's allowlist by swap_at_settlement
+    #[account(executable)]
+    pub swap_router: UncheckedAccount<'info>,
+    
+    /// CHECK: global TVL ledger, updated by update_tvl_ledger once it exists
+    #[account(
+        mut,
+        seeds = [seeds::TVL_LEDGER],
+        bump
+    )]
+    pub tvl_ledger: UncheckedAccount<'info>,
+}
+
+pub fn swap_escrow_mint<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapEscrowMintAsym<'info>>,
+    bounds: SwapBounds,
+    route_data: Vec<u8>,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &ctx.accounts.escrow;
+    let source = *escrow.accepted_mints
+        .iter()
+        .find(|m| m.mint == escrow.payer.currency)
+        .ok_or(EscrowError::InvalidToken)?;
+    let target = *escrow.accepted_mints
+        .iter()
+        .find(|m| m.mint == ctx.accounts.target_mint.key())
+        .ok_or(EscrowError::InvalidToken)?;
+    require!(source.mint != target.mint, EscrowError::InvalidToken);
+    
+    //only a deposit nothing has been paid out of, and that is all in the vault, can change mint
+    require!(
+        escrow.payer.amount_released == 0 && escrow.payer.amount_refunded == 0 && escrow.arbitration_fee_paid == 0,
+        EscrowError::InvalidEscrowState
+    );
+    require!(!escrow.is_lent(), EscrowError::FundsLent);
+    require!(!escrow.is_staked(), EscrowError::FundsStaked);
+    let amount_in = escrow.payer.amount_paid;
+    require!(amount_in > 0, EscrowError::InvalidAmount);
+    
+    //what the deposit is worth in the target mint, at the escrow's terms
+    let fair_amount_out = (amount_in as u128)
+        .checked_mul(target.amount as u128)
+        .ok_or(EscrowError::ArithmeticOverflow)?
+        / source.amount as u128;
+    let fair_amount_out = u64::try_from(fair_amount_out).map_err(|_| EscrowError::ArithmeticOverflow)?;
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    let accounts = &mut *ctx.accounts;
+    let amount_out = guarded_external_call(&mut accounts.escrow, || {
+        swap_at_settlement(
+            &accounts.program_config,
+            &accounts.swap_router,
+            &accounts.escrow_vault.to_account_info(),
+            &mut accounts.source_token_account,
+            &mut accounts.destination_token_account,
+            ctx.remaining_accounts,
+            route_data,
+            amount_in,
+            &bounds,
+            Some(fair_amount_out),
+            vault_signer,
+        )
+    })?;
+    
+    //the escrow now owes and holds the target mint
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.payer.currency = target.mint;
+    escrow.payer.amount = target.amount;
+    escrow.payer.decimals = ctx.accounts.target_mint.decimals;
+    escrow.payer.amount_paid = amount_out;
+    update_tvl_ledger(&ctx.accounts.tvl_ledger, &source.mint, 0, amount_in)?;
+    update_tvl_ledger(&ctx.accounts.tvl_ledger, &target.mint, amount_out, 0)?;
+    
+    emit_cpi!(EscrowMintSwappedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        from_mint: source.mint,
+        to_mint: target.mint,
+        amount_in,
+        amount_out,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Bounds a swap at settlement must execute within, taken from the router's quote
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapBounds {
+    /// Output the router quoted for the input amount
+    pub quoted_amount_out: u64,
+    /// Slippage tolerated below the quote (bps)
+    pub slippage_bps: u16,
+}
+
+impl SwapBounds {
+    /// Check the bounds against the protocol's slippage limit
+    pub fn validate(&self, max_slippage_bps: u16) -> Result<()> {
+        require!(self.quoted_amount_out > 0, SwapError::InvalidQuote);
+        require!(self.slippage_bps <= max_slippage_bps, SwapError::SlippageTooHigh);
+        Ok(())
+    }
+
+    /// Lowest output the swap may deliver
+    pub fn min_amount_out(&self) -> Result<u64> {
+        let min_amount_out = (self.quoted_amount_out as u128)
+            .checked_mul(BPS_DENOMINATOR.saturating_sub(self.slippage_bps as u64) as u128)
+            .ok_or(EscrowError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(min_amount_out as u64)
+    }
+}
+
+/// Check a swap's output against the fair amount for its input (e.g. from an oracle), failing if the route
+/// moved the price by more than the protocol allows
+pub fn check_price_impact(amount_out: u64, fair_amount_out: u64, max_price_impact_bps: u16) -> Result<()> {
+    let floor = (fair_amount_out as u128)
+        .checked_mul(BPS_DENOMINATOR.saturating_sub(max_price_impact_bps as u64) as u128)
+        .ok_or(EscrowError::ArithmeticOverflow)?
+        / BPS_DENOMINATOR as u128;
+    require!(amount_out as u128 >= floor, SwapError::PriceImpactExceeded);
+    Ok(())
+}
+
+/// Swap escrowed tokens through an allowlisted router (e.g. Jupiter), returning the amount received
+///
+/// The route's instruction data and accounts come from the router's quote, built off-chain, and the escrow
+/// vault signs for them. Only balances are trusted: both token accounts must belong to the vault, the source
+/// may lose at most `amount_in`, and the destination must gain at least the bounds' minimum, and stay within
+/// the price impact limit of `fair_amount_out` when one is given.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_at_settlement<'info>(
+    program_config: &ProgramConfig,
+    router: &UncheckedAccount<'info>,
+    escrow_vault: &AccountInfo<'info>,
+    source: &mut InterfaceAccount<'info, TokenAccount>,
+    destination: &mut InterfaceAccount<'info, TokenAccount>,
+    route_accounts: &[AccountInfo<'info>],
+    route_data: Vec<u8>,
+    amount_in: u64,
+    bounds: &SwapBounds,
+    fair_amount_out: Option<u64>,
+    vault_signer: &[&[&[u8]]],
+) -> Result<u64> {
+    //validate router, bounds and accounts
+    require!(
+        program_config.swap_router_allowlist.contains(router.key),
+        SwapError::RouterNotAllowed
+    );
+    bounds.validate(program_config.max_swap_slippage_bps)?;
+    require!(amount_in > 0, EscrowError::InvalidAmount);
+    require!(
+        source.owner == escrow_vault.key() && destination.owner == escrow_vault.key(),
+        SwapError::InvalidSwapAccounts
+    );
+    require!(source.mint != destination.mint, SwapError::InvalidSwapAccounts);
+
+    //the vault signs for itself wherever the route uses it
+    let accounts = route_accounts
+        .iter()
+        .map(|account| {
+            let is_signer = account.is_signer || account.key == escrow_vault.key;
+            if account.is_writable {
+                AccountMeta::new(*account.key, is_signer)
+            } else {
+                AccountMeta::new_readonly(*account.key, is_signer)
+            }
+        })
+        .collect();
+
+    let source_before = source.amount;
+    let destination_before = destination.amount;
+    invoke_signed(
+        &Instruction {
+            program_id: router.key(),
+            accounts,
+            data: route_data,
+        },
+        route_accounts,
+        vault_signer,
+    )?;
+
+    //check what the route actually moved
+    source.reload()?;
+    destination.reload()?;
+    let amount_spent = source_before
+        .checked_sub(source.amount)
+        .ok_or(SwapError::InputExceeded)?;
+    require!(amount_spent <= amount_in, SwapError::InputExceeded);
+    let amount_out = destination.amount
+        .checked_sub(destination_before)
+        .ok_or(SwapError::SlippageExceeded)?;
+    require!(amount_out >= bounds.min_amount_out()?, SwapError::SlippageExceeded);
+    if let Some(fair_amount_out) = fair_amount_out {
+        check_price_impact(amount_out, fair_amount_out, program_config.max_swap_price_impact_bps)?;
+    }
+
+    Ok(amount_out)
+}
+
+//events
+#[event]
+pub struct EscrowMintSwappedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub from_mint: Pubkey,
+    pub to_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -0,0 +1,948 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+use crate::instructions::asym_escrow::{EscrowStatusChangedEvent, ReleaseAssentGivenEvent};
+use crate::instructions::expiry::mark_expired_sym;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreateSymEscrowParams {
+    pub payer: Pubkey,
+    pub payer_currency: Pubkey, // Pubkey::default() for native SOL
+    pub payer_amount: u64,
+    pub receiver: Pubkey,
+    pub receiver_currency: Pubkey, // Pubkey::default() for native SOL
+    pub receiver_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64, // required; deposits can be refunded after it
+    pub nonce: u64,
+    pub category: EscrowCategory, // Uncategorized if not given
+}
+
+/// Create symmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateSymEscrowParams)]
+pub struct CreateSymEscrow<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = SymEscrow::space(),
+        seeds = [seeds::SYM_ESCROW, creator.key().as_ref(), &params.nonce.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, SymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Mint of the first party's asset (only required for SPL tokens)
+    #[account(
+        address = params.payer_currency @ EscrowError::InvalidToken
+    )]
+    pub payer_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Mint of the second party's asset (only required for SPL tokens)
+    #[account(
+        address = params.receiver_currency @ EscrowError::InvalidToken
+    )]
+    pub receiver_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_escrow_sym(
+    ctx: Context<CreateSymEscrow>,
+    params: CreateSymEscrowParams,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //validate inputs
+    require!(params.payer != Pubkey::default(), EscrowError::InvalidPayer);
+    require!(params.receiver != Pubkey::default(), EscrowError::InvalidReceiver);
+    require!(params.payer != params.receiver, EscrowError::InvalidReceiver);
+    require!(params.payer_amount > 0 && params.receiver_amount > 0, EscrowError::InvalidAmount);
+    //at most one side can be native SOL
+    require!(params.payer_currency != params.receiver_currency, EscrowError::InvalidCurrency);
+    
+    //validate currencies; swaps settle in one instruction, which has no room for transfer hook accounts
+    let currencies = [
+        (params.payer_currency, &ctx.accounts.payer_mint),
+        (params.receiver_currency, &ctx.accounts.receiver_mint),
+    ];
+    let mut decimals = [NATIVE_DECIMALS; 2];
+    for (i, (currency, mint)) in currencies.into_iter().enumerate() {
+        if currency != Pubkey::default() {
+            let mint = mint.as_ref().ok_or(EscrowError::InvalidToken)?;
+            require!(mint.key() == currency, EscrowError::InvalidToken);
+            require!(
+                get_transfer_hook_program(mint)?.is_none(),
+                EscrowError::TransferHookNotAllowed
+            );
+            decimals[i] = mint.decimals;
+        }
+    }
+    
+    //validate dates; deposits must not be stranded if the counterparty never deposits
+    require!(params.end_time > 0, EscrowError::InvalidEndDate);
+    validate_escrow_dates(params.start_time, params.end_time)?;
+    
+    //initialize escrow
+    let escrow = &mut ctx.accounts.escrow;
+    let escrow_id = generate_escrow_id(&ctx.accounts.creator.key(), params.nonce);
+    
+    escrow.id = escrow_id;
+    escrow.payer = EscrowParty {
+        addr: params.payer,
+        currency: params.payer_currency,
+        currency_type: currency_type_of(&params.payer_currency),
+        amount: params.payer_amount,
+        decimals: decimals[0],
+        ..Default::default()
+    };
+    escrow.receiver = EscrowParty {
+        addr: params.receiver,
+        currency: params.receiver_currency,
+        currency_type: currency_type_of(&params.receiver_currency),
+        amount: params.receiver_amount,
+        decimals: decimals[1],
+        ..Default::default()
+    };
+    escrow.timestamp = Clock::get()?.unix_timestamp;
+    escrow.start_time = params.start_time;
+    escrow.end_time = params.end_time;
+    escrow.status = EscrowStatus::Pending;
+    escrow.released = false;
+    escrow.fee_bps = ctx.accounts.program_config.default_fee_bps;
+    escrow.creator = ctx.accounts.creator.key();
+    escrow.nonce = params.nonce;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.category = params.category;
+    
+    log_escrow_action(log_actions::CREATE, &escrow_id, &[
+        ("payer_amount", params.payer_amount),
+        ("receiver_amount", params.receiver_amount),
+    ]);
+    emit_cpi!(SymEscrowCreatedEvent {
+        version: EVENT_VERSION,
+        escrow_id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        creator: ctx.accounts.creator.key(),
+        payer: params.payer,
+        payer_amount: params.payer_amount,
+        receiver: params.receiver,
+        receiver_amount: params.receiver_amount,
+        payer_decimals: decimals[0],
+        receiver_decimals: decimals[1],
+        category: params.category,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Deposit one party's side of a symmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlacePaymentSym<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, SymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, owner of the deposit token accounts
+    #[account(
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = depositor,
+        token::token_program = token_program,
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Vault-owned token account holding the depositor's side
+    #[account(
+        init,
+        payer = depositor,
+        token::mint = mint,
+        token::authority = escrow_vault,
+        token::token_program = token_program,
+        seeds = [seeds::SYM_VAULT, escrow.key().as_ref(), depositor.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn place_payment_sym(ctx: Context<PlacePaymentSym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //check escrow timing
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &mut ctx.accounts.escrow;
+    require!(
+        now >= escrow.start_time && !escrow.is_expired(now),
+        EscrowError::EscrowNotActive
+    );
+    
+    //validate depositor and their side
+    let depositor = ctx.accounts.depositor.key();
+    let party = escrow.party_mut(&depositor).ok_or(EscrowError::Unauthorized)?;
+    require!(party.amount_paid == 0, EscrowError::AlreadyDeposited);
+    require!(ctx.accounts.mint.key() == party.currency, EscrowError::InvalidToken);
+    require!(ctx.accounts.mint.decimals == party.decimals, EscrowError::InvalidDecimals);
+    let amount = party.amount;
+    let decimals = party.decimals;
+    
+    transfer_spl_tokens(
+        &ctx.accounts.depositor_token_account,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.mint,
+        &ctx.accounts.depositor,
+        amount,
+        &ctx.accounts.token_program,
+        &[],
+    )?;
+    
+    //transfer fee extensions withhold part of the amount at the destination
+    ctx.accounts.vault_token_account.reload()?;
+    let amount_received = ctx.accounts.vault_token_account.amount;
+    require!(amount_received > 0, EscrowError::InvalidAmount);
+    
+    //update escrow state
+    let escrow = &mut ctx.accounts.escrow;
+    let party = escrow.party_mut(&depositor).ok_or(EscrowError::Unauthorized)?;
+    party.amount_paid = amount_received;
+    if let Some(old) = escrow.set_status(EscrowStatus::Active) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Active,
+            actor: depositor,
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    
+    log_escrow_action(log_actions::DEPOSIT, &escrow.id, &[("amount", amount_received)]);
+    emit_cpi!(SymPaymentReceivedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        depositor,
+        amount: amount_received,
+        decimals,
+        transfer_fee: amount.saturating_sub(amount_received),
+        fully_deposited: escrow.is_fully_deposited(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Deposit one party's native SOL side of a symmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceNativePaymentSym<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, SymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault holding the native side
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn place_native_payment_sym(ctx: Context<PlaceNativePaymentSym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //check escrow timing
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &mut ctx.accounts.escrow;
+    require!(
+        now >= escrow.start_time && !escrow.is_expired(now),
+        EscrowError::EscrowNotActive
+    );
+    
+    //validate depositor and their side
+    let depositor = ctx.accounts.depositor.key();
+    let party = escrow.party_mut(&depositor).ok_or(EscrowError::Unauthorized)?;
+    require!(party.currency_type == CurrencyType::Native, EscrowError::InvalidCurrency);
+    require!(party.amount_paid == 0, EscrowError::AlreadyDeposited);
+    let amount = party.amount;
+    
+    transfer_native_sol(
+        ctx.accounts.depositor.to_account_info(),
+        ctx.accounts.escrow_vault.to_account_info(),
+        amount,
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    //update escrow state
+    party.amount_paid = amount;
+    if let Some(old) = escrow.set_status(EscrowStatus::Active) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Active,
+            actor: depositor,
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    
+    log_escrow_action(log_actions::DEPOSIT, &escrow.id, &[("amount", amount)]);
+    emit_cpi!(SymPaymentReceivedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        depositor,
+        amount,
+        decimals: NATIVE_DECIMALS,
+        transfer_fee: 0,
+        fully_deposited: escrow.is_fully_deposited(),
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+/// Consent to settle a symmetric escrow; the deposits are swapped once both parties consent
+/// 
+/// Settlement crosses both vaults in this one instruction: SPL sides move between token accounts and a 
+/// native SOL side moves out of the escrow vault, with the protocol fee charged on both legs.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseEscrowSym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Active @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, SymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, holding a native side and owning the deposit token accounts
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: payer's wallet, receiving a native side; may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = escrow.payer.addr @ EscrowError::InvalidPayer
+    )]
+    pub payer: UncheckedAccount<'info>,
+    
+    /// CHECK: receiver's wallet, receiving a native side; may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = escrow.receiver.addr @ EscrowError::InvalidReceiver
+    )]
+    pub receiver: UncheckedAccount<'info>,
+    
+    /// Fee vault, receiving native fees
+    #[account(
+        mut,
+        address = program_config.fee_vault @ EscrowError::InvalidConfig
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    /// First party's SPL deposit
+    #[account(
+        mut,
+        seeds = [seeds::SYM_VAULT, escrow.key().as_ref(), escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Second party's SPL deposit
+    #[account(
+        mut,
+        seeds = [seeds::SYM_VAULT, escrow.key().as_ref(), escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Second party's account receiving the first party's SPL asset
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == escrow.receiver.addr
+            && receiver_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub receiver_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// First party's account receiving the second party's SPL asset
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == escrow.payer.addr
+            && payer_token_account.mint == escrow.receiver.currency @ EscrowError::InvalidToken,
+    )]
+    pub payer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Fee vault accounts for SPL legs (required when fees apply)
+    #[account(
+        mut,
+        constraint = payer_fee_token_account.owner == program_config.fee_vault
+            && payer_fee_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub payer_fee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = receiver_fee_token_account.owner == program_config.fee_vault
+            && receiver_fee_token_account.mint == escrow.receiver.currency @ EscrowError::InvalidToken,
+    )]
+    pub receiver_fee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Mints and token programs of SPL legs
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub payer_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        address = escrow.receiver.currency @ EscrowError::InvalidToken
+    )]
+    pub receiver_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub payer_token_program: Option<Interface<'info, TokenInterface>>,
+    pub receiver_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_escrow_sym(ctx: Context<ReleaseEscrowSym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    
+    //check authorization (either party)
+    let is_payer = ctx.accounts.signer.key() == escrow.payer.addr;
+    let is_receiver = ctx.accounts.signer.key() == escrow.receiver.addr;
+    require!(is_payer || is_receiver, EscrowError::Unauthorized);
+    
+    //check escrow timing and deposits
+    require!(
+        !escrow.is_expired(Clock::get()?.unix_timestamp),
+        EscrowError::EscrowNotActive
+    );
+    require!(escrow.is_fully_deposited(), EscrowError::InvalidEscrowState);
+    
+    //record consent
+    if is_payer && !escrow.payer.released {
+        escrow.payer.released = true;
+        emit_cpi!(ReleaseAssentGivenEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            assenting_address: ctx.accounts.signer.key(),
+            assent_type: ReleaseAssentType::Payer,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    
+    if is_receiver && !escrow.receiver.released {
+        escrow.receiver.released = true;
+        emit_cpi!(ReleaseAssentGivenEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            assenting_address: ctx.accounts.signer.key(),
+            assent_type: ReleaseAssentType::Receiver,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    
+    //execute the swap if both parties consent
+    if escrow.payer.released && escrow.receiver.released {
+        execute_swap(ctx)?;
+    }
+    
+    Ok(())
+}
+
+/// Refund both deposits of an expired symmetric escrow
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundEscrowSym<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, SymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// Escrow vault, holding a native side and owning the deposit token accounts
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW_VAULT, escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: SystemAccount<'info>,
+    
+    /// CHECK: payer's wallet, receiving a native side back; may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = escrow.payer.addr @ EscrowError::InvalidPayer
+    )]
+    pub payer: UncheckedAccount<'info>,
+    
+    /// CHECK: receiver's wallet, receiving a native side back; may be a program-owned multisig vault
+    #[account(
+        mut,
+        address = escrow.receiver.addr @ EscrowError::InvalidReceiver
+    )]
+    pub receiver: UncheckedAccount<'info>,
+    
+    /// SPL deposits (only required for SPL parties that deposited)
+    #[account(
+        mut,
+        seeds = [seeds::SYM_VAULT, escrow.key().as_ref(), escrow.payer.addr.as_ref()],
+        bump
+    )]
+    pub payer_vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SYM_VAULT, escrow.key().as_ref(), escrow.receiver.addr.as_ref()],
+        bump
+    )]
+    pub receiver_vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Parties' accounts receiving their own SPL deposits back
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == escrow.payer.addr
+            && payer_token_account.mint == escrow.payer.currency @ EscrowError::InvalidToken,
+    )]
+    pub payer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == escrow.receiver.addr
+            && receiver_token_account.mint == escrow.receiver.currency @ EscrowError::InvalidToken,
+    )]
+    pub receiver_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Mints and token programs of SPL legs
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub payer_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        address = escrow.receiver.currency @ EscrowError::InvalidToken
+    )]
+    pub receiver_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub payer_token_program: Option<Interface<'info, TokenInterface>>,
+    pub receiver_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn refund_escrow_sym(ctx: Context<RefundEscrowSym>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    let escrow = &ctx.accounts.escrow;
+    
+    //check authorization (either party)
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == escrow.payer.addr || signer == escrow.receiver.addr,
+        EscrowError::Unauthorized
+    );
+    
+    //deposits can only be reclaimed once the escrow has expired
+    require!(
+        escrow.is_expired(Clock::get()?.unix_timestamp),
+        EscrowError::EscrowNotExpired
+    );
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    //return each deposit to its owner
+    let legs = [
+        (
+            SymLeg {
+                currency_type: escrow.payer.currency_type,
+                vault_token_account: ctx.accounts.payer_vault_token_account.as_ref(),
+                mint: ctx.accounts.payer_mint.as_ref(),
+                token_program: ctx.accounts.payer_token_program.as_ref(),
+            },
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.payer_token_account.as_ref(),
+            escrow.payer.amount_paid,
+        ),
+        (
+            SymLeg {
+                currency_type: escrow.receiver.currency_type,
+                vault_token_account: ctx.accounts.receiver_vault_token_account.as_ref(),
+                mint: ctx.accounts.receiver_mint.as_ref(),
+                token_program: ctx.accounts.receiver_token_program.as_ref(),
+            },
+            ctx.accounts.receiver.to_account_info(),
+            ctx.accounts.receiver_token_account.as_ref(),
+            escrow.receiver.amount_paid,
+        ),
+    ];
+    for (leg, to, to_token_account, amount) in legs.iter() {
+        leg.transfer_out(
+            &ctx.accounts.escrow_vault,
+            to,
+            *to_token_account,
+            *amount,
+            &ctx.accounts.system_program,
+            vault_signer,
+        )?;
+    }
+    
+    //update escrow state
+    let payer_refund = escrow.payer.amount_paid;
+    let receiver_refund = escrow.receiver.amount_paid;
+    let escrow = &mut ctx.accounts.escrow;
+    mark_expired_sym(
+        escrow,
+        ctx.accounts.signer.key(),
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+    escrow.payer.amount_refunded = payer_refund;
+    escrow.receiver.amount_refunded = receiver_refund;
+    if let Some(old) = escrow.set_status(EscrowStatus::Completed) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Completed,
+            actor: ctx.accounts.signer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    
+    log_escrow_action(log_actions::REFUND, &escrow.id, &[
+        ("payer_amount", payer_refund),
+        ("receiver_amount", receiver_refund),
+    ]);
+    emit_cpi!(SymEscrowRefundedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        payer_amount: payer_refund,
+        receiver_amount: receiver_refund,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//accounts holding one side of a symmetric escrow
+struct SymLeg<'a, 'info> {
+    currency_type: CurrencyType,
+    vault_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+}
+
+impl<'a, 'info> SymLeg<'a, 'info> {
+    //move part of the deposit out of the vault, in lamports or tokens depending on the currency
+    fn transfer_out(
+        &self,
+        escrow_vault: &SystemAccount<'info>,
+        to: &AccountInfo<'info>,
+        to_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+        system_program: &Program<'info, System>,
+        vault_signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        match self.currency_type {
+            CurrencyType::Native => transfer_native_sol_signed(
+                escrow_vault.to_account_info(),
+                to.clone(),
+                amount,
+                system_program.to_account_info(),
+                vault_signer,
+            ),
+            _ => transfer_spl_tokens_signed(
+                self.vault_token_account.ok_or(EscrowError::InvalidToken)?,
+                to_token_account.ok_or(EscrowError::InvalidToken)?,
+                self.mint.ok_or(EscrowError::InvalidToken)?,
+                escrow_vault.to_account_info(),
+                amount,
+                self.token_program.ok_or(EscrowError::InvalidToken)?,
+                vault_signer,
+                &[],
+            ),
+        }
+    }
+
+    //amount that arrives at the destination, net of any token transfer fee
+    fn delivered(&self, amount: u64) -> Result<u64> {
+        match self.mint {
+            Some(mint) if self.currency_type != CurrencyType::Native => amount
+                .checked_sub(calculate_transfer_fee(mint, amount)?)
+                .ok_or(error!(EscrowError::ArithmeticOverflow)),
+            _ => Ok(amount),
+        }
+    }
+}
+
+//helper function to get the currency type of a currency address
+fn currency_type_of(currency: &Pubkey) -> CurrencyType {
+    if *currency == Pubkey::default() {
+        CurrencyType::Native
+    } else {
+        CurrencyType::SplToken
+    }
+}
+
+//helper function to swap both deposits, charging the protocol fee on each leg
+fn execute_swap(ctx: Context<ReleaseEscrowSym>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    
+    //calculate fees and amounts for both legs
+    let (payer_fee, payer_leg) = calculate_fee_and_amount(escrow.payer.amount_paid, escrow.fee_bps)?;
+    let (receiver_fee, receiver_leg) = calculate_fee_and_amount(escrow.receiver.amount_paid, escrow.fee_bps)?;
+    
+    //generate signer seeds for escrow vault
+    let escrow_key = escrow.key();
+    let vault_seeds = &[
+        seeds::ESCROW_VAULT,
+        escrow_key.as_ref(),
+        &[ctx.bumps.escrow_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    let payer_side = SymLeg {
+        currency_type: escrow.payer.currency_type,
+        vault_token_account: ctx.accounts.payer_vault_token_account.as_ref(),
+        mint: ctx.accounts.payer_mint.as_ref(),
+        token_program: ctx.accounts.payer_token_program.as_ref(),
+    };
+    let receiver_side = SymLeg {
+        currency_type: escrow.receiver.currency_type,
+        vault_token_account: ctx.accounts.receiver_vault_token_account.as_ref(),
+        mint: ctx.accounts.receiver_mint.as_ref(),
+        token_program: ctx.accounts.receiver_token_program.as_ref(),
+    };
+    let fee_vault = ctx.accounts.fee_vault.to_account_info();
+    
+    //first party's deposit goes to the second party, and vice versa
+    let legs = [
+        (
+            &payer_side,
+            ctx.accounts.receiver.to_account_info(),
+            ctx.accounts.receiver_token_account.as_ref(),
+            ctx.accounts.payer_fee_token_account.as_ref(),
+            payer_leg,
+            payer_fee,
+        ),
+        (
+            &receiver_side,
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.payer_token_account.as_ref(),
+            ctx.accounts.receiver_fee_token_account.as_ref(),
+            receiver_leg,
+            receiver_fee,
+        ),
+    ];
+    for (side, to, to_token_account, fee_token_account, amount, fee) in legs.iter() {
+        side.transfer_out(
+            &ctx.accounts.escrow_vault,
+            to,
+            *to_token_account,
+            *amount,
+            &ctx.accounts.system_program,
+            vault_signer,
+        )?;
+        side.transfer_out(
+            &ctx.accounts.escrow_vault,
+            &fee_vault,
+            *fee_token_account,
+            *fee,
+            &ctx.accounts.system_program,
+            vault_signer,
+        )?;
+    }
+    
+    let payer_delivered = payer_side.delivered(payer_leg)?;
+    let receiver_delivered = receiver_side.delivered(receiver_leg)?;
+    
+    //update escrow state
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.released = true;
+    escrow.payer.amount_released = payer_leg;
+    escrow.payer.amount_delivered = payer_delivered;
+    escrow.receiver.amount_released = receiver_leg;
+    escrow.receiver.amount_delivered = receiver_delivered;
+    if let Some(old) = escrow.set_status(EscrowStatus::Completed) {
+        emit_cpi!(EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: escrow.id,
+            seq: escrow.next_event_seq(),
+            escrow: escrow.key(),
+            old,
+            new: EscrowStatus::Completed,
+            actor: ctx.accounts.signer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        });
+    }
+    
+    log_escrow_action(log_actions::RELEASE, &escrow.id, &[
+        ("payer_amount", payer_leg),
+        ("receiver_amount", receiver_leg),
+    ]);
+    emit_cpi!(SymEscrowSwappedEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        payer_amount: payer_leg,
+        payer_fee,
+        receiver_amount: receiver_leg,
+        receiver_fee,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct SymEscrowCreatedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub creator: Pubkey,
+    pub payer: Pubkey,
+    pub payer_amount: u64,
+    pub receiver: Pubkey,
+    pub receiver_amount: u64,
+    pub payer_decimals: u8,
+    pub receiver_decimals: u8,
+    pub category: EscrowCategory,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct SymPaymentReceivedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub decimals: u8,
+    pub transfer_fee: u64,
+    pub fully_deposited: bool,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct SymEscrowSwappedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub payer_amount: u64,
+    pub payer_fee: u64,
+    pub receiver_amount: u64,
+    pub receiver_fee: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct SymEscrowRefundedEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub payer_amount: u64,
+    pub receiver_amount: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
@@ -1,16 +1,39 @@
+use std::fmt::Write;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::TransferFeeConfig,
+        transfer_hook::TransferHook,
+        BaseStateWithExtensions,
+        StateWithExtensions,
+    },
+};
+use anchor_spl::metadata::{
+    MetadataAccount,
+    mpl_token_metadata::{self, instructions::TransferV1CpiBuilder, types::TokenStandard},
+};
 use crate::state::*;
 use crate::errors::*;
 use crate::constants::*;
+use crate::instructions::asym_escrow::CreateAsymEscrowParams;
 
 /// Transfer native SOL
+/// 
+/// `from` may be a wallet or another program's PDA signing through `invoke_signed`; either way it must be
+/// a system-owned account without data, as the system program can't debit anything else.
 pub fn transfer_native_sol<'info>(
     from: AccountInfo<'info>,
     to: AccountInfo<'info>,
     amount: u64,
     system_program: AccountInfo<'info>,
 ) -> Result<()> {
+    require!(
+        *from.owner == anchor_lang::system_program::ID && from.data_is_empty(),
+        EscrowError::InvalidFundingAccount
+    );
+    
     let ix = anchor_lang::solana_program::system_instruction::transfer(
         from.key,
         to.key,
@@ -25,28 +48,273 @@ pub fn transfer_native_sol<'info>(
     Ok(())
 }
 
-/// Transfer SPL tokens
+/// Transfer SPL tokens (legacy SPL Token or Token-2022)
+/// 
+/// `remaining_accounts` carries the extra accounts of a Token-2022 transfer hook, if the mint has one.
 pub fn transfer_spl_tokens<'info>(
-    from: &Account<'info, TokenAccount>,
-    to: &Account<'info, TokenAccount>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &impl ToAccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
     authority: &Signer<'info>,
     amount: u64,
-    token_program: &Program<'info, Token>,
+    token_program: &Interface<'info, TokenInterface>,
+    remaining_accounts: &[AccountInfo<'info>],
 ) -> Result<()> {
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: from.to_account_info(),
+        mint: mint.to_account_info(),
         to: to.to_account_info(),
         authority: authority.to_account_info(),
     };
     
     let cpi_program = token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+        .with_remaining_accounts(remaining_accounts.to_vec());
+    
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    
+    Ok(())
+}
+
+/// Transfer native SOL out of a PDA vault
+pub fn transfer_native_sol_signed<'info>(
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    system_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = anchor_lang::system_program::Transfer { from, to };
+    let cpi_ctx = CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds);
+    
+    anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+    
+    Ok(())
+}
+
+/// Rent-exempt minimum of a native escrow vault (a system account without data)
+/// 
+/// The first native deposit funds it on top of the payment. Payouts leave it in the vault, so partial 
+/// settlements never drop the vault into a rent-paying state, and it goes back to the payer when the 
+/// completed escrow is garbage collected.
+pub fn vault_rent_reserve() -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(0))
+}
+
+/// Transfer native SOL out of an escrow vault, keeping its rent reserve
+/// 
+/// Vaults funded before the reserve was introduced have none, and may still be emptied outright.
+pub fn transfer_from_native_vault<'info>(
+    vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    system_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    native_vault_remaining(vault.lamports(), amount, vault_rent_reserve()?)?;
+    
+    transfer_native_sol_signed(vault, to, amount, system_program, signer_seeds)
+}
+
+/// Lamports left in a native vault after paying out `amount`, which must empty it or keep `reserve`
+pub fn native_vault_remaining(balance: u64, amount: u64, reserve: u64) -> Result<u64> {
+    let remaining = balance
+        .checked_sub(amount)
+        .ok_or(EscrowError::InsufficientFunds)?;
+    require!(
+        remaining == 0 || remaining >= reserve,
+        EscrowError::InsufficientFunds
+    );
+    
+    Ok(remaining)
+}
+
+/// Transfer SPL tokens out of a PDA-owned token account (legacy SPL Token or Token-2022)
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_spl_tokens_signed<'info>(
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: AccountInfo<'info>,
+    amount: u64,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority,
+    };
+    
+    let cpi_program = token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds)
+        .with_remaining_accounts(remaining_accounts.to_vec());
     
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
     
     Ok(())
 }
 
+/// Metaplex accounts for a programmable NFT transfer
+/// 
+/// Passed as remaining accounts, in order: metadata, master edition, source token record, destination 
+/// token record, token metadata program, instructions sysvar, associated token program, and optionally 
+/// the authorization rules program and rule set enforcing the pNFT's royalty rules.
+pub struct ProgrammableNftAccounts<'a, 'info> {
+    pub metadata: &'a AccountInfo<'info>,
+    pub edition: &'a AccountInfo<'info>,
+    pub token_record: &'a AccountInfo<'info>,
+    pub destination_token_record: &'a AccountInfo<'info>,
+    pub token_metadata_program: &'a AccountInfo<'info>,
+    pub sysvar_instructions: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub authorization_rules_program: Option<&'a AccountInfo<'info>>,
+    pub authorization_rules: Option<&'a AccountInfo<'info>>,
+}
+
+impl<'a, 'info> ProgrammableNftAccounts<'a, 'info> {
+    /// Read the Metaplex accounts from an instruction's remaining accounts
+    pub fn from_remaining_accounts(accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        require!(accounts.len() >= 7, EscrowError::MissingProgrammableNftAccounts);
+        require!(
+            accounts[4].key() == mpl_token_metadata::ID
+                && accounts[5].key() == anchor_lang::solana_program::sysvar::instructions::ID
+                && accounts[6].key() == anchor_spl::associated_token::ID,
+            EscrowError::MissingProgrammableNftAccounts
+        );
+        
+        Ok(Self {
+            metadata: &accounts[0],
+            edition: &accounts[1],
+            token_record: &accounts[2],
+            destination_token_record: &accounts[3],
+            token_metadata_program: &accounts[4],
+            sysvar_instructions: &accounts[5],
+            associated_token_program: &accounts[6],
+            authorization_rules_program: accounts.get(7),
+            authorization_rules: accounts.get(8),
+        })
+    }
+}
+
+/// Transfer a programmable NFT through Metaplex, which enforces its rule set (including royalty enforcement)
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_programmable_nft<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    to_owner: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    pnft: &ProgrammableNftAccounts<'_, 'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    TransferV1CpiBuilder::new(pnft.token_metadata_program)
+        .token(from)
+        .token_owner(authority)
+        .destination_token(to)
+        .destination_owner(to_owner)
+        .mint(mint)
+        .metadata(pnft.metadata)
+        .edition(Some(pnft.edition))
+        .token_record(Some(pnft.token_record))
+        .destination_token_record(Some(pnft.destination_token_record))
+        .authority(authority)
+        .payer(payer)
+        .system_program(system_program)
+        .sysvar_instructions(pnft.sysvar_instructions)
+        .spl_token_program(token_program)
+        .spl_ata_program(pnft.associated_token_program)
+        .authorization_rules_program(pnft.authorization_rules_program)
+        .authorization_rules(pnft.authorization_rules)
+        .amount(1)
+        .invoke_signed(signer_seeds)?;
+    
+    Ok(())
+}
+
+/// Calculate the Token-2022 transfer fee withheld when moving an amount of the given mint (0 for mints without the extension)
+pub fn calculate_transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(error!(EscrowError::ArithmeticOverflow)),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Get the transfer hook program of a Token-2022 mint, if it has one
+pub fn get_transfer_hook_program(mint: &InterfaceAccount<Mint>) -> Result<Option<Pubkey>> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferHook>() {
+        Ok(transfer_hook) => Ok(Option::<Pubkey>::from(transfer_hook.program_id)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Validate that a mint's transfer hook program is allowlisted and its extra accounts were supplied
+pub fn validate_transfer_hook(
+    mint: &InterfaceAccount<Mint>,
+    program_config: &ProgramConfig,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if let Some(hook_program) = get_transfer_hook_program(mint)? {
+        require!(
+            program_config.transfer_hook_allowlist.contains(&hook_program),
+            EscrowError::TransferHookNotAllowed
+        );
+        require!(
+            remaining_accounts.iter().any(|account| account.key() == hook_program),
+            EscrowError::MissingTransferHookAccounts
+        );
+    }
+    Ok(())
+}
+
+/// Move lamports out of a program-owned account
+pub fn transfer_lamports_from_program_account<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let from_balance = from.lamports()
+        .checked_sub(amount)
+        .ok_or(EscrowError::InsufficientFunds)?;
+    let to_balance = to.lamports()
+        .checked_add(amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    
+    Ok(())
+}
+
+/// Generate an escrow ID from its creator and nonce
+pub fn generate_escrow_id(creator: &Pubkey, nonce: u64) -> [u8; 32] {
+    let mut hasher = anchor_lang::solana_program::hash::Hasher::default();
+    hasher.hash(creator.as_ref());
+    hasher.hash(&nonce.to_le_bytes());
+    hasher.result().to_bytes()
+}
+
 /// Validate escrow timing
 pub fn validate_escrow_dates(start_time: i64, end_time: i64) -> Result<()> {
     if end_time > 0 {
@@ -59,12 +327,180 @@ pub fn validate_escrow_dates(start_time: i64, end_time: i64) -> Result<()> {
     Ok(())
 }
 
+/// Validate escrow arbitration terms
+pub fn validate_arbitration_terms(params: &CreateAsymEscrowParams) -> Result<()> {
+    if params.arbiter != Pubkey::default() {
+        require!(
+            params.arbiter != params.payer && params.arbiter != params.receiver,
+            EscrowError::InvalidArbiter
+        );
+    }
+    if params.appeal_arbiter != Pubkey::default() {
+        require!(
+            (params.arbiter != Pubkey::default() || params.arbiter_kind == ArbiterKind::Jury)
+                && params.appeal_arbiter != params.arbiter
+                && params.appeal_arbiter != params.payer
+                && params.appeal_arbiter != params.receiver,
+            EscrowError::InvalidArbiter
+        );
+    }
+    if params.automated_settler != Pubkey::default() {
+        require!(
+            params.automated_settler != params.payer && params.automated_settler != params.receiver,
+            EscrowError::InvalidArbiter
+        );
+    }
+    match params.arbiter_kind {
+        ArbiterKind::Individual => {},
+        ArbiterKind::Panel => return err!(EscrowError::InvalidPanel),
+        //juries are drawn at dispute time and are not paid an arbitration fee
+        ArbiterKind::Jury => require!(
+            params.arbiter == Pubkey::default() && params.arbitration_fee.value == 0,
+            EscrowError::InvalidArbiter
+        ),
+        ArbiterKind::Governance => require!(
+            params.arbiter != Pubkey::default(),
+            EscrowError::InvalidArbiter
+        ),
+    }
+    //fees in a separate token can't be a share of the escrowed asset
+    if params.arbitration_fee_mint != Pubkey::default() {
+        require!(
+            params.arbitration_fee.fee_type == ArbitrationFeeType::Flat
+                && params.arbitration_fee.value > 0,
+            EscrowError::InvalidArbitrationFee
+        );
+    }
+    if params.arbitration_fee.fee_type == ArbitrationFeeType::Bps {
+        require!(
+            params.arbitration_fee.value <= BPS_DENOMINATOR,
+            EscrowError::InvalidArbitrationFee
+        );
+    }
+    Ok(())
+}
+
+/// Validate a configurable dispute phase length
+pub fn validate_dispute_phase_window(window: i64) -> Result<()> {
+    require!(
+        window > 0 && window <= MAX_DISPUTE_PHASE_WINDOW,
+        EscrowError::InvalidConfig
+    );
+    Ok(())
+}
+
+/// Resolve an escrow's dispute window against the config bounds (0 = config maximum)
+pub fn resolve_dispute_window(dispute_window: i64, program_config: &ProgramConfig) -> Result<i64> {
+    if dispute_window == 0 {
+        return Ok(program_config.max_dispute_window);
+    }
+    require!(
+        dispute_window >= program_config.min_dispute_window
+            && dispute_window <= program_config.max_dispute_window,
+        EscrowError::InvalidDisputeWindow
+    );
+    Ok(dispute_window)
+}
+
+/// Compute the commitment to an escrow's hidden agreed amount, bound to the escrow so it can't be replayed
+pub fn compute_amount_commitment(escrow: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[escrow.as_ref(), &amount.to_le_bytes(), salt]).to_bytes()
+}
+
+/// Validate an NFT's Metaplex metadata, optionally requiring a verified collection (Pubkey::default() = any)
+pub fn validate_nft_metadata(metadata: &MetadataAccount, collection: Pubkey) -> Result<()> {
+    //legacy NFTs may not have a token standard set
+    require!(
+        matches!(
+            metadata.token_standard,
+            None | Some(TokenStandard::NonFungible) | Some(TokenStandard::ProgrammableNonFungible)
+        ),
+        EscrowError::InvalidNft
+    );
+    if collection != Pubkey::default() {
+        require!(
+            metadata.collection
+                .as_ref()
+                .is_some_and(|c| c.verified && c.key == collection),
+            EscrowError::InvalidNftCollection
+        );
+    }
+    Ok(())
+}
+
+/// Get the account a receiver payout goes to: the vault of a chained escrow, the holder of a transferable 
+/// claim token, or the receiver
+/// 
+/// Returns Pubkey::default() if the escrow's claim is tokenized and no account holding the token is given.
+pub fn payout_recipient(escrow: &AsymEscrow, claim_token_account: Option<&InterfaceAccount<TokenAccount>>) -> Pubkey {
+    if escrow.is_chained() {
+        return Pubkey::find_program_address(
+            &[seeds::ESCROW_VAULT, escrow.chained_escrow.as_ref()],
+            &crate::ID,
+        ).0;
+    }
+    if !escrow.is_claim_tokenized() {
+        return escrow.receiver.addr;
+    }
+    match claim_token_account {
+        Some(account) if account.mint == escrow.receipt_mint && account.amount == 1 => account.owner,
+        _ => Pubkey::default(),
+    }
+}
+
 /// Check if escrow is not paused
 pub fn require_not_paused(program_config: &ProgramConfig) -> Result<()> {
     require!(!program_config.paused, EscrowError::ProgramPaused);
     Ok(())
 }
 
+/// Record an admin action in the audit trail and advance the program config's admin action counter
+/// 
+/// The entry must be the PDA at the counter's current index; `params` are hashed in order.
+pub fn record_admin_action(
+    entry: &mut AdminAuditEntry,
+    bump: u8,
+    program_config: &mut ProgramConfig,
+    actor: Pubkey,
+    action: AdminActionKind,
+    params: &[&[u8]],
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    entry.index = program_config.next_admin_action_index();
+    entry.actor = actor;
+    entry.action = action;
+    entry.params_hash = anchor_lang::solana_program::hash::hashv(params).to_bytes();
+    entry.slot = clock.slot;
+    entry.timestamp = clock.unix_timestamp;
+    entry.bump = bump;
+
+    Ok(())
+}
+
+/// Make a call into an external program (hook, adapter) with the escrow locked against re-entry
+/// 
+/// The in-progress flag is written to the escrow account before the call, so a callee calling back into 
+/// the program sees it and cannot release or refund mid-settlement. The flag is cleared once the call returns.
+pub fn guarded_external_call<'info, T>(
+    escrow: &mut Account<'info, AsymEscrow>,
+    call: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    require!(!escrow.external_call_in_progress, EscrowError::ReentrantCall);
+    escrow.external_call_in_progress = true;
+    escrow.exit(&crate::ID)?;
+    
+    let result = call()?;
+    
+    escrow.external_call_in_progress = false;
+    Ok(result)
+}
+
+/// Native treasury PDA of a Realms governance, which signs for the governance when proposals execute
+pub fn realms_native_treasury(governance: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"native-treasury", governance.as_ref()], &SPL_GOVERNANCE_PROGRAM_ID).0
+}
+
 /// Calculate fee and remaining amount
 pub fn calculate_fee_and_amount(amount: u64, fee_bps: u16) -> Result<(u64, u64)> {
     if fee_bps == 0 {
@@ -86,4 +522,165 @@ pub fn calculate_fee_and_amount(amount: u64, fee_bps: u16) -> Result<(u64, u64)>
         .ok_or(EscrowError::ArithmeticOverflow)?;
     
     Ok((fee, amount_to_pay))
+}
+
+/// Split a release into the protocol fee taken and the amount paid out to the receiver
+/// 
+/// Consent releases, arbitration awards and release quotes all take the fee from this split, in every 
+/// currency.
+pub fn release_fee_and_payout(escrow: &AsymEscrow, amount: u64) -> Result<(u64, u64)> {
+    calculate_fee_and_amount(amount, escrow.fee_bps)
+}
+
+/// Share of a protocol fee diverted to the insurance fund
+/// 
+/// The fund holds lamports only, so it takes its share of native fees; token fees go to the fee vault whole.
+pub fn insurance_fee_share(escrow: &AsymEscrow, protocol_fee: u64) -> Result<u64> {
+    if escrow.payer.currency_type != CurrencyType::Native {
+        return Ok(0);
+    }
+    Ok(calculate_fee_and_amount(protocol_fee, escrow.fee_terms.insurance_fee_bps)?.0)
+}
+
+/// Format an escrow log line: `solescrow:<action> escrow=<hex id> <key>=<value>...`
+pub fn format_escrow_log(action: &str, escrow_id: &[u8; 32], fields: &[(&str, u64)]) -> String {
+    let mut line = format!("{}:{} escrow=", LOG_PREFIX, action);
+    for byte in escrow_id {
+        let _ = write!(line, "{:02x}", byte);
+    }
+    for (key, value) in fields {
+        let _ = write!(line, " {}={}", key, value);
+    }
+    line
+}
+
+/// Log an escrow action in the stable format of `format_escrow_log`
+pub fn log_escrow_action(action: &str, escrow_id: &[u8; 32], fields: &[(&str, u64)]) {
+    msg!("{}", format_escrow_log(action, escrow_id, fields));
+}
+
+/// Record a consent-based completion on the parties' reputations
+pub fn record_settlement<'info>(
+    payer_reputation: Option<&mut Account<'info, Reputation>>,
+    receiver_reputation: Option<&mut Account<'info, Reputation>>,
+    volume: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    for reputation in [payer_reputation, receiver_reputation].into_iter().flatten() {
+        reputation.record_settlement(volume, now);
+    }
+    Ok(())
+}
+
+/// Draw jurors from the pool, weighted by stake and without replacement
+pub fn select_weighted(entries: &[ArbiterPoolEntry], seed: [u8; 32], count: usize) -> Vec<Pubkey> {
+    let mut candidates: Vec<&ArbiterPoolEntry> = entries.iter().filter(|e| e.stake > 0).collect();
+    let mut selected = Vec::with_capacity(count);
+    
+    for round in 0..count {
+        let total: u128 = candidates.iter().map(|e| e.stake as u128).sum();
+        if total == 0 {
+            break;
+        }
+        
+        //derive a fresh random value for each round
+        let hash = anchor_lang::solana_program::hash::hashv(&[&seed, &(round as u64).to_le_bytes()]);
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&hash.to_bytes()[..16]);
+        let mut target = u128::from_le_bytes(bytes) % total;
+        
+        let mut index = 0;
+        for (i, candidate) in candidates.iter().enumerate() {
+            if target < candidate.stake as u128 {
+                index = i;
+                break;
+            }
+            target -= candidate.stake as u128;
+        }
+        selected.push(candidates.remove(index).arbiter);
+    }
+    
+    selected
+}
+
+/// Split a fee-token arbitration fee between the parties: the losing party pays it all, an even split halves it
+pub fn fee_token_shares(fee: u64, award_against: Option<DisputeParty>) -> (u64, u64) {
+    match award_against {
+        Some(DisputeParty::Payer) => (fee, 0),
+        Some(DisputeParty::Receiver) => (0, fee),
+        None => (fee / 2, fee - fee / 2),
+    }
+}
+
+/// Charge an arbitration fee against the award shares
+/// 
+/// The fee is taken from the share of the party the award goes against (the one receiving less); 
+/// if that share can't cover it, the rest comes out of the other share. An even split splits the fee.
+/// Returns the net payer share, net receiver share, and the party the award went against.
+pub fn split_arbitration_fee(
+    payer_amount: u64,
+    receiver_amount: u64,
+    fee: u64,
+) -> Result<(u64, u64, Option<DisputeParty>)> {
+    let total = payer_amount
+        .checked_add(receiver_amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    require!(fee <= total, EscrowError::InvalidArbitrationFee);
+    
+    let (payer_fee, award_against) = if payer_amount < receiver_amount {
+        (fee.min(payer_amount), Some(DisputeParty::Payer))
+    } else if receiver_amount < payer_amount {
+        (fee.saturating_sub(receiver_amount), Some(DisputeParty::Receiver))
+    } else {
+        (fee / 2, None)
+    };
+    let receiver_fee = fee
+        .checked_sub(payer_fee)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    let payer_net = payer_amount
+        .checked_sub(payer_fee)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    let receiver_net = receiver_amount
+        .checked_sub(receiver_fee)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    Ok((payer_net, receiver_net, award_against))
+}
+
+/// Emitter for helpers that raise events outside a handler
+/// 
+/// `emit_cpi!` needs `ctx` in scope; helpers shared across instructions take an emitter built from the 
+/// `#[event_cpi]` accounts instead, and emit through the same self-CPI.
+pub struct EventEmitter<'a, 'info> {
+    event_authority: &'a AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'a, 'info> EventEmitter<'a, 'info> {
+    pub fn new(event_authority: &'a AccountInfo<'info>, bump: u8) -> Self {
+        Self { event_authority, bump }
+    }
+    
+    /// Emit an event as self-CPI instruction data, so it can't be lost to log truncation
+    pub fn emit<E: anchor_lang::Event>(&self, event: E) -> Result<()> {
+        let ix_data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .iter()
+            .copied()
+            .chain(event.data())
+            .collect();
+        let ix = anchor_lang::solana_program::instruction::Instruction::new_with_bytes(
+            crate::ID,
+            &ix_data,
+            vec![AccountMeta::new_readonly(*self.event_authority.key, true)],
+        );
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            std::slice::from_ref(self.event_authority),
+            &[&[seeds::EVENT_AUTHORITY, &[self.bump]]],
+        )?;
+        
+        Ok(())
+    }
 }
\ No newline at end of file
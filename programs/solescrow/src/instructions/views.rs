@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::utils::*;
+
+/// Derived view of an escrow, returned by `get_escrow_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EscrowSummary {
+    pub escrow_id: [u8; 32],
+    pub status: EscrowStatus,
+    pub amount_remaining: u64, // held in escrow, net of releases, refunds and arbitration fees
+    pub amount_outstanding: u64, // still owed by the payer (0 when the terms are hidden)
+    pub fee_bps: u16,
+    pub release_fee: u64, // fee on releasing the remaining amount
+    pub release_payout: u64, // remaining amount net of the fee
+    pub seconds_remaining: Option<i64>, // until the end time (None if the escrow has none)
+    pub expired: bool,
+    pub payer_consented: bool,
+    pub receiver_consented: bool,
+    pub release_held: bool, // whether a guardian hold blocks releasing the remaining amount
+    pub dispute_window_open: bool,
+    pub timestamp: i64, // time the summary was computed at
+}
+
+/// Summarize an escrow into the return data (read-only, meant to be simulated)
+#[derive(Accounts)]
+pub struct GetEscrowSummaryAsym<'info> {
+    pub escrow: Account<'info, AsymEscrow>,
+}
+
+pub fn get_escrow_summary(ctx: Context<GetEscrowSummaryAsym>) -> Result<EscrowSummary> {
+    let escrow = &ctx.accounts.escrow;
+    let now = Clock::get()?.unix_timestamp;
+
+    let amount_remaining = escrow.get_amount_remaining();
+    let (release_fee, release_payout) = release_fee_and_payout(escrow, amount_remaining)?;
+    let amount_outstanding = if escrow.has_hidden_terms() {
+        0
+    } else {
+        escrow.payer.amount.saturating_sub(escrow.payer.amount_paid)
+    };
+
+    Ok(EscrowSummary {
+        escrow_id: escrow.id,
+        status: escrow.status,
+        amount_remaining,
+        amount_outstanding,
+        fee_bps: escrow.fee_bps,
+        release_fee,
+        release_payout,
+        seconds_remaining: (escrow.end_time > 0).then(|| escrow.end_time.saturating_sub(now).max(0)),
+        expired: escrow.is_expired(now),
+        payer_consented: escrow.payer.released,
+        receiver_consented: escrow.receiver.released,
+        release_held: escrow.is_release_held(amount_remaining, now),
+        dispute_window_open: escrow.is_dispute_window_open(now),
+        timestamp: now,
+    })
+}
+
+/// Settlement preview returned by `quote_release`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReleaseQuote {
+    pub amount: u64,
+    pub fee: u64, // protocol fee taken from the release
+    pub payout: u64, // amount sent to the receiver
+    pub transfer_fee: u64, // token transfer fee withheld from the payout
+    pub amount_delivered: u64, // amount the receiver ends up with
+    pub platform_share: u64, // part of the fee attributed to the escrow's platform
+    pub release_held: bool, // whether a guardian hold blocks the release
+}
+
+/// Preview the fee and payout of releasing an amount (read-only, meant to be simulated)
+#[derive(Accounts)]
+pub struct QuoteReleaseAsym<'info> {
+    pub escrow: Account<'info, AsymEscrow>,
+
+    /// Token mint (only required for SPL token escrows, to account for transfer fees)
+    #[account(
+        address = escrow.payer.currency @ EscrowError::InvalidToken
+    )]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+}
+
+pub fn quote_release(ctx: Context<QuoteReleaseAsym>, amount: u64) -> Result<ReleaseQuote> {
+    let escrow = &ctx.accounts.escrow;
+    require!(amount <= escrow.get_amount_remaining(), EscrowError::InsufficientFunds);
+
+    //same split as the release itself
+    let (fee, payout) = release_fee_and_payout(escrow, amount)?;
+    let transfer_fee = match ctx.accounts.token_mint.as_ref() {
+        Some(token_mint) if matches!(escrow.payer.currency_type, CurrencyType::SplToken | CurrencyType::Nft) => {
+            calculate_transfer_fee(token_mint, payout)?
+        },
+        _ => 0,
+    };
+    let platform_share = calculate_fee_and_amount(fee, escrow.fee_terms.platform_fee_share_bps)?.0;
+
+    Ok(ReleaseQuote {
+        amount,
+        fee,
+        payout,
+        transfer_fee,
+        amount_delivered: payout.checked_sub(transfer_fee).ok_or(EscrowError::ArithmeticOverflow)?,
+        platform_share,
+        release_held: escrow.is_release_held(amount, Clock::get()?.unix_timestamp),
+    })
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::instructions::utils::*;
+
+/// Message posted by the Wormhole core bridge once a VAA's guardian signatures are verified
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostedVaa {
+    /// Wormhole chain id of the emitting chain
+    pub emitter_chain: u16,
+    /// Emitter address on that chain, left-padded to 32 bytes
+    pub emitter_address: [u8; 32],
+    /// Sequence number of the message from the emitter
+    pub sequence: u64,
+    /// Application payload
+    pub payload: Vec<u8>,
+}
+
+//layout of a core bridge PostedVAA account
+const POSTED_VAA_MAGIC: &[u8; 3] = b"vaa";
+const POSTED_VAA_HEADER_LEN: usize = 95;
+
+/// Read a posted VAA, checking it was written by the Wormhole core bridge
+pub fn load_posted_vaa(posted_vaa: &AccountInfo) -> Result<PostedVaa> {
+    require_keys_eq!(*posted_vaa.owner, WORMHOLE_PROGRAM_ID, EscrowError::InvalidVaa);
+    
+    let data = posted_vaa.try_borrow_data()?;
+    require!(
+        data.len() >= POSTED_VAA_HEADER_LEN && &data[..3] == POSTED_VAA_MAGIC,
+        EscrowError::InvalidVaa
+    );
+    
+    //header: magic, version, consistency level, timestamps, signature set, nonce; message fields from 49
+    let sequence = u64::from_le_bytes(data[49..57].try_into().unwrap());
+    let emitter_chain = u16::from_le_bytes(data[57..59].try_into().unwrap());
+    let emitter_address: [u8; 32] = data[59..91].try_into().unwrap();
+    let payload_len = u32::from_le_bytes(data[91..95].try_into().unwrap()) as usize;
+    let payload = data
+        .get(POSTED_VAA_HEADER_LEN..POSTED_VAA_HEADER_LEN + payload_len)
+        .ok_or(EscrowError::InvalidVaa)?
+        .to_vec();
+    
+    Ok(PostedVaa {
+        emitter_chain,
+        emitter_address,
+        sequence,
+        payload,
+    })
+}
+
+/// Check a posted VAA comes from the escrow's release emitter and names the escrow
+/// 
+/// The payload must start with the escrow account's address; anything after it is ignored.
+pub fn validate_release_vaa(escrow: &Account<AsymEscrow>, vaa: &PostedVaa) -> Result<()> {
+    require!(
+        vaa.emitter_chain == escrow.wormhole_emitter_chain
+            && vaa.emitter_address == escrow.wormhole_emitter,
+        EscrowError::InvalidVaa
+    );
+    require!(
+        vaa.payload.len() >= 32 && vaa.payload[..32] == escrow.key().to_bytes(),
+        EscrowError::InvalidVaa
+    );
+    Ok(())
+}
+
+/// Set the cross-chain emitter whose Wormhole message releases the escrow (both parties must sign)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetWormholeTriggerAsym<'info> {
+    pub payer: Signer<'info>,
+    
+    pub receiver: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = escrow.payer.addr == payer.key() @ EscrowError::InvalidPayer,
+        constraint = escrow.receiver.addr == receiver.key() @ EscrowError::InvalidReceiver,
+        constraint = escrow.status != EscrowStatus::Completed @ EscrowError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, AsymEscrow>,
+    
+    #[account(
+        seeds = [ProgramConfig::SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+pub fn set_wormhole_trigger(
+    ctx: Context<SetWormholeTriggerAsym>,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_config)?;
+    
+    //chain id 0 is unset in Wormhole
+    require!(emitter_chain != 0 && emitter_address != [0u8; 32], EscrowError::InvalidVaa);
+    
+    //a USD-denominated obligation needs a price at settlement, which the message doesn't carry
+    let escrow = &mut ctx.accounts.escrow;
+    require!(!escrow.is_usd_denominated(), EscrowError::InvalidEscrowState);
+    require!(!escrow.released, EscrowError::AlreadyReleased);
+    escrow.wormhole_emitter_chain = emitter_chain;
+    escrow.wormhole_emitter = emitter_address;
+    
+    emit_cpi!(WormholeTriggerSetEvent {
+        version: EVENT_VERSION,
+        escrow_id: escrow.id,
+        seq: escrow.next_event_seq(),
+        escrow: escrow.key(),
+        emitter_chain,
+        emitter_address,
+        reserved: [0; EVENT_RESERVED_BYTES],
+    });
+    
+    Ok(())
+}
+
+//events
+#[event]
+pub struct WormholeTriggerSetEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
+
+#[event]
+pub struct WormholeReleaseEvent {
+    pub version: u8,
+    pub escrow_id: [u8; 32],
+    pub seq: u64,
+    pub escrow: Pubkey,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub reserved: [u8; EVENT_RESERVED_BYTES],
+}
\ No newline at end of file
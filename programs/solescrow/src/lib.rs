@@ -6,8 +6,12 @@ pub mod state;
 pub mod instructions;
 pub mod errors;
 pub mod constants;
+pub mod evm_compat;
+#[cfg(feature = "cpi")]
+pub mod cpi_helpers;
 
 use instructions::*;
+use state::*;
 
 #[program]
 pub mod escrow {
@@ -18,23 +22,505 @@ pub mod escrow {
         instructions::initialize::initialize_program(ctx, params)
     }
 
+    pub fn update_program_config(ctx: Context<UpdateProgramConfig>, params: UpdateProgramConfigParams) -> Result<()> {
+        instructions::initialize::update_program_config(ctx, params)
+    }
+
+    pub fn set_program_authority(ctx: Context<SetProgramAuthority>) -> Result<()> {
+        instructions::initialize::set_program_authority(ctx)
+    }
+
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        instructions::lookup_table::create_lookup_table(ctx, recent_slot)
+    }
+
+    pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+        instructions::lookup_table::extend_lookup_table(ctx, addresses)
+    }
+
     //asymmetric escrow instructions
     //TODO: rename to create_escrow_asym
     pub fn create_asym_escrow(ctx: Context<CreateAsymEscrow>, params: CreateAsymEscrowParams) -> Result<()> {
         instructions::asym_escrow::create_escrow(ctx, params)
     }
 
+    pub fn create_usd_escrow_asym(ctx: Context<CreateUsdEscrowAsym>, params: CreateAsymEscrowParams, usd_params: UsdEscrowParams) -> Result<()> {
+        instructions::asym_escrow::create_usd_escrow(ctx, params, usd_params)
+    }
+
+    pub fn create_multi_mint_escrow_asym<'info>(ctx: Context<'_, '_, 'info, 'info, CreateMultiMintEscrowAsym<'info>>, params: CreateAsymEscrowParams, accepted_mints: Vec<AcceptedMint>) -> Result<()> {
+        instructions::asym_escrow::create_multi_mint_escrow(ctx, params, accepted_mints)
+    }
+
+    pub fn create_htlc_escrow_asym(ctx: Context<CreateAsymEscrow>, params: CreateAsymEscrowParams, hashlock: [u8; 32], timelock: i64) -> Result<()> {
+        instructions::asym_escrow::create_htlc_escrow(ctx, params, hashlock, timelock)
+    }
+
+    pub fn create_attested_escrow_asym(ctx: Context<CreateAttestedEscrowAsym>, params: CreateAsymEscrowParams, requirement: AttestationRequirement) -> Result<()> {
+        instructions::asym_escrow::create_attested_escrow(ctx, params, requirement)
+    }
+
+    pub fn create_escrow_for_asym(ctx: Context<CreateEscrowForAsym>, params: CreateAsymEscrowParams) -> Result<()> {
+        instructions::asym_escrow::create_escrow_for(ctx, params)
+    }
+
+    pub fn create_escrow_via_partner(ctx: Context<CreateEscrowViaPartnerAsym>, params: CreateAsymEscrowParams) -> Result<()> {
+        instructions::asym_escrow::create_escrow_via_partner(ctx, params)
+    }
+
+    pub fn create_nft_escrow_asym(ctx: Context<CreateNftEscrowAsym>, params: CreateAsymEscrowParams, collection: Pubkey) -> Result<()> {
+        instructions::asym_escrow::create_nft_escrow(ctx, params, collection)
+    }
+
+    pub fn create_receipt_escrow_asym(ctx: Context<CreateReceiptEscrowAsym>, params: CreateAsymEscrowParams, uri: String, transferable: bool) -> Result<()> {
+        instructions::asym_escrow::create_receipt_escrow(ctx, params, uri, transferable)
+    }
+
+    pub fn deposit_nft_asym<'info>(ctx: Context<'_, '_, '_, 'info, DepositNftAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::deposit_nft(ctx)
+    }
+
+    pub fn deposit_programmable_nft_asym<'info>(ctx: Context<'_, '_, '_, 'info, DepositProgrammableNftAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::deposit_programmable_nft(ctx)
+    }
+
     pub fn place_payment_asym(ctx: Context<PlacePaymentAsym>, amount: u64) -> Result<()> {
         instructions::asym_escrow::place_payment(ctx, amount)
     }
 
-    pub fn release_escrow_asym(ctx: Context<ReleaseEscrowAsym>) -> Result<()> {
+    pub fn place_token_payment_asym<'info>(ctx: Context<'_, '_, '_, 'info, PlaceTokenPaymentAsym<'info>>, amount: u64) -> Result<()> {
+        instructions::asym_escrow::place_token_payment(ctx, amount)
+    }
+
+    pub fn release_escrow_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>) -> Result<()> {
         instructions::asym_escrow::release_escrow(ctx)
     }
 
-    pub fn refund_escrow_asym(ctx: Context<RefundEscrowAsym>, amount: u64) -> Result<()> {
+    pub fn release_token_escrow_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::release_token_escrow(ctx)
+    }
+
+    pub fn refund_escrow_asym<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>, amount: u64) -> Result<()> {
         instructions::asym_escrow::refund_escrow(ctx, amount)
     }
+
+    pub fn claim_htlc_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>, preimage: [u8; 32]) -> Result<()> {
+        instructions::asym_escrow::claim_htlc(ctx, preimage)
+    }
+
+    pub fn claim_token_htlc_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>, preimage: [u8; 32]) -> Result<()> {
+        instructions::asym_escrow::claim_token_htlc(ctx, preimage)
+    }
+
+    pub fn release_on_vaa_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::release_on_vaa(ctx)
+    }
+
+    pub fn release_token_on_vaa_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::release_token_on_vaa(ctx)
+    }
+
+    pub fn reclaim_htlc_asym<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::reclaim_htlc(ctx)
+    }
+
+    //symmetric escrow instructions
+    pub fn create_escrow_sym(ctx: Context<CreateSymEscrow>, params: CreateSymEscrowParams) -> Result<()> {
+        instructions::sym_escrow::create_escrow_sym(ctx, params)
+    }
+
+    pub fn place_payment_sym(ctx: Context<PlacePaymentSym>) -> Result<()> {
+        instructions::sym_escrow::place_payment_sym(ctx)
+    }
+
+    pub fn place_native_payment_sym(ctx: Context<PlaceNativePaymentSym>) -> Result<()> {
+        instructions::sym_escrow::place_native_payment_sym(ctx)
+    }
+
+    pub fn release_escrow_sym(ctx: Context<ReleaseEscrowSym>) -> Result<()> {
+        instructions::sym_escrow::release_escrow_sym(ctx)
+    }
+
+    pub fn refund_escrow_sym(ctx: Context<RefundEscrowSym>) -> Result<()> {
+        instructions::sym_escrow::refund_escrow_sym(ctx)
+    }
+
+    //view instructions
+    pub fn get_escrow_summary(ctx: Context<GetEscrowSummaryAsym>) -> Result<EscrowSummary> {
+        instructions::views::get_escrow_summary(ctx)
+    }
+
+    pub fn quote_release(ctx: Context<QuoteReleaseAsym>, amount: u64) -> Result<ReleaseQuote> {
+        instructions::views::quote_release(ctx, amount)
+    }
+
+    //expiry instructions
+    pub fn expire_escrow_asym(ctx: Context<ExpireEscrowAsym>) -> Result<()> {
+        instructions::expiry::expire_escrow_asym(ctx)
+    }
+
+    pub fn expire_escrow_sym(ctx: Context<ExpireEscrowSym>) -> Result<()> {
+        instructions::expiry::expire_escrow_sym(ctx)
+    }
+
+    pub fn crank_escrows_asym<'info>(ctx: Context<'_, '_, 'info, 'info, CrankEscrowsAsym<'info>>) -> Result<()> {
+        instructions::crank::crank_escrows_asym(ctx)
+    }
+
+    //cleanup instructions
+    pub fn garbage_collect_asym<'info>(ctx: Context<'_, '_, 'info, 'info, GarbageCollectAsym<'info>>) -> Result<()> {
+        instructions::cleanup::garbage_collect(ctx)
+    }
+
+    //arbitration instructions
+    pub fn open_dispute_asym(ctx: Context<OpenDisputeAsym>) -> Result<()> {
+        instructions::arbitration::open_dispute(ctx)
+    }
+
+    pub fn answer_dispute_asym(ctx: Context<AnswerDisputeAsym>) -> Result<()> {
+        instructions::arbitration::answer_dispute(ctx)
+    }
+
+    pub fn submit_evidence_asym(ctx: Context<SubmitEvidenceAsym>, content_hash: [u8; 32], scheme: EvidenceHashScheme) -> Result<()> {
+        instructions::arbitration::submit_evidence(ctx, content_hash, scheme)
+    }
+
+    pub fn disclose_amount_asym(ctx: Context<DiscloseAmountAsym>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        instructions::arbitration::disclose_amount(ctx, amount, salt)
+    }
+
+    pub fn close_evidence_phase_asym(ctx: Context<CloseEvidencePhaseAsym>) -> Result<()> {
+        instructions::arbitration::close_evidence_phase(ctx)
+    }
+
+    pub fn award_arbitration_asym(ctx: Context<AwardArbitrationAsym>, payer_amount: u64, rationale_hash: [u8; 32]) -> Result<()> {
+        instructions::arbitration::award_arbitration(ctx, payer_amount, rationale_hash)
+    }
+
+    pub fn assign_arbiter_panel_asym(ctx: Context<AssignArbiterPanelAsym>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::arbitration::assign_arbiter_panel(ctx, members, threshold)
+    }
+
+    pub fn draw_jury_asym(ctx: Context<DrawJuryAsym>) -> Result<()> {
+        instructions::arbitration::draw_jury(ctx)
+    }
+
+    pub fn vote_award_asym(ctx: Context<VoteAwardAsym>, payer_amount: u64, rationale_hash: [u8; 32]) -> Result<()> {
+        instructions::arbitration::vote_award(ctx, payer_amount, rationale_hash)
+    }
+
+    pub fn resolve_expired_dispute_asym(ctx: Context<ResolveExpiredDisputeAsym>) -> Result<()> {
+        instructions::arbitration::resolve_expired_dispute(ctx)
+    }
+
+    pub fn submit_automated_outcome_asym(ctx: Context<SubmitAutomatedOutcomeAsym>, payer_amount: u64, rationale_hash: [u8; 32]) -> Result<()> {
+        instructions::arbitration::submit_automated_outcome(ctx, payer_amount, rationale_hash)
+    }
+
+    pub fn escalate_automated_outcome_asym(ctx: Context<EscalateAutomatedOutcomeAsym>) -> Result<()> {
+        instructions::arbitration::escalate_automated_outcome(ctx)
+    }
+
+    pub fn appeal_award_asym(ctx: Context<AppealAwardAsym>) -> Result<()> {
+        instructions::arbitration::appeal_award(ctx)
+    }
+
+    pub fn award_appeal_asym(ctx: Context<AwardAppealAsym>, payer_amount: u64, rationale_hash: [u8; 32]) -> Result<()> {
+        instructions::arbitration::award_appeal(ctx, payer_amount, rationale_hash)
+    }
+
+    pub fn execute_award_asym<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteAwardAsym<'info>>) -> Result<()> {
+        instructions::arbitration::execute_award(ctx)
+    }
+
+    pub fn initialize_dispute_fee_vault_asym(ctx: Context<InitializeDisputeFeeVaultAsym>) -> Result<()> {
+        instructions::arbitration::initialize_dispute_fee_vault(ctx)
+    }
+
+    pub fn deposit_arbitration_fee_asym<'info>(ctx: Context<'_, '_, '_, 'info, DepositArbitrationFeeAsym<'info>>) -> Result<()> {
+        instructions::arbitration::deposit_arbitration_fee(ctx)
+    }
+
+    pub fn settle_arbitration_fee_asym<'info>(ctx: Context<'_, '_, '_, 'info, SettleArbitrationFeeAsym<'info>>) -> Result<()> {
+        instructions::arbitration::settle_arbitration_fee(ctx)
+    }
+
+    //bundle instructions
+    pub fn initialize_bundle_asym(ctx: Context<InitializeBundleAsym>, assets: Vec<BundleAssetParams>) -> Result<()> {
+        instructions::bundle::initialize_bundle(ctx, assets)
+    }
+
+    pub fn deposit_bundle_asset_asym(ctx: Context<DepositBundleAssetAsym>, index: u8) -> Result<()> {
+        instructions::bundle::deposit_bundle_asset(ctx, index)
+    }
+
+    pub fn settle_bundle_asym<'info>(ctx: Context<'_, '_, 'info, 'info, SettleBundleAsym<'info>>) -> Result<()> {
+        instructions::bundle::settle_bundle(ctx)
+    }
+
+    //mediation instructions
+    pub fn initialize_mediation_log(ctx: Context<InitializeMediationLog>) -> Result<()> {
+        instructions::mediation::initialize_mediation_log(ctx)
+    }
+
+    pub fn append_mediation_entry(ctx: Context<AppendMediationEntry>, kind: MediationEntryKind, content_hash: [u8; 32], payer_amount: u64) -> Result<()> {
+        instructions::mediation::append_mediation_entry(ctx, kind, content_hash, payer_amount)
+    }
+
+    //reputation instructions
+    pub fn initialize_reputation(ctx: Context<InitializeReputation>, addr: Pubkey) -> Result<()> {
+        instructions::reputation::initialize_reputation(ctx, addr)
+    }
+
+    pub fn initialize_rating_aggregate(ctx: Context<InitializeRatingAggregate>, addr: Pubkey) -> Result<()> {
+        instructions::reputation::initialize_rating_aggregate(ctx, addr)
+    }
+
+    pub fn rate_counterparty(ctx: Context<RateCounterparty>, score: u8, comment_hash: [u8; 32]) -> Result<()> {
+        instructions::reputation::rate_counterparty(ctx, score, comment_hash)
+    }
+
+    //arbiter registry instructions
+    pub fn initialize_arbiter_pool(ctx: Context<InitializeArbiterPool>) -> Result<()> {
+        instructions::arbiter::initialize_arbiter_pool(ctx)
+    }
+
+    pub fn register_arbiter(ctx: Context<RegisterArbiter>, bond: u64) -> Result<()> {
+        instructions::arbiter::register_arbiter(ctx, bond)
+    }
+
+    pub fn slash_arbiter(ctx: Context<SlashArbiter>, amount: u64, insurance_amount: u64) -> Result<()> {
+        instructions::arbiter::slash_arbiter(ctx, amount, insurance_amount)
+    }
+
+    //settlement hook instructions
+    pub fn set_settlement_hook_asym(ctx: Context<SetSettlementHookAsym>, settlement_hook: Pubkey) -> Result<()> {
+        instructions::settlement_hook::set_settlement_hook(ctx, settlement_hook)
+    }
+
+    //automation instructions
+    pub fn register_automation_trigger_asym<'info>(ctx: Context<'_, '_, '_, 'info, RegisterAutomationTriggerAsym<'info>>, kind: AutomationTriggerKind, interval: i64) -> Result<()> {
+        instructions::automation::register_automation_trigger(ctx, kind, interval)
+    }
+
+    pub fn unregister_automation_trigger_asym<'info>(ctx: Context<'_, '_, '_, 'info, UnregisterAutomationTriggerAsym<'info>>) -> Result<()> {
+        instructions::automation::unregister_automation_trigger(ctx)
+    }
+
+    //lending instructions
+    pub fn opt_in_lending_asym(ctx: Context<OptInLendingAsym>, lending_adapter: Pubkey, yield_payer_bps: u16) -> Result<()> {
+        instructions::lending::opt_in_lending(ctx, lending_adapter, yield_payer_bps)
+    }
+
+    pub fn supply_escrow_funds_asym<'info>(ctx: Context<'_, '_, '_, 'info, SupplyEscrowFundsAsym<'info>>, amount: u64) -> Result<()> {
+        instructions::lending::supply_escrow_funds(ctx, amount)
+    }
+
+    pub fn withdraw_escrow_funds_asym<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawEscrowFundsAsym<'info>>) -> Result<()> {
+        instructions::lending::withdraw_escrow_funds(ctx)
+    }
+
+    //liquid staking instructions
+    pub fn stake_escrow_sol_asym(ctx: Context<StakeEscrowSolAsym>) -> Result<()> {
+        instructions::staking::stake_escrow_sol(ctx)
+    }
+
+    pub fn unstake_escrow_sol_asym(ctx: Context<UnstakeEscrowSolAsym>) -> Result<()> {
+        instructions::staking::unstake_escrow_sol(ctx)
+    }
+
+    //swap instructions
+    pub fn swap_escrow_mint_asym<'info>(ctx: Context<'_, '_, '_, 'info, SwapEscrowMintAsym<'info>>, bounds: SwapBounds, route_data: Vec<u8>) -> Result<()> {
+        instructions::swap::swap_escrow_mint(ctx, bounds, route_data)
+    }
+
+    //interest instructions
+    pub fn set_interest_terms_asym(ctx: Context<SetInterestTermsAsym>, terms: InterestTerms) -> Result<()> {
+        instructions::interest::set_interest_terms(ctx, terms)
+    }
+
+    pub fn accrue_interest_asym(ctx: Context<AccrueInterestAsym>) -> Result<()> {
+        instructions::interest::accrue_interest(ctx)
+    }
+
+    //delegate instructions
+    pub fn set_delegate_asym(ctx: Context<SetDelegateAsym>, delegate: Pubkey, delegate_limit: u64) -> Result<()> {
+        instructions::delegate::set_delegate(ctx, delegate, delegate_limit)
+    }
+
+    pub fn register_session_key_asym(ctx: Context<ManageSessionKeyAsym>, session_key: Pubkey, permissions: u8, expiry_slot: u64) -> Result<()> {
+        instructions::delegate::register_session_key(ctx, session_key, permissions, expiry_slot)
+    }
+
+    pub fn revoke_session_key_asym(ctx: Context<ManageSessionKeyAsym>) -> Result<()> {
+        instructions::delegate::revoke_session_key(ctx)
+    }
+
+    //memo instructions
+    pub fn set_memo_options_asym(ctx: Context<SetMemoOptionsAsym>, memo_enabled: bool, client_ref: [u8; 32]) -> Result<()> {
+        instructions::memo::set_memo_options(ctx, memo_enabled, client_ref)
+    }
+
+    //relay instructions
+    pub fn relay_release_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::relay_release(ctx)
+    }
+
+    pub fn relay_token_release_asym<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseTokenEscrowAsym<'info>>) -> Result<()> {
+        instructions::asym_escrow::relay_token_release(ctx)
+    }
+
+    pub fn relay_refund_asym<'info>(ctx: Context<'_, '_, '_, 'info, RefundEscrowAsym<'info>>, amount: u64) -> Result<()> {
+        instructions::asym_escrow::relay_refund(ctx, amount)
+    }
+
+    pub fn relay_open_dispute_asym(ctx: Context<OpenDisputeAsym>) -> Result<()> {
+        instructions::arbitration::relay_open_dispute(ctx)
+    }
+
+    //guardian instructions
+    pub fn set_guardian_asym(ctx: Context<SetGuardianAsym>, guardian: Pubkey, guardian_threshold: u64) -> Result<()> {
+        instructions::guardian::set_guardian(ctx, guardian, guardian_threshold)
+    }
+
+    pub fn hold_release_asym(ctx: Context<GuardianHoldAsym>) -> Result<()> {
+        instructions::guardian::hold_release(ctx)
+    }
+
+    pub fn lift_release_hold_asym(ctx: Context<GuardianHoldAsym>) -> Result<()> {
+        instructions::guardian::lift_release_hold(ctx)
+    }
+
+    //observer instructions
+    pub fn add_observer_asym(ctx: Context<ManageObserversAsym>, key: Pubkey, name: [u8; 32], permissions: u8) -> Result<()> {
+        instructions::observers::add_observer(ctx, key, name, permissions)
+    }
+
+    pub fn remove_observer_asym(ctx: Context<ManageObserversAsym>, key: Pubkey) -> Result<()> {
+        instructions::observers::remove_observer(ctx, key)
+    }
+
+    pub fn snapshot_escrow_asym(ctx: Context<SnapshotEscrowAsym>) -> Result<EscrowSnapshot> {
+        instructions::observers::snapshot_escrow(ctx)
+    }
+
+    pub fn append_audit_note_asym(ctx: Context<AppendAuditNoteAsym>, content_hash: [u8; 32]) -> Result<()> {
+        instructions::observers::append_audit_note(ctx, content_hash)
+    }
+
+    //platform instructions
+    pub fn register_platform(ctx: Context<RegisterPlatform>, platform_authority: Pubkey, fee_share_bps: u16) -> Result<()> {
+        instructions::platform::register_platform(ctx, platform_authority, fee_share_bps)
+    }
+
+    pub fn register_partner_program(ctx: Context<RegisterPartnerProgram>, partner_program_id: Pubkey, fee_share_bps: u16) -> Result<()> {
+        instructions::platform::register_partner_program(ctx, partner_program_id, fee_share_bps)
+    }
+
+    pub fn deregister_platform(ctx: Context<DeregisterPlatform>) -> Result<()> {
+        instructions::platform::deregister_platform(ctx)
+    }
+
+    //stats instructions
+    pub fn initialize_currency_stats(ctx: Context<InitializeCurrencyStats>, currency: Pubkey) -> Result<()> {
+        instructions::stats::initialize_currency_stats(ctx, currency)
+    }
+
+    pub fn initialize_tvl_ledger(ctx: Context<InitializeTvlLedger>) -> Result<()> {
+        instructions::stats::initialize_tvl_ledger(ctx)
+    }
+
+    pub fn track_tvl_currency(ctx: Context<TrackTvlCurrency>, currency: Pubkey) -> Result<()> {
+        instructions::stats::track_tvl_currency(ctx, currency)
+    }
+
+    pub fn audit_tvl<'info>(ctx: Context<'_, '_, 'info, 'info, AuditTvl<'info>>, currency: Pubkey) -> Result<()> {
+        instructions::stats::audit_tvl(ctx, currency)
+    }
+
+    pub fn initialize_user_stats(ctx: Context<InitializeUserStats>, addr: Pubkey) -> Result<()> {
+        instructions::stats::initialize_user_stats(ctx, addr)
+    }
+
+    pub fn initialize_category_stats(ctx: Context<InitializeCategoryStats>, category: EscrowCategory) -> Result<()> {
+        instructions::stats::initialize_category_stats(ctx, category)
+    }
+
+    pub fn initialize_volume_history(ctx: Context<InitializeVolumeHistory>, currency: Pubkey) -> Result<()> {
+        instructions::stats::initialize_volume_history(ctx, currency)
+    }
+
+    //sanctions blocklist instructions
+    pub fn block_address(ctx: Context<BlockAddress>, addr: Pubkey) -> Result<()> {
+        instructions::blocklist::block_address(ctx, addr)
+    }
+
+    pub fn unblock_address(ctx: Context<UnblockAddress>) -> Result<()> {
+        instructions::blocklist::unblock_address(ctx)
+    }
+
+    //counterparty allowlist instructions
+    pub fn initialize_counterparty_allowlist(ctx: Context<InitializeCounterpartyAllowlist>, counterparties: Vec<Pubkey>) -> Result<()> {
+        instructions::allowlist::initialize_counterparty_allowlist(ctx, counterparties)
+    }
+
+    pub fn update_counterparty_allowlist(ctx: Context<UpdateCounterpartyAllowlist>, counterparties: Vec<Pubkey>) -> Result<()> {
+        instructions::allowlist::update_counterparty_allowlist(ctx, counterparties)
+    }
+
+    pub fn close_counterparty_allowlist(ctx: Context<CloseCounterpartyAllowlist>) -> Result<()> {
+        instructions::allowlist::close_counterparty_allowlist(ctx)
+    }
+
+    //social recovery instructions
+    pub fn set_recovery_asym(ctx: Context<ManageRecoveryAsym>, recovery_addresses: Vec<Pubkey>, delay: i64) -> Result<()> {
+        instructions::recovery::set_recovery(ctx, recovery_addresses, delay)
+    }
+
+    pub fn propose_recovery_asym(ctx: Context<ManageRecoveryAsym>, new_addr: Pubkey) -> Result<()> {
+        instructions::recovery::propose_recovery(ctx, new_addr)
+    }
+
+    pub fn veto_recovery_asym(ctx: Context<ManageRecoveryAsym>) -> Result<()> {
+        instructions::recovery::veto_recovery(ctx)
+    }
+
+    pub fn execute_recovery_asym(ctx: Context<ManageRecoveryAsym>) -> Result<()> {
+        instructions::recovery::execute_recovery(ctx)
+    }
+
+    //co-signer instructions
+    pub fn set_co_signers_asym(ctx: Context<SetCoSignersAsym>, co_signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::co_signers::set_co_signers(ctx, co_signers, threshold)
+    }
+
+    //escrow chaining instructions
+    pub fn chain_escrow_asym(ctx: Context<ChainEscrowAsym>) -> Result<()> {
+        instructions::chaining::chain_escrow(ctx)
+    }
+
+    //receipt instructions
+    pub fn mint_escrow_receipt_asym(ctx: Context<MintEscrowReceiptAsym>, uri: String, transferable: bool) -> Result<()> {
+        instructions::receipt::mint_escrow_receipt(ctx, uri, transferable)
+    }
+
+    pub fn burn_escrow_receipt_asym(ctx: Context<BurnEscrowReceiptAsym>) -> Result<()> {
+        instructions::receipt::burn_escrow_receipt(ctx)
+    }
+
+    //cross-chain instructions
+    pub fn set_wormhole_trigger_asym(ctx: Context<SetWormholeTriggerAsym>, emitter_chain: u16, emitter_address: [u8; 32]) -> Result<()> {
+        instructions::wormhole::set_wormhole_trigger(ctx, emitter_chain, emitter_address)
+    }
+
+    //insurance fund instructions
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        instructions::insurance::initialize_insurance_fund(ctx)
+    }
+
+    pub fn pay_insurance_claim(ctx: Context<PayInsuranceClaim>, amount: u64, claim_hash: [u8; 32]) -> Result<()> {
+        instructions::insurance::pay_insurance_claim(ctx, amount, claim_hash)
+    }
 }
 
 #[cfg(test)]
@@ -69,7 +555,43 @@ mod tests {
         
         //space should be reasonable bounds
         assert!(expected_space >= 76); //8 (discriminator) + 32 (authority) + 32 (fee_vault) + 2 (fee_bps) + 1 (paused) + 1 (bump)
-        assert!(expected_space <= 200); //not excessively large
+        assert!(expected_space <= 320 + (constants::MAX_TRANSFER_HOOK_PROGRAMS + constants::MAX_LENDING_ADAPTERS + constants::MAX_STAKE_POOLS + constants::MAX_SETTLEMENT_HOOKS + constants::MAX_AUTOMATION_PROGRAMS + constants::MAX_SWAP_ROUTERS) * 32); //not excessively large beyond the allowlists
+        
+        //a config with every allowlist full should fit its allocated space exactly
+        let full = |max: usize| vec![Pubkey::new_unique(); max];
+        let config = state::program_config::ProgramConfig {
+            authority: Pubkey::new_unique(),
+            fee_vault: Pubkey::new_unique(),
+            default_fee_bps: 100,
+            paused: false,
+            bump: 255,
+            evidence_window: 86400,
+            decision_window: 86400,
+            default_resolution: state::program_config::DefaultResolution::Split,
+            dispute_bond: 100_000_000,
+            insurance_fee_bps: 500,
+            min_dispute_window: 3600,
+            max_dispute_window: 2592000,
+            transfer_hook_allowlist: full(constants::MAX_TRANSFER_HOOK_PROGRAMS),
+            yield_fee_bps: 1_000,
+            lending_adapter_allowlist: full(constants::MAX_LENDING_ADAPTERS),
+            stake_pool_allowlist: full(constants::MAX_STAKE_POOLS),
+            guardian: Pubkey::new_unique(),
+            guardian_threshold: u64::MAX,
+            event_seq: u64::MAX,
+            admin_action_count: u64::MAX,
+            settlement_hook_allowlist: full(constants::MAX_SETTLEMENT_HOOKS),
+            automation_program_allowlist: full(constants::MAX_AUTOMATION_PROGRAMS),
+            swap_router_allowlist: full(constants::MAX_SWAP_ROUTERS),
+            max_swap_slippage_bps: 100,
+            max_swap_price_impact_bps: 300,
+            governance: Pubkey::new_unique(),
+            lookup_table: Pubkey::new_unique(),
+            compact_events: true,
+        };
+        let mut data = Vec::new();
+        anchor_lang::AccountSerialize::try_serialize(&config, &mut data).unwrap();
+        assert_eq!(data.len(), expected_space);
     }
 
     #[test]
@@ -122,7 +644,7 @@ mod tests {
     #[test]
     fn test_create_asym_escrow() {
         use crate::instructions::asym_escrow::CreateAsymEscrowParams;
-        use crate::state::escrow::{EscrowStatus, CurrencyType};
+        use crate::state::escrow::{EscrowStatus, CurrencyType, ArbitrationFee};
         
         //test escrow id generation
         let creator = Pubkey::new_unique();
@@ -152,6 +674,15 @@ mod tests {
             start_time: 1600000000,
             end_time: 1600086400, //24 hours later
             nonce,
+            arbiter: Pubkey::default(), //no arbitration
+            arbitration_fee: ArbitrationFee::default(),
+            appeal_arbiter: Pubkey::default(), //no appeals
+            arbiter_kind: ArbiterKind::Individual,
+            automated_settler: Pubkey::default(),
+            arbitration_fee_mint: Pubkey::default(),
+            dispute_window: 0,
+            amount_commitment: [0u8; 32], //public amount
+            category: EscrowCategory::Freelance,
         };
         
         //validate params structure
@@ -169,7 +700,7 @@ mod tests {
 
     #[test]
     fn test_escrow_payment_calculations() {
-        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType};
+        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType, ArbitrationFee};
         
         //create mock escrow with 1 SOL requirement
         let mut escrow = AsymEscrow {
@@ -183,6 +714,14 @@ mod tests {
                 amount_refunded: 0,
                 amount_released: 0,
                 released: false,
+                amount_delivered: 0,
+                decimals: 9,
+                delegate: Pubkey::default(),
+                delegate_limit: 0,
+                session_key: Pubkey::default(),
+                session_permissions: 0,
+                session_expiry_slot: 0,
+                relay_nonce: 0,
             },
             receiver: EscrowParty::default(),
             timestamp: 1600000000,
@@ -194,6 +733,65 @@ mod tests {
             creator: Pubkey::new_unique(),
             nonce: 12345,
             bump: 254,
+            arbiter: Pubkey::default(),
+            arbitration_fee: ArbitrationFee::default(),
+            arbitration_fee_paid: 0,
+            appeal_arbiter: Pubkey::default(),
+            arbiter_kind: ArbiterKind::Individual,
+            automated_settler: Pubkey::default(),
+            arbitration_fee_mint: Pubkey::default(),
+            dispute_window: 0,
+            release_requested_at: 0,
+            amount_commitment: [0u8; 32],
+            nft_collection: Pubkey::default(),
+            usd_amount: 0,
+            price_feed: Pubkey::default(),
+            oracle_source: OracleSource::Pyth,
+            price_floor: 0,
+            price_ceiling: 0,
+            collar_breached: false,
+            lending_adapter: Pubkey::default(),
+            lent_amount: 0,
+            yield_payer_bps: 0,
+            stake_pool: Pubkey::default(),
+            staked_lamports: 0,
+            lst_amount: 0,
+            accepted_mints: Vec::new(),
+            interest_rate_bps: 0,
+            payment_due_time: 0,
+            interest_cap: 0,
+            accrued_interest: 0,
+            interest_accrued_at: 0,
+            hashlock: [0u8; 32],
+            timelock: 0,
+            wormhole_emitter_chain: 0,
+            wormhole_emitter: [0u8; 32],
+            receipt_mint: Pubkey::default(),
+            claim_transferable: false,
+            chained_escrow: Pubkey::default(),
+            chain_depth: 0,
+            co_signed_party: Pubkey::default(),
+            co_signer_threshold: 0,
+            co_signers: Vec::new(),
+            payer_recovery: Default::default(),
+            receiver_recovery: Default::default(),
+            attestation_kind: Default::default(),
+            attestation_issuer: Pubkey::default(),
+            attestation_schema: Pubkey::default(),
+            guardian: Pubkey::default(),
+            guardian_threshold: 0,
+            guardian_hold_until: 0,
+            platform: Pubkey::default(),
+            observers: Vec::new(),
+            audit_note_count: 0,
+            event_seq: 0,
+            category: EscrowCategory::default(),
+            fee_terms: FeeTerms::default(),
+            settlement_hook: Pubkey::default(),
+            memo_enabled: false,
+            client_ref: [0; 32],
+            quoted_price: OraclePrice::default(),
+            external_call_in_progress: false,
         };
 
         //test partial payment (0.5 SOL)
@@ -225,11 +823,225 @@ mod tests {
         escrow.payer.amount_paid = 1_200_000_000; //1.2 SOL paid
         assert!(escrow.payer.amount_paid > escrow.payer.amount);
         assert_eq!(escrow.get_amount_remaining(), 1_200_000_000);
+        
+        //accepted mints can only be selected before the first deposit
+        let usdc = Pubkey::new_unique();
+        escrow.accepted_mints = vec![state::escrow::AcceptedMint { mint: usdc, amount: 100_000_000 }];
+        assert!(!escrow.can_select_mint(&usdc));
+        escrow.payer.amount_paid = 0;
+        assert!(escrow.can_select_mint(&usdc));
+        assert!(!escrow.can_select_mint(&Pubkey::new_unique()));
+        
+        //late interest accrues on the unpaid balance only after the due date, up to the cap
+        escrow.interest_rate_bps = 1000; //10% a year
+        escrow.payment_due_time = 1_000;
+        escrow.interest_cap = 60_000_000;
+        escrow.payer.amount_paid = 500_000_000;
+        assert_eq!(escrow.pending_interest(1_000), 0);
+        assert_eq!(escrow.pending_interest(1_000 + constants::SECONDS_PER_YEAR), 50_000_000);
+        assert_eq!(escrow.pending_interest(1_000 + 2 * constants::SECONDS_PER_YEAR), 60_000_000);
+        escrow.interest_accrued_at = 1_000 + constants::SECONDS_PER_YEAR / 2;
+        assert_eq!(escrow.pending_interest(1_000 + constants::SECONDS_PER_YEAR), 25_000_000);
+        
+        //a delegate can act for the payer only within its per-action limit
+        let delegate = Pubkey::new_unique();
+        assert!(!escrow.payer.is_authorized(&delegate, 1));
+        escrow.payer.delegate = delegate;
+        escrow.payer.delegate_limit = 100_000_000;
+        assert!(escrow.payer.is_authorized(&delegate, 100_000_000));
+        assert!(!escrow.payer.is_authorized(&delegate, 100_000_001));
+        assert!(escrow.payer.is_authorized(&escrow.payer.addr, u64::MAX));
+        
+        //a session key can act only with its permissions, until it expires
+        let session_key = Pubkey::new_unique();
+        escrow.payer.session_key = session_key;
+        escrow.payer.session_permissions = constants::session_permissions::DEPOSIT;
+        escrow.payer.session_expiry_slot = 500;
+        assert!(escrow.payer.can_act(&session_key, u64::MAX, constants::session_permissions::DEPOSIT, 500));
+        assert!(!escrow.payer.can_act(&session_key, 1, constants::session_permissions::DEPOSIT, 501));
+        assert!(!escrow.payer.can_act(&session_key, 1, constants::session_permissions::RELEASE, 500));
+        
+        //a co-signed party consents once its threshold of co-signers has assented
+        let officers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        escrow.co_signed_party = escrow.payer.addr;
+        escrow.co_signer_threshold = 2;
+        escrow.co_signers = officers
+            .iter()
+            .map(|key| state::escrow::CoSigner { key: *key, assented: false })
+            .collect();
+        assert!(escrow.has_co_signers(&escrow.payer.addr));
+        assert!(!escrow.has_co_signers(&escrow.receiver.addr));
+        assert_eq!(escrow.co_signer_index(&officers[2]), Some(2));
+        escrow.co_signers[0].assented = true;
+        assert!(!escrow.is_co_signer_threshold_met());
+        escrow.co_signers[2].assented = true;
+        assert!(escrow.is_co_signer_threshold_met());
+        
+        //a relayed intent is bound to the escrow, action, amount and the party's nonce
+        let escrow_key = Pubkey::new_unique();
+        let message = instructions::relay::relay_intent_message(&escrow_key, constants::relay_actions::REFUND, 5, 7);
+        assert_eq!(message.len(), 49);
+        assert_eq!(&message[..32], escrow_key.as_ref());
+        assert_eq!(message[32], constants::relay_actions::REFUND);
+        assert_ne!(message, instructions::relay::relay_intent_message(&escrow_key, constants::relay_actions::REFUND, 5, 8));
+        
+        //a key rotation is pending until executed or vetoed
+        assert!(!escrow.payer_recovery.is_pending());
+        escrow.payer_recovery.pending_addr = Pubkey::new_unique();
+        assert!(escrow.payer_recovery.is_pending());
+        
+        //test guardian holds
+        assert!(!escrow.is_release_held(u64::MAX, 0));
+        escrow.guardian = Pubkey::new_unique();
+        escrow.guardian_threshold = 1000;
+        escrow.guardian_hold_until = 100;
+        assert!(escrow.is_release_held(1001, 50));
+        assert!(!escrow.is_release_held(1000, 50));
+        assert!(!escrow.is_release_held(1001, 100));
+        
+        //test observer permissions
+        let observer = Pubkey::new_unique();
+        assert!(!escrow.is_observer(&observer));
+        escrow.observers.push(Observer {
+            key: observer,
+            name: [0u8; 32],
+            permissions: constants::observer_permissions::SNAPSHOT,
+        });
+        assert!(escrow.is_observer(&observer));
+        assert!(escrow.can_observe(&observer, constants::observer_permissions::SNAPSHOT));
+        assert!(!escrow.can_observe(&observer, constants::observer_permissions::AUDIT_NOTE));
+        
+        //test event sequencing
+        assert_eq!(escrow.next_event_seq(), 1);
+        assert_eq!(escrow.next_event_seq(), 2);
+        assert_eq!(escrow.event_seq, 2);
+        
+        //test status changes
+        let status = escrow.status;
+        assert_eq!(escrow.set_status(status), None);
+        assert_eq!(escrow.set_status(EscrowStatus::Completed), Some(status));
+        
+        //test release split (the same fee in every currency)
+        escrow.payer.currency_type = CurrencyType::SplToken;
+        assert_eq!(crate::instructions::release_fee_and_payout(&escrow, 10_000).unwrap(), (100, 9_900));
+        escrow.payer.currency_type = CurrencyType::Native;
+        assert_eq!(crate::instructions::release_fee_and_payout(&escrow, 10_000).unwrap(), (100, 9_900));
+        
+        //test log format
+        let line = crate::instructions::format_escrow_log(
+            constants::log_actions::RELEASE,
+            &[0xab; 32],
+            &[("amount", 100), ("fee", 1)],
+        );
+        assert_eq!(line, format!("solescrow:release escrow={} amount=100 fee=1", "ab".repeat(32)));
+        
+        //test memo format: the log line, with the client reference if one is set
+        let mut client_ref = [0u8; 32];
+        client_ref[..9].copy_from_slice(b"INV-00042");
+        let memo = crate::instructions::format_escrow_memo(constants::log_actions::DEPOSIT, &[0xab; 32], &client_ref, 500).unwrap();
+        assert_eq!(memo, format!("solescrow:deposit escrow={} amount=500 ref=INV-00042", "ab".repeat(32)));
+        let memo = crate::instructions::format_escrow_memo(constants::log_actions::REFUND, &[0xab; 32], &[0; 32], 500).unwrap();
+        assert_eq!(memo, format!("solescrow:refund escrow={} amount=500", "ab".repeat(32)));
+        assert!(crate::instructions::format_escrow_memo(constants::log_actions::REFUND, &[0xab; 32], &[0xff; 32], 500).is_err());
+        
+        //test expiry detection
+        escrow.status = EscrowStatus::Active;
+        escrow.end_time = 0;
+        assert!(!escrow.can_expire(i64::MAX)); //no end time
+        escrow.end_time = 1000;
+        assert!(!escrow.can_expire(1000));
+        assert!(escrow.can_expire(1001));
+        escrow.status = EscrowStatus::Completed;
+        assert!(!escrow.can_expire(1001));
+        
+        //test currency stats
+        let mut stats = crate::state::CurrencyStats::default();
+        let amount = 1_000_000_000u64;
+        stats.record_deposit(amount, true, 1);
+        stats.record_release(amount - 2_500_000, 2_500_000, true, 2);
+        assert_eq!(stats.volume_in, amount as u128);
+        assert_eq!(stats.volume_released + stats.fees_collected, amount as u128);
+        assert_eq!(stats.active_escrows, 0);
+        stats.record_refund(amount, true, 3);
+        assert_eq!(stats.active_escrows, 0); //escrows funded before tracking started
+        assert_eq!(stats.updated_at, 3);
+        
+        //test TVL ledger
+        let mut ledger = crate::state::TvlLedger::default();
+        let native = Pubkey::default();
+        assert!(ledger.track(native));
+        assert!(!ledger.track(native));
+        ledger.record(&native, amount, 0, 1);
+        ledger.record(&native, 0, amount / 4, 2);
+        ledger.record(&Pubkey::new_unique(), amount, 0, 3); //untracked currency
+        assert_eq!(ledger.locked(&native), Some(amount - amount / 4));
+        assert_eq!(ledger.updated_at, 2);
+        
+        //test user stats
+        let mut user_stats = crate::state::UserStats::default();
+        user_stats.record_settlement(true, amount, false, 1);
+        user_stats.record_settlement(false, amount, true, 2);
+        assert_eq!((user_stats.escrows_as_payer, user_stats.escrows_as_receiver), (1, 1));
+        assert_eq!(user_stats.settled_volume, 2 * amount);
+        assert_eq!(user_stats.disputes, 1);
+        assert_eq!(user_stats.last_settled_at, 2);
+        
+        //test rating aggregate: only the most recent scores are kept
+        let mut ratings = crate::state::RatingAggregate::default();
+        for i in 0..(constants::RECENT_RATINGS_LEN as i64 + 2) {
+            ratings.record((i % 5) as u8 + 1, i);
+        }
+        assert_eq!(ratings.count, constants::RECENT_RATINGS_LEN as u64 + 2);
+        assert_eq!(ratings.recent_scores.len(), constants::RECENT_RATINGS_LEN);
+        assert_eq!(ratings.recent_scores[0], 3); //first two scores dropped
+        assert_eq!(*ratings.recent_scores.last().unwrap(), 2);
+        assert_eq!(ratings.sum, 33);
+        
+        //test category stats
+        let mut category_stats = crate::state::CategoryStats { category: EscrowCategory::Rental, ..Default::default() };
+        category_stats.record_funded(1);
+        category_stats.record_funded(2);
+        category_stats.record_settled(true, 3);
+        category_stats.record_settled(false, 4);
+        category_stats.record_settled(false, 5); //funded before tracking started
+        assert_eq!((category_stats.escrows_funded, category_stats.escrows_settled), (2, 3));
+        assert_eq!(category_stats.disputes, 1);
+        assert_eq!(category_stats.active_escrows, 0);
+        assert_eq!(category_stats.updated_at, 5);
+        
+        //test volume history: buckets roll over by day and are reused once the window wraps
+        let day = constants::VOLUME_BUCKET_SECONDS;
+        let start = 20_000 * day;
+        let mut history = crate::state::VolumeHistory::default();
+        history.record(amount, 0, start);
+        history.record(0, amount / 2, start + day - 1);
+        history.record(amount, amount, start + day);
+        assert_eq!(history.volume_since(1, start + day), (amount, amount));
+        assert_eq!(history.volume_since(2, start + day), (2 * amount, amount + amount / 2));
+        let wrapped = start + constants::VOLUME_HISTORY_BUCKETS as i64 * day;
+        history.record(1, 0, wrapped); //reuses the first day's bucket
+        assert_eq!(history.volume_since(constants::VOLUME_HISTORY_BUCKETS as u64, wrapped), (amount + 1, amount));
+        assert_eq!(history.updated_at, wrapped);
+        
+        //test fee terms snapshot size
+        let fee_terms = FeeTerms { insurance_fee_bps: 500, yield_fee_bps: 1_000, dispute_bond: 100_000_000, platform_fee_share_bps: 2_500 };
+        assert_eq!(anchor_lang::AnchorSerialize::try_to_vec(&fee_terms).unwrap().len(), FeeTerms::INIT_SPACE);
+        
+        //an escrow with every list full should fit its allocated space exactly
+        use crate::state::escrow::{AcceptedMint, CoSigner, Observer};
+        escrow.accepted_mints = vec![AcceptedMint { mint: Pubkey::new_unique(), amount: 1 }; constants::MAX_ACCEPTED_MINTS];
+        escrow.co_signers = vec![CoSigner { key: Pubkey::new_unique(), assented: true }; constants::MAX_CO_SIGNERS];
+        escrow.observers = vec![Observer { key: Pubkey::new_unique(), name: [1; 32], permissions: 1 }; constants::MAX_OBSERVERS];
+        escrow.payer_recovery.recovery_addresses = vec![Pubkey::new_unique(); constants::MAX_RECOVERY_ADDRESSES];
+        escrow.receiver_recovery.recovery_addresses = vec![Pubkey::new_unique(); constants::MAX_RECOVERY_ADDRESSES];
+        let mut data = Vec::new();
+        anchor_lang::AccountSerialize::try_serialize(&escrow, &mut data).unwrap();
+        assert_eq!(data.len(), AsymEscrow::space());
     }
 
     #[test]
     fn test_escrow_validation_logic() {
-        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType};
+        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType, ArbitrationFee};
         
         let payer = Pubkey::new_unique();
         let receiver = Pubkey::new_unique();
@@ -296,7 +1108,7 @@ mod tests {
 
     #[test]
     fn test_escrow_release_consent_logic() {
-        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType};
+        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType, ArbitrationFee};
         
         //create mock escrow with full payment made
         let payer_key = Pubkey::new_unique();
@@ -313,6 +1125,14 @@ mod tests {
                 amount_refunded: 0,
                 amount_released: 0,
                 released: false, //no consent yet
+                amount_delivered: 0,
+                decimals: 9,
+                delegate: Pubkey::default(),
+                delegate_limit: 0,
+                session_key: Pubkey::default(),
+                session_permissions: 0,
+                session_expiry_slot: 0,
+                relay_nonce: 0,
             },
             receiver: EscrowParty {
                 addr: receiver_key,
@@ -323,6 +1143,14 @@ mod tests {
                 amount_refunded: 0,
                 amount_released: 0,
                 released: false, //no consent yet
+                amount_delivered: 0,
+                decimals: 9,
+                delegate: Pubkey::default(),
+                delegate_limit: 0,
+                session_key: Pubkey::default(),
+                session_permissions: 0,
+                session_expiry_slot: 0,
+                relay_nonce: 0,
             },
             timestamp: 1600000000,
             start_time: 0,
@@ -333,6 +1161,65 @@ mod tests {
             creator: Pubkey::new_unique(),
             nonce: 12345,
             bump: 254,
+            arbiter: Pubkey::default(),
+            arbitration_fee: ArbitrationFee::default(),
+            arbitration_fee_paid: 0,
+            appeal_arbiter: Pubkey::default(),
+            arbiter_kind: ArbiterKind::Individual,
+            automated_settler: Pubkey::default(),
+            arbitration_fee_mint: Pubkey::default(),
+            dispute_window: 0,
+            release_requested_at: 0,
+            amount_commitment: [0u8; 32],
+            nft_collection: Pubkey::default(),
+            usd_amount: 0,
+            price_feed: Pubkey::default(),
+            oracle_source: OracleSource::Pyth,
+            price_floor: 0,
+            price_ceiling: 0,
+            collar_breached: false,
+            lending_adapter: Pubkey::default(),
+            lent_amount: 0,
+            yield_payer_bps: 0,
+            stake_pool: Pubkey::default(),
+            staked_lamports: 0,
+            lst_amount: 0,
+            accepted_mints: Vec::new(),
+            interest_rate_bps: 0,
+            payment_due_time: 0,
+            interest_cap: 0,
+            accrued_interest: 0,
+            interest_accrued_at: 0,
+            hashlock: [0u8; 32],
+            timelock: 0,
+            wormhole_emitter_chain: 0,
+            wormhole_emitter: [0u8; 32],
+            receipt_mint: Pubkey::default(),
+            claim_transferable: false,
+            chained_escrow: Pubkey::default(),
+            chain_depth: 0,
+            co_signed_party: Pubkey::default(),
+            co_signer_threshold: 0,
+            co_signers: Vec::new(),
+            payer_recovery: Default::default(),
+            receiver_recovery: Default::default(),
+            attestation_kind: Default::default(),
+            attestation_issuer: Pubkey::default(),
+            attestation_schema: Pubkey::default(),
+            guardian: Pubkey::default(),
+            guardian_threshold: 0,
+            guardian_hold_until: 0,
+            platform: Pubkey::default(),
+            observers: Vec::new(),
+            audit_note_count: 0,
+            event_seq: 0,
+            category: EscrowCategory::default(),
+            fee_terms: FeeTerms::default(),
+            settlement_hook: Pubkey::default(),
+            memo_enabled: false,
+            client_ref: [0; 32],
+            quoted_price: OraclePrice::default(),
+            external_call_in_progress: false,
         };
 
         //test payer consent
@@ -364,7 +1251,7 @@ mod tests {
 
     #[test]
     fn test_escrow_authorization_logic() {
-        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType};
+        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType, ArbitrationFee};
         
         let payer_key = Pubkey::new_unique();
         let receiver_key = Pubkey::new_unique();
@@ -381,6 +1268,14 @@ mod tests {
                 amount_refunded: 0,
                 amount_released: 0,
                 released: false,
+                amount_delivered: 0,
+                decimals: 9,
+                delegate: Pubkey::default(),
+                delegate_limit: 0,
+                session_key: Pubkey::default(),
+                session_permissions: 0,
+                session_expiry_slot: 0,
+                relay_nonce: 0,
             },
             receiver: EscrowParty {
                 addr: receiver_key,
@@ -395,6 +1290,65 @@ mod tests {
             creator: Pubkey::new_unique(),
             nonce: 12346,
             bump: 254,
+            arbiter: Pubkey::default(),
+            arbitration_fee: ArbitrationFee::default(),
+            arbitration_fee_paid: 0,
+            appeal_arbiter: Pubkey::default(),
+            arbiter_kind: ArbiterKind::Individual,
+            automated_settler: Pubkey::default(),
+            arbitration_fee_mint: Pubkey::default(),
+            dispute_window: 0,
+            release_requested_at: 0,
+            amount_commitment: [0u8; 32],
+            nft_collection: Pubkey::default(),
+            usd_amount: 0,
+            price_feed: Pubkey::default(),
+            oracle_source: OracleSource::Pyth,
+            price_floor: 0,
+            price_ceiling: 0,
+            collar_breached: false,
+            lending_adapter: Pubkey::default(),
+            lent_amount: 0,
+            yield_payer_bps: 0,
+            stake_pool: Pubkey::default(),
+            staked_lamports: 0,
+            lst_amount: 0,
+            accepted_mints: Vec::new(),
+            interest_rate_bps: 0,
+            payment_due_time: 0,
+            interest_cap: 0,
+            accrued_interest: 0,
+            interest_accrued_at: 0,
+            hashlock: [0u8; 32],
+            timelock: 0,
+            wormhole_emitter_chain: 0,
+            wormhole_emitter: [0u8; 32],
+            receipt_mint: Pubkey::default(),
+            claim_transferable: false,
+            chained_escrow: Pubkey::default(),
+            chain_depth: 0,
+            co_signed_party: Pubkey::default(),
+            co_signer_threshold: 0,
+            co_signers: Vec::new(),
+            payer_recovery: Default::default(),
+            receiver_recovery: Default::default(),
+            attestation_kind: Default::default(),
+            attestation_issuer: Pubkey::default(),
+            attestation_schema: Pubkey::default(),
+            guardian: Pubkey::default(),
+            guardian_threshold: 0,
+            guardian_hold_until: 0,
+            platform: Pubkey::default(),
+            observers: Vec::new(),
+            audit_note_count: 0,
+            event_seq: 0,
+            category: EscrowCategory::default(),
+            fee_terms: FeeTerms::default(),
+            settlement_hook: Pubkey::default(),
+            memo_enabled: false,
+            client_ref: [0; 32],
+            quoted_price: OraclePrice::default(),
+            external_call_in_progress: false,
         };
 
         //test payer authorization
@@ -453,9 +1407,28 @@ mod tests {
         assert_eq!(expected_fee_max, amount); //entire amount as fee
     }
 
+    #[test]
+    fn test_native_vault_reserve() {
+        use crate::instructions::native_vault_remaining;
+        
+        let reserve = 890_880u64; //rent-exempt minimum of a system account without data
+        let vault = 1_000_000_000 + reserve; //1 SOL paid in, plus the reserve
+        
+        //partial and full payouts keep the reserve
+        assert_eq!(native_vault_remaining(vault, 400_000_000, reserve).unwrap(), 600_000_000 + reserve);
+        assert_eq!(native_vault_remaining(vault, 1_000_000_000, reserve).unwrap(), reserve);
+        
+        //a vault funded without the reserve can be emptied, but not left below it
+        assert_eq!(native_vault_remaining(1_000_000_000, 1_000_000_000, reserve).unwrap(), 0);
+        assert!(native_vault_remaining(1_000_000_000, 999_500_000, reserve).is_err());
+        
+        //nor can more than the vault holds be paid out
+        assert!(native_vault_remaining(vault, vault + 1, reserve).is_err());
+    }
+
     #[test]
     fn test_escrow_refund_logic() {
-        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType};
+        use crate::state::escrow::{AsymEscrow, EscrowParty, EscrowStatus, CurrencyType, ArbitrationFee};
         
         //create mock escrow with full payment made
         let payer_key = Pubkey::new_unique();
@@ -472,6 +1445,14 @@ mod tests {
                 amount_refunded: 0,
                 amount_released: 0,
                 released: false, //no consent yet
+                amount_delivered: 0,
+                decimals: 9,
+                delegate: Pubkey::default(),
+                delegate_limit: 0,
+                session_key: Pubkey::default(),
+                session_permissions: 0,
+                session_expiry_slot: 0,
+                relay_nonce: 0,
             },
             receiver: EscrowParty {
                 addr: receiver_key,
@@ -482,6 +1463,14 @@ mod tests {
                 amount_refunded: 0,
                 amount_released: 0,
                 released: false, //no consent yet
+                amount_delivered: 0,
+                decimals: 9,
+                delegate: Pubkey::default(),
+                delegate_limit: 0,
+                session_key: Pubkey::default(),
+                session_permissions: 0,
+                session_expiry_slot: 0,
+                relay_nonce: 0,
             },
             timestamp: 1600000000,
             start_time: 0,
@@ -492,6 +1481,573 @@ mod tests {
             creator: Pubkey::new_unique(),
             nonce: 12345,
             bump: 254,
+            arbiter: Pubkey::default(),
+            arbitration_fee: ArbitrationFee::default(),
+            arbitration_fee_paid: 0,
+            appeal_arbiter: Pubkey::default(),
+            arbiter_kind: ArbiterKind::Individual,
+            automated_settler: Pubkey::default(),
+            arbitration_fee_mint: Pubkey::default(),
+            dispute_window: 0,
+            release_requested_at: 0,
+            amount_commitment: [0u8; 32],
+            nft_collection: Pubkey::default(),
+            usd_amount: 0,
+            price_feed: Pubkey::default(),
+            oracle_source: OracleSource::Pyth,
+            price_floor: 0,
+            price_ceiling: 0,
+            collar_breached: false,
+            lending_adapter: Pubkey::default(),
+            lent_amount: 0,
+            yield_payer_bps: 0,
+            stake_pool: Pubkey::default(),
+            staked_lamports: 0,
+            lst_amount: 0,
+            accepted_mints: Vec::new(),
+            interest_rate_bps: 0,
+            payment_due_time: 0,
+            interest_cap: 0,
+            accrued_interest: 0,
+            interest_accrued_at: 0,
+            hashlock: [0u8; 32],
+            timelock: 0,
+            wormhole_emitter_chain: 0,
+            wormhole_emitter: [0u8; 32],
+            receipt_mint: Pubkey::default(),
+            claim_transferable: false,
+            chained_escrow: Pubkey::default(),
+            chain_depth: 0,
+            co_signed_party: Pubkey::default(),
+            co_signer_threshold: 0,
+            co_signers: Vec::new(),
+            payer_recovery: Default::default(),
+            receiver_recovery: Default::default(),
+            attestation_kind: Default::default(),
+            attestation_issuer: Pubkey::default(),
+            attestation_schema: Pubkey::default(),
+            guardian: Pubkey::default(),
+            guardian_threshold: 0,
+            guardian_hold_until: 0,
+            platform: Pubkey::default(),
+            observers: Vec::new(),
+            audit_note_count: 0,
+            event_seq: 0,
+            category: EscrowCategory::default(),
+            fee_terms: FeeTerms::default(),
+            settlement_hook: Pubkey::default(),
+            memo_enabled: false,
+            client_ref: [0; 32],
+            quoted_price: OraclePrice::default(),
+            external_call_in_progress: false,
+        };
+    }
+    #[test]
+    fn test_arbitration_fee_split() {
+        use crate::state::escrow::{ArbitrationFee, ArbitrationFeeType};
+        use crate::state::dispute::DisputeParty;
+        use crate::instructions::utils::{split_arbitration_fee, fee_token_shares};
+        
+        //test 2% bps fee on 1 SOL
+        let bps_fee = ArbitrationFee { fee_type: ArbitrationFeeType::Bps, value: 200 };
+        assert_eq!(bps_fee.calculate(1_000_000_000), Some(20_000_000));
+        
+        //test flat fee is capped at disputed amount
+        let flat_fee = ArbitrationFee { fee_type: ArbitrationFeeType::Flat, value: 50_000_000 };
+        assert_eq!(flat_fee.calculate(1_000_000_000), Some(50_000_000));
+        assert_eq!(flat_fee.calculate(10_000_000), Some(10_000_000));
+        
+        //test award against receiver (payer gets most of the funds)
+        let (payer_net, receiver_net, against) = split_arbitration_fee(800_000_000, 200_000_000, 20_000_000).unwrap();
+        assert_eq!(payer_net, 800_000_000);
+        assert_eq!(receiver_net, 180_000_000);
+        assert_eq!(against, Some(DisputeParty::Receiver));
+        
+        //test award against payer
+        let (payer_net, receiver_net, against) = split_arbitration_fee(0, 1_000_000_000, 20_000_000).unwrap();
+        assert_eq!(payer_net, 0);
+        assert_eq!(receiver_net, 980_000_000); //loser's share is empty, fee comes from winner
+        assert_eq!(against, Some(DisputeParty::Payer));
+        
+        //test even split shares the fee
+        let (payer_net, receiver_net, against) = split_arbitration_fee(500_000_000, 500_000_000, 20_000_001).unwrap();
+        assert_eq!(payer_net, 490_000_000);
+        assert_eq!(receiver_net, 489_999_999);
+        assert_eq!(against, None);
+        
+        //test fee larger than the award is rejected
+        assert!(split_arbitration_fee(100, 100, 201).is_err());
+        
+        //test fee token fees fall on the loser, or are halved on an even split
+        assert_eq!(fee_token_shares(1_000_001, Some(DisputeParty::Payer)), (1_000_001, 0));
+        assert_eq!(fee_token_shares(1_000_001, Some(DisputeParty::Receiver)), (0, 1_000_001));
+        assert_eq!(fee_token_shares(1_000_001, None), (500_000, 500_001));
+    }
+    #[test]
+    fn test_jury_selection() {
+        use crate::state::arbiter::ArbiterPoolEntry;
+        use crate::instructions::utils::select_weighted;
+        
+        let entries: Vec<ArbiterPoolEntry> = (0..6)
+            .map(|i| ArbiterPoolEntry {
+                arbiter: Pubkey::new_unique(),
+                stake: if i == 0 { 0 } else { 100_000_000 * i },
+            })
+            .collect();
+        let seed = [7u8; 32];
+        
+        //selection should be deterministic for a seed
+        let jurors = select_weighted(&entries, seed, 3);
+        assert_eq!(jurors, select_weighted(&entries, seed, 3));
+        assert_eq!(jurors.len(), 3);
+        
+        //jurors should be unique and never unstaked
+        assert_ne!(jurors[0], jurors[1]);
+        assert_ne!(jurors[1], jurors[2]);
+        assert_ne!(jurors[0], jurors[2]);
+        assert!(!jurors.contains(&entries[0].arbiter));
+        
+        //selection stops when candidates run out
+        assert_eq!(select_weighted(&entries, seed, 10).len(), 5);
+    }
+    #[test]
+    fn test_dispute_status_transitions() {
+        //test the regular lifecycle
+        assert!(DisputeStatus::EvidencePhase.can_transition_to(DisputeStatus::UnderReview));
+        assert!(DisputeStatus::UnderReview.can_transition_to(DisputeStatus::Awarded));
+        assert!(DisputeStatus::Awarded.can_transition_to(DisputeStatus::Appealed));
+        assert!(DisputeStatus::Appealed.can_transition_to(DisputeStatus::Awarded));
+        assert!(DisputeStatus::Awarded.can_transition_to(DisputeStatus::Resolved));
+        
+        //test invalid jumps are rejected
+        assert!(!DisputeStatus::EvidencePhase.can_transition_to(DisputeStatus::Awarded));
+        assert!(!DisputeStatus::UnderReview.can_transition_to(DisputeStatus::Resolved));
+        assert!(!DisputeStatus::Appealed.can_transition_to(DisputeStatus::Resolved));
+        assert!(!DisputeStatus::Resolved.can_transition_to(DisputeStatus::EvidencePhase));
+        assert!(!DisputeStatus::Resolved.can_transition_to(DisputeStatus::Awarded));
+    }
+    #[test]
+    fn test_bundle_deposit_tracking() {
+        use crate::state::bundle::{BundleAsset, EscrowBundle};
+        
+        let mut bundle = EscrowBundle {
+            escrow: Pubkey::new_unique(),
+            assets: vec![
+                BundleAsset { mint: Pubkey::new_unique(), amount: 500_000_000, amount_deposited: 0, vault: Pubkey::default() },
+                BundleAsset { mint: Pubkey::new_unique(), amount: 1, amount_deposited: 0, vault: Pubkey::default() },
+            ],
+            settled: false,
+            rent_payer: Pubkey::new_unique(),
+            bump: 255,
+        };
+        
+        //bundle is only fully deposited once every asset has a vault
+        assert!(!bundle.is_fully_deposited());
+        bundle.assets[0].vault = Pubkey::new_unique();
+        assert!(!bundle.is_fully_deposited());
+        bundle.assets[1].vault = Pubkey::new_unique();
+        assert!(bundle.is_fully_deposited());
+        
+        //space should cover a full bundle
+        assert!(EscrowBundle::space() >= 8 + 32 + 4 + constants::MAX_BUNDLE_ASSETS * BundleAsset::INIT_SPACE + 2);
+    }
+    #[test]
+    fn test_sym_escrow_deposits() {
+        use crate::state::escrow::{SymEscrow, EscrowParty, EscrowStatus, CurrencyType};
+        
+        let payer = Pubkey::new_unique();
+        let receiver = Pubkey::new_unique();
+        let mut escrow = SymEscrow {
+            id: [0u8; 32],
+            payer: EscrowParty {
+                addr: payer,
+                currency: Pubkey::new_unique(),
+                currency_type: CurrencyType::SplToken,
+                amount: 500_000_000,
+                ..Default::default()
+            },
+            receiver: EscrowParty {
+                addr: receiver,
+                currency: Pubkey::new_unique(),
+                currency_type: CurrencyType::SplToken,
+                amount: 2_000,
+                ..Default::default()
+            },
+            timestamp: 1600000000,
+            start_time: 0,
+            end_time: 1600086400,
+            status: EscrowStatus::Pending,
+            released: false,
+            fee_bps: 100,
+            creator: payer,
+            nonce: 1,
+            bump: 254,
+            event_seq: 0,
+            category: EscrowCategory::default(),
+        };
+        
+        //deposits are recorded against the depositing party
+        assert!(escrow.party_mut(&Pubkey::new_unique()).is_none());
+        escrow.party_mut(&payer).unwrap().amount_paid = 500_000_000;
+        assert!(!escrow.is_fully_deposited());
+        escrow.party_mut(&receiver).unwrap().amount_paid = 2_000;
+        assert!(escrow.is_fully_deposited());
+        
+        //escrow expires after its end time
+        assert!(!escrow.is_expired(1600086400));
+        assert!(escrow.is_expired(1600086401));
+    }
+    #[test]
+    fn test_amount_commitment() {
+        use crate::instructions::utils::compute_amount_commitment;
+        
+        let escrow = Pubkey::new_unique();
+        let salt = [9u8; 32];
+        let commitment = compute_amount_commitment(&escrow, 1_000_000_000, &salt);
+        
+        //the correct opening should match
+        assert_eq!(commitment, compute_amount_commitment(&escrow, 1_000_000_000, &salt));
+        
+        //a different amount, salt or escrow should not
+        assert_ne!(commitment, compute_amount_commitment(&escrow, 999_999_999, &salt));
+        assert_ne!(commitment, compute_amount_commitment(&escrow, 1_000_000_000, &[8u8; 32]));
+        assert_ne!(commitment, compute_amount_commitment(&Pubkey::new_unique(), 1_000_000_000, &salt));
+    }
+    #[test]
+    fn test_usd_price_conversion() {
+        use crate::instructions::oracle::{oracle_price_to_usd, usd_to_currency_amount, validate_oracle_price};
+        
+        //SOL at $150.00000000
+        let price = OraclePrice {
+            price: 15_000_000_000,
+            conf: 10_000_000,
+            expo: -8,
+            publish_time: 1_700_000_000,
+        };
+        
+        //$300 is 2 SOL, $1 in a 6-decimal token at $1 is 1 token
+        assert_eq!(usd_to_currency_amount(300_000_000, &price, 9).unwrap(), 2_000_000_000);
+        let stable = OraclePrice { price: 100_000_000, ..price };
+        assert_eq!(usd_to_currency_amount(1_000_000, &stable, 6).unwrap(), 1_000_000);
+        
+        //conversions round up in favor of the receiver
+        assert_eq!(usd_to_currency_amount(1, &price, 0).unwrap(), 1);
+        
+        //stale or imprecise prices are rejected
+        assert!(validate_oracle_price(&price, 1_700_000_000 + constants::MAX_PRICE_AGE).is_ok());
+        assert!(validate_oracle_price(&price, 1_700_000_001 + constants::MAX_PRICE_AGE).is_err());
+        let wide = OraclePrice { conf: 400_000_000, ..price };
+        assert!(validate_oracle_price(&wide, 1_700_000_000).is_err());
+        let negative = OraclePrice { price: -1, ..price };
+        assert!(validate_oracle_price(&negative, 1_700_000_000).is_err());
+        
+        //$150 per SOL, in USD decimals
+        assert_eq!(oracle_price_to_usd(&price).unwrap(), 150_000_000);
+    }
+    #[test]
+    fn test_pyth_price_update() {
+        use crate::instructions::oracle::parse_pyth_price_update;
+        use anchor_lang::solana_program::hash::hash;
+        
+        //a fully verified price update: discriminator, write authority, verification level, price message
+        let feed_id = [7u8; 32];
+        let mut data = hash(b"account:PriceUpdateV2").to_bytes()[..8].to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.push(1);
+        data.extend_from_slice(&feed_id);
+        data.extend_from_slice(&15_000_000_000i64.to_le_bytes());
+        data.extend_from_slice(&10_000_000u64.to_le_bytes());
+        data.extend_from_slice(&(-8i32).to_le_bytes());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&[0u8; 40]); //prev publish time, ema price and conf, posted slot
+        
+        let (feed, price) = parse_pyth_price_update(&data).unwrap();
+        assert_eq!(feed, Pubkey::new_from_array(feed_id));
+        assert_eq!(price, OraclePrice { price: 15_000_000_000, conf: 10_000_000, expo: -8, publish_time: 1_700_000_000 });
+        
+        //partially verified updates (level tag 0, followed by a signature count) are rejected
+        let mut partial = data.clone();
+        partial[40] = 0;
+        assert!(parse_pyth_price_update(&partial).is_err());
+        
+        //so are other accounts and truncated updates
+        let mut other = data.clone();
+        other[0] ^= 1;
+        assert!(parse_pyth_price_update(&other).is_err());
+        assert!(parse_pyth_price_update(&data[..80]).is_err());
+    }
+    #[test]
+    fn test_switchboard_pull_feed() {
+        use crate::instructions::oracle::parse_switchboard_pull_feed;
+        use anchor_lang::solana_program::hash::hash;
+        
+        //a pull feed whose current result ($150, std dev $0.10) was taken from submission 3 at slot 42
+        let mut data = vec![0u8; 3208];
+        data[..8].copy_from_slice(&hash(b"account:PullFeedAccountData").to_bytes()[..8]);
+        data[2264..2280].copy_from_slice(&150_000_000_000_000_000_000i128.to_le_bytes());
+        data[2280..2296].copy_from_slice(&100_000_000_000_000_000i128.to_le_bytes());
+        data[2361] = 3;
+        data[2368..2376].copy_from_slice(&42u64.to_le_bytes());
+        data[2976..2984].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        
+        let price = parse_switchboard_pull_feed(&data).unwrap();
+        assert_eq!(price, OraclePrice { price: 150_000_000_000_000, conf: 100_000_000_000, expo: -12, publish_time: 1_700_000_000 });
+        
+        //feeds without a result are rejected
+        let mut empty = data.clone();
+        empty[2368..2376].fill(0);
+        assert!(parse_switchboard_pull_feed(&empty).is_err());
+        
+        //so are other accounts and truncated feeds
+        let mut other = data.clone();
+        other[0] ^= 1;
+        assert!(parse_switchboard_pull_feed(&other).is_err());
+        assert!(parse_switchboard_pull_feed(&data[..2400]).is_err());
+    }
+    #[test]
+    fn test_swap_bounds() {
+        use crate::instructions::swap::{check_price_impact, SwapBounds};
+        
+        //1% slippage on a 1,000,000 quote accepts 990,000 or more
+        let bounds = SwapBounds { quoted_amount_out: 1_000_000, slippage_bps: 100 };
+        assert_eq!(bounds.min_amount_out().unwrap(), 990_000);
+        assert!(bounds.validate(constants::DEFAULT_MAX_SWAP_SLIPPAGE_BPS).is_ok());
+        
+        //quotes looser than the protocol limit, or empty, are rejected
+        assert!(bounds.validate(50).is_err());
+        assert!(SwapBounds { quoted_amount_out: 0, slippage_bps: 0 }.validate(100).is_err());
+        
+        //outputs more than the allowed impact below the fair amount are rejected
+        assert!(check_price_impact(970_000, 1_000_000, 300).is_ok());
+        assert!(check_price_impact(969_999, 1_000_000, 300).is_err());
+    }
+    
+    #[test]
+    fn test_event_layouts() {
+        use anchor_lang::{Discriminator, Event};
+        use crate::instructions::asym_escrow::{EscrowReleasedEvent, EscrowStatusChangedEvent};
+        use crate::state::escrow::EscrowStatus;
+        use constants::{EVENT_RESERVED_BYTES, EVENT_VERSION};
+        
+        //every event starts with its version and ends with reserved padding
+        let event = EscrowStatusChangedEvent {
+            version: EVENT_VERSION,
+            escrow_id: [7; 32],
+            seq: 3,
+            escrow: Pubkey::new_unique(),
+            old: EscrowStatus::Active,
+            new: EscrowStatus::Completed,
+            actor: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        };
+        let data = event.data();
+        assert_eq!(&data[..8], EscrowStatusChangedEvent::DISCRIMINATOR);
+        assert_eq!(data[8], EVENT_VERSION);
+        assert_eq!(data.len(), 8 + 1 + 32 + 8 + 32 + 1 + 1 + 32 + 8 + EVENT_RESERVED_BYTES);
+        assert_eq!(&data[9..41], &[7; 32]);
+        assert_eq!(&data[41..49], &3u64.to_le_bytes());
+        
+        //the escrow account follows the sequence number so consumers can load it directly
+        assert_eq!(&data[49..81], event.escrow.as_ref());
+        
+        //decoding round-trips
+        let decoded = EscrowStatusChangedEvent::try_from_slice(&data[8..]).unwrap();
+        assert_eq!(decoded.seq, 3);
+        assert_eq!(decoded.new, EscrowStatus::Completed);
+        assert_eq!(decoded.actor, event.actor);
+        assert_eq!(decoded.escrow, event.escrow);
+        
+        //pin the release event layout: amounts follow the escrow id, sequence number and escrow account
+        let event = EscrowReleasedEvent {
+            version: EVENT_VERSION,
+            escrow_id: [1; 32],
+            seq: 9,
+            escrow: Pubkey::new_unique(),
+            amount: 1_000,
+            fee: 10,
+            amount_delivered: 990,
+            decimals: 9,
+            reserved: [0; EVENT_RESERVED_BYTES],
+        };
+        let data = event.data();
+        assert_eq!(data.len(), 8 + 1 + 32 + 8 + 32 + 8 + 8 + 8 + 1 + EVENT_RESERVED_BYTES);
+        assert_eq!(&data[81..89], &1_000u64.to_le_bytes());
+        assert_eq!(&data[89..97], &10u64.to_le_bytes());
+        assert_eq!(&data[97..105], &990u64.to_le_bytes());
+        assert_eq!(data[105], 9);
+        assert_eq!(&data[106..], &[0; EVENT_RESERVED_BYTES]);
+        
+        //pin the settlement hook interface: escrow key, outcome, then the amounts
+        let escrow = Pubkey::new_unique();
+        let data = settlement_hook_data(&escrow, SettlementOutcome::Refund, 1_000, 0, 1_000).unwrap();
+        assert_eq!(data.len(), 8 + 32 + 1 + 8 + 8 + 8);
+        assert_eq!(&data[..8], &anchor_lang::solana_program::hash::hash(b"global:on_escrow_settled").to_bytes()[..8]);
+        assert_eq!(&data[8..40], escrow.as_ref());
+        assert_eq!(data[40], SettlementOutcome::Refund as u8);
+        assert_eq!(&data[41..49], &1_000u64.to_le_bytes());
+        assert_eq!(&data[49..57], &0u64.to_le_bytes());
+        assert_eq!(&data[57..65], &1_000u64.to_le_bytes());
+        
+        //automation triggers crank real instructions: their names must derive the instructions' discriminators
+        let crank_discriminator = |kind: AutomationTriggerKind| {
+            anchor_lang::solana_program::hash::hash(format!("global:{}", kind.crank_instruction()).as_bytes()).to_bytes()[..8].to_vec()
+        };
+        assert_eq!(crank_discriminator(AutomationTriggerKind::Expiry), instruction::ExpireEscrowAsym::DISCRIMINATOR);
+        assert_eq!(crank_discriminator(AutomationTriggerKind::DisputeTimeout), instruction::ResolveExpiredDisputeAsym::DISCRIMINATOR);
+        assert_eq!(crank_discriminator(AutomationTriggerKind::InterestAccrual), instruction::AccrueInterestAsym::DISCRIMINATOR);
+        assert!(AutomationTriggerKind::InterestAccrual.is_recurring() && !AutomationTriggerKind::Expiry.is_recurring());
+    }    
+    #[test]
+    fn test_partner_platform() {
+        use anchor_lang::AccountSerialize;
+        use crate::state::platform::Platform;
+        
+        //a partner platform is keyed by a PDA only its own program can sign for
+        let partner_program = Pubkey::new_unique();
+        let partner_authority = Platform::partner_authority(&partner_program);
+        assert_eq!(
+            partner_authority,
+            Pubkey::find_program_address(&[constants::seeds::PARTNER_AUTHORITY], &partner_program).0
+        );
+        assert_ne!(partner_authority, Platform::partner_authority(&Pubkey::new_unique()));
+        assert!(!partner_authority.is_on_curve());
+        
+        //a serialized platform should fit its allocated space exactly
+        let platform = Platform {
+            authority: partner_authority,
+            fee_share_bps: 2_000,
+            registered_at: 1_700_000_000,
+            bump: 255,
+            program: partner_program,
         };
+        assert!(platform.is_partner_program());
+        let mut data = Vec::new();
+        platform.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Platform::space());
+        assert!(!Platform { program: Pubkey::default(), ..platform }.is_partner_program());
+    }
+    #[test]
+    fn test_admin_audit_trail() {
+        use anchor_lang::AccountSerialize;
+        use crate::state::admin_audit::{AdminActionKind, AdminAuditEntry};
+        
+        //a serialized entry should fit its allocated space exactly
+        let entry = AdminAuditEntry {
+            index: 4,
+            actor: Pubkey::new_unique(),
+            action: AdminActionKind::Pause,
+            params_hash: [3; 32],
+            slot: 250_000_000,
+            timestamp: 1_700_000_000,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        entry.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), AdminAuditEntry::space());
+        
+        //entries are append-only: each index gets its own PDA
+        let (first, _) = Pubkey::find_program_address(&[constants::seeds::ADMIN_AUDIT, &0u64.to_le_bytes()], &ID);
+        let (second, _) = Pubkey::find_program_address(&[constants::seeds::ADMIN_AUDIT, &1u64.to_le_bytes()], &ID);
+        assert_ne!(first, second);
+        
+        //config updates hash their full params, so different updates are distinguishable
+        let pause = instructions::initialize::UpdateProgramConfigParams { paused: Some(true), ..Default::default() };
+        let bond = instructions::initialize::UpdateProgramConfigParams { dispute_bond: Some(1), ..Default::default() };
+        let pause_hash = anchor_lang::solana_program::hash::hashv(&[&anchor_lang::AnchorSerialize::try_to_vec(&pause).unwrap()]);
+        let bond_hash = anchor_lang::solana_program::hash::hashv(&[&anchor_lang::AnchorSerialize::try_to_vec(&bond).unwrap()]);
+        assert_ne!(pause_hash, bond_hash);
+    }
+    #[test]
+    fn test_governance_authority() {
+        use crate::instructions::utils::realms_native_treasury;
+        use crate::state::admin_audit::AdminActionKind;
+        
+        //a governance holds the authority through its native treasury, a Realms PDA
+        let governance = Pubkey::new_unique();
+        let treasury = realms_native_treasury(&governance);
+        assert_eq!(
+            treasury,
+            Pubkey::find_program_address(&[b"native-treasury", governance.as_ref()], &constants::SPL_GOVERNANCE_PROGRAM_ID).0
+        );
+        assert!(!treasury.is_on_curve());
+        assert_ne!(treasury, realms_native_treasury(&Pubkey::new_unique()));
+        
+        //handovers are audited under their own kind, appended after the existing ones
+        assert_eq!(anchor_lang::AnchorSerialize::try_to_vec(&AdminActionKind::TransferAuthority).unwrap(), vec![9]);
+    }
+    
+    #[test]
+    fn test_lookup_table() {
+        use crate::instructions::lookup_table::*;
+        
+        //the table is derived from the program's lookup table authority and the recent slot
+        let lookup_table_authority = Pubkey::find_program_address(&[constants::seeds::LOOKUP_TABLE_AUTHORITY], &crate::ID).0;
+        let (lookup_table, bump) = lookup_table_address(42);
+        assert_eq!(
+            (lookup_table, bump),
+            Pubkey::find_program_address(&[lookup_table_authority.as_ref(), &42u64.to_le_bytes()], &constants::ADDRESS_LOOKUP_TABLE_PROGRAM_ID)
+        );
+        assert_ne!(lookup_table, lookup_table_address(43).0);
+        
+        //extensions are bincode-encoded: variant, u64 length, then the addresses
+        let mint = Pubkey::new_unique();
+        let platform = Pubkey::new_unique();
+        let data = extend_lookup_table_data(&[mint, platform]);
+        assert_eq!(&data[..4], &2u32.to_le_bytes());
+        assert_eq!(&data[4..12], &2u64.to_le_bytes());
+        assert_eq!(&data[12..44], mint.as_ref());
+        assert_eq!(&data[44..], platform.as_ref());
+        
+        //addresses already in the table, or repeated, are only added once
+        let mut table_data = vec![0u8; 56];
+        table_data.extend_from_slice(mint.as_ref());
+        assert_eq!(new_lookup_table_addresses(&table_data, &[mint, platform, platform]).unwrap(), vec![platform]);
+        assert!(new_lookup_table_addresses(&table_data, &[mint]).unwrap().is_empty());
+        assert!(new_lookup_table_addresses(&table_data[..40], &[mint]).is_err());
+    }
+    
+    #[test]
+    fn test_evm_event_mapping() {
+        use crate::evm_compat::{evm_topic, EvmEvent};
+        use crate::instructions::asym_escrow::{EscrowReleasedEvent, ReleaseAssentGivenEvent};
+        
+        //topic 0 is the keccak hash of the signature, as for ERC-20 transfers
+        assert_eq!(
+            evm_topic("Transfer(address,address,uint256)")[..4],
+            [0xdd, 0xf2, 0x52, 0xad]
+        );
+        
+        //the escrow id is indexed; amounts are big-endian uint256 words in the data
+        let released = EscrowReleasedEvent {
+            version: constants::EVENT_VERSION,
+            escrow_id: [3; 32],
+            seq: 7,
+            escrow: Pubkey::new_unique(),
+            amount: 990,
+            fee: 10,
+            amount_delivered: 990,
+            decimals: 6,
+            reserved: [0; constants::EVENT_RESERVED_BYTES],
+        };
+        let log = released.to_evm_log();
+        assert_eq!(log.topics, vec![evm_topic("EscrowReleased(bytes32,uint256,uint256)"), [3; 32]]);
+        assert_eq!(log.data.len(), 64);
+        assert_eq!(log.data[24..32], 990u64.to_be_bytes());
+        assert_eq!(log.data[56..64], 10u64.to_be_bytes());
+        
+        //addresses fill the whole word, enums become small integers
+        let assenting_address = Pubkey::new_unique();
+        let log = ReleaseAssentGivenEvent {
+            version: constants::EVENT_VERSION,
+            escrow_id: [3; 32],
+            seq: 8,
+            escrow: Pubkey::new_unique(),
+            assenting_address,
+            assent_type: ReleaseAssentType::Receiver,
+            reserved: [0; constants::EVENT_RESERVED_BYTES],
+        }.to_evm_log();
+        assert_eq!(log.topics[2], assenting_address.to_bytes());
+        assert_eq!(log.data[31], 1);
     }
 }
\ No newline at end of file
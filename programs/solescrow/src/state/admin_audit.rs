@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Kind of admin action recorded in the audit trail
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum AdminActionKind {
+    /// Program paused
+    Pause = 0,
+    /// Program unpaused
+    Unpause = 1,
+    /// Program configuration updated
+    #[default]
+    ConfigUpdate = 2,
+    /// Address added to the sanctions blocklist
+    BlockAddress = 3,
+    /// Address removed from the sanctions blocklist
+    UnblockAddress = 4,
+    /// Arbiter bond slashed
+    SlashArbiter = 5,
+    /// Insurance claim paid out of the fund
+    InsuranceClaim = 6,
+    /// Custodial platform registered
+    RegisterPlatform = 7,
+    /// Custodial platform deregistered
+    DeregisterPlatform = 8,
+    /// Program authority handed to a new key or governance
+    TransferAuthority = 9,
+    /// Hot account lookup table created or extended
+    LookupTableUpdate = 10,
+}
+
+/// Audit trail entry for one admin action
+/// 
+/// Entries are append-only PDAs indexed by the program config's admin action counter, so auditors can 
+/// enumerate every admin action from on-chain state without relying on transaction log retention. The 
+/// parameters are stored as a hash of the instruction arguments.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct AdminAuditEntry {
+    /// Position of the entry in the audit trail
+    pub index: u64,
+    /// Authority that performed the action
+    pub actor: Pubkey,
+    /// Action performed
+    pub action: AdminActionKind,
+    /// Hash of the action's parameters
+    pub params_hash: [u8; 32],
+    /// Slot the action was performed in
+    pub slot: u64,
+    /// Timestamp the action was performed at
+    pub timestamp: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AdminAuditEntry {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ALLOWED_COUNTERPARTIES;
+
+/// Per-address counterparty allowlist
+/// 
+/// Opt-in procurement policy: once an address creates its allowlist, escrows can only be created with it 
+/// as payer if the receiver is on the list. Closing the allowlist lifts the restriction.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct CounterpartyAllowlist {
+    /// Address whose escrows the allowlist restricts
+    pub owner: Pubkey,
+    /// Receivers the owner may enter escrows with
+    #[max_len(MAX_ALLOWED_COUNTERPARTIES)]
+    pub counterparties: Vec<Pubkey>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CounterpartyAllowlist {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Check if a receiver is on the allowlist
+    pub fn allows(&self, counterparty: &Pubkey) -> bool {
+        self.counterparties.contains(counterparty)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_PANEL_SIZE, MAX_ARBITER_POOL_SIZE};
+
+/// Registered arbiter account
+/// 
+/// Holds the arbiter's staked bond as lamports on the account itself. The bond backs the arbiter's 
+/// awards: if an award is proven fraudulent, the program authority can slash it to the wronged party.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ArbiterAccount {
+    /// Arbiter's wallet address
+    pub arbiter: Pubkey,
+    /// Currently staked bond (lamports, excluding rent)
+    pub bond: u64,
+    /// Total bond slashed over the arbiter's lifetime
+    pub slashed: u64,
+    /// Timestamp when the arbiter registered
+    pub registered_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ArbiterAccount {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
+
+/// Arbiter pool entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct ArbiterPoolEntry {
+    /// Registered arbiter
+    pub arbiter: Pubkey,
+    /// Arbiter's current bond, used as selection weight
+    pub stake: u64,
+}
+
+/// Arbiter pool account
+/// 
+/// Global list of registered arbiters and their stakes, from which juries are drawn for escrows that 
+/// don't name an arbiter up front.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ArbiterPool {
+    /// Registered arbiters
+    #[max_len(MAX_ARBITER_POOL_SIZE)]
+    pub entries: Vec<ArbiterPoolEntry>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ArbiterPool {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Find an arbiter's entry
+    pub fn entry_mut(&mut self, arbiter: &Pubkey) -> Option<&mut ArbiterPoolEntry> {
+        self.entries.iter_mut().find(|e| e.arbiter == *arbiter)
+    }
+}
+
+/// Single panel member's vote
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct PanelVote {
+    /// Voting panel member
+    pub voter: Pubkey,
+    /// Amount the member voted to award back to the payer
+    pub payer_amount: u64,
+}
+
+/// Arbiter panel account
+/// 
+/// Panel of arbiters assigned to an escrow in place of a single arbiter's decision. Each member votes 
+/// on a split of the disputed funds, and the award is made once `threshold` members agree on the same 
+/// split. The escrow's arbiter of record still receives the arbitration fee.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ArbiterPanel {
+    /// Escrow the panel is assigned to
+    pub escrow: Pubkey,
+    /// Panel members
+    #[max_len(MAX_PANEL_SIZE)]
+    pub members: Vec<Pubkey>,
+    /// Number of matching votes required for an award
+    pub threshold: u8,
+    /// Votes cast on the escrow's dispute
+    #[max_len(MAX_PANEL_SIZE)]
+    pub votes: Vec<PanelVote>,
+    /// Account that paid the rent, refunded when the panel is garbage collected
+    pub rent_payer: Pubkey,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ArbiterPanel {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Check if an address is a panel member
+    pub fn is_member(&self, addr: &Pubkey) -> bool {
+        self.members.contains(addr)
+    }
+
+    /// Count votes for a given split
+    pub fn votes_for(&self, payer_amount: u64) -> usize {
+        self.votes.iter().filter(|v| v.payer_amount == payer_amount).count()
+    }
+}
+
+/// Arbitration vote account, one per panel member per dispute
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ArbitrationVote {
+    /// Dispute voted on
+    pub dispute: Pubkey,
+    /// Voting panel member
+    pub voter: Pubkey,
+    /// Amount the member voted to award back to the payer
+    pub payer_amount: u64,
+    /// Hash of the member's off-chain rationale
+    pub rationale_hash: [u8; 32],
+    /// Timestamp of the vote
+    pub timestamp: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ArbitrationVote {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+/// Timed action an automation trigger cranks
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace, Default)]
+pub enum AutomationTriggerKind {
+    /// Mark the escrow expired once its end time has passed
+    #[default]
+    Expiry = 0,
+    /// Apply the default resolution once the arbiter misses the decision deadline
+    DisputeTimeout = 1,
+    /// Crystallize interest on the unpaid balance at a fixed interval after the due date
+    InterestAccrual = 2,
+}
+
+impl AutomationTriggerKind {
+    /// Escrow program instruction the automation framework calls when the trigger fires
+    pub fn crank_instruction(&self) -> &'static str {
+        match self {
+            AutomationTriggerKind::Expiry => "expire_escrow_asym",
+            AutomationTriggerKind::DisputeTimeout => "resolve_expired_dispute_asym",
+            AutomationTriggerKind::InterestAccrual => "accrue_interest_asym",
+        }
+    }
+
+    /// Check if the trigger fires repeatedly rather than once
+    pub fn is_recurring(&self) -> bool {
+        *self == AutomationTriggerKind::InterestAccrual
+    }
+}
+
+/// When an automation trigger fires
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct AutomationSchedule {
+    /// Timestamp of the first firing
+    pub start_at: i64,
+    /// Seconds between firings (0 = fires once)
+    pub interval: i64,
+}
+
+/// Timed action of an escrow registered with an on-chain automation framework
+///
+/// The trigger PDA owns the framework's thread for the action and signs its registration, so the framework
+/// can tell the escrow program registered it. Keepers of the framework then crank the action on schedule,
+/// without the parties running jobs of their own.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct AutomationTrigger {
+    /// Escrow the action is for
+    pub escrow: Pubkey,
+    /// Action cranked when the trigger fires
+    pub kind: AutomationTriggerKind,
+    /// Automation framework program the trigger is registered with
+    pub automation_program: Pubkey,
+    /// Framework account (thread or task) that fires the action
+    pub thread: Pubkey,
+    /// When the action fires
+    pub schedule: AutomationSchedule,
+    /// Address that registered the trigger and paid its rent
+    pub registered_by: Pubkey,
+    /// Timestamp when the trigger was registered
+    pub registered_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AutomationTrigger {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
\ No newline at end of file
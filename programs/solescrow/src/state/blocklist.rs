@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Blocklist entry for a sanctioned address
+/// 
+/// One PDA per blocked address, managed by the program authority. Fund movements check the PDAs of the 
+/// addresses involved; an address is blocked exactly while its entry exists.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct BlockedAddress {
+    /// Blocked address
+    pub addr: Pubkey,
+    /// Timestamp the address was blocked
+    pub blocked_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl BlockedAddress {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
\ No newline at end of file
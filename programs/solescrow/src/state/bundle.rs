@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_BUNDLE_ASSETS;
+
+/// Asset held in an escrow bundle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct BundleAsset {
+    /// Token mint of the asset (SPL token or NFT)
+    pub mint: Pubkey,
+    /// Required amount of the asset
+    pub amount: u64,
+    /// Amount received by the vault (net of token transfer fees)
+    pub amount_deposited: u64,
+    /// Vault-owned token account holding the asset (Pubkey::default() = not yet deposited)
+    pub vault: Pubkey,
+}
+
+/// Escrow bundle account
+/// 
+/// Additional assets held by an escrow alongside its primary currency (e.g. 500 USDC + 2 NFTs). Each 
+/// asset sits in its own token account owned by the escrow vault, and once the escrow completes the 
+/// whole bundle settles atomically: to the receiver if the escrow was released, back to the payer if 
+/// it was refunded.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct EscrowBundle {
+    /// Escrow the bundle belongs to
+    pub escrow: Pubkey,
+    /// Assets in the bundle
+    #[max_len(MAX_BUNDLE_ASSETS)]
+    pub assets: Vec<BundleAsset>,
+    /// Whether the bundle has been settled
+    pub settled: bool,
+    /// Account that paid the rent, refunded when the bundle is garbage collected
+    pub rent_payer: Pubkey,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl EscrowBundle {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Check if every asset in the bundle has been deposited
+    pub fn is_fully_deposited(&self) -> bool {
+        self.assets.iter().all(|a| a.vault != Pubkey::default())
+    }
+}
\ No newline at end of file
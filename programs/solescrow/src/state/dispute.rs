@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_EVIDENCE_PER_DISPUTE, MAX_MEDIATION_ENTRIES};
+use crate::state::program_config::DefaultResolution;
+use crate::errors::EscrowError;
+
+/// Escrow party enumeration, used to record who an award went against
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum DisputeParty {
+    Payer = 0,
+    Receiver = 1,
+}
+
+/// Dispute lifecycle stage
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum DisputeStatus {
+    /// Parties are submitting evidence
+    #[default]
+    EvidencePhase = 0,
+    /// Evidence is closed and the arbiter is deciding
+    UnderReview = 1,
+    /// An award has been made and awaits execution (or appeal)
+    Awarded = 2,
+    /// The award has been appealed to the appeal arbiter
+    Appealed = 3,
+    /// The award has been executed
+    Resolved = 4,
+}
+
+impl DisputeStatus {
+    /// Check if moving to the given status is a valid lifecycle step
+    pub fn can_transition_to(&self, to: DisputeStatus) -> bool {
+        matches!(
+            (self, to),
+            (DisputeStatus::EvidencePhase, DisputeStatus::UnderReview)
+                | (DisputeStatus::UnderReview, DisputeStatus::Awarded)
+                | (DisputeStatus::Awarded, DisputeStatus::Appealed)
+                | (DisputeStatus::Awarded, DisputeStatus::Resolved)
+                | (DisputeStatus::Awarded, DisputeStatus::EvidencePhase) // escalated automated outcome
+                | (DisputeStatus::Appealed, DisputeStatus::Awarded)
+        )
+    }
+}
+
+/// Hash scheme used for an evidence commitment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum EvidenceHashScheme {
+    Sha256 = 0,
+    Keccak256 = 1,
+    Blake3 = 2,
+}
+
+/// Commitment to a piece of off-chain evidence
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct EvidenceCommitment {
+    /// Party that submitted the evidence
+    pub submitted_by: Pubkey,
+    /// Hash of the evidence content
+    pub content_hash: [u8; 32],
+    /// Hash scheme used to produce content_hash
+    pub scheme: EvidenceHashScheme,
+    /// Timestamp when the commitment was submitted
+    pub timestamp: i64,
+}
+
+/// Dispute account
+/// 
+/// Opened by either party of an escrow with an assigned arbiter. While a dispute is open the escrow is 
+/// held in `EscrowStatus::Arbitration`, and consent-based release and refunds are blocked until the 
+/// award is executed. Both parties post a dispute bond: the prevailing party's bond is returned, and the 
+/// losing party's bond covers arbitration costs. If the escrow has an appeal arbiter, the losing party may appeal the award within 
+/// the appeal window; the appeal arbiter's award is final.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Dispute {
+    /// Escrow under dispute
+    pub escrow: Pubkey,
+    /// Party that opened the dispute
+    pub opened_by: Pubkey,
+    /// Arbiter responsible for the award
+    pub arbiter: Pubkey,
+    /// Timestamp when the dispute was opened
+    pub opened_at: i64,
+    /// Deadline for submitting evidence
+    pub evidence_deadline: i64,
+    /// Deadline for the arbiter's decision
+    pub decision_deadline: i64,
+    /// Resolution applied if the arbiter misses the decision deadline
+    pub default_resolution: DefaultResolution,
+    /// Bond each party posts to take part in the dispute (lamports)
+    pub bond_amount: u64,
+    /// Bond posted by the payer, held on this account
+    pub payer_bond: u64,
+    /// Bond posted by the receiver, held on this account
+    pub receiver_bond: u64,
+    /// Whether the dispute was settled by the default resolution
+    pub defaulted: bool,
+    /// Evidence commitments submitted by the parties
+    #[max_len(MAX_EVIDENCE_PER_DISPUTE)]
+    pub evidence: Vec<EvidenceCommitment>,
+    /// Lifecycle stage of the dispute
+    pub status: DisputeStatus,
+    /// Timestamp of the latest award
+    pub awarded_at: i64,
+    /// Deadline for appealing the award
+    pub appeal_deadline: i64,
+    /// Whether the award has been appealed
+    pub appealed: bool,
+    /// Party that appealed the award
+    pub appellant: Pubkey,
+    /// Appeal bond held on this account (lamports)
+    pub appeal_bond: u64,
+    /// Whether the award can no longer be appealed
+    pub final_award: bool,
+    /// Amount awarded back to the payer (net of arbitration fee)
+    pub payer_award: u64,
+    /// Amount awarded to the receiver (net of arbitration fee)
+    pub receiver_award: u64,
+    /// Arbitration fee paid to the arbiter
+    pub arbitration_fee: u64,
+    /// Party the award went against (None if evenly split)
+    pub award_against: Option<DisputeParty>,
+    /// Hash of the arbiter's off-chain award rationale
+    pub rationale_hash: [u8; 32],
+    /// Arbitration fee deposited by the payer in the fee token
+    pub payer_fee_deposit: u64,
+    /// Arbitration fee deposited by the receiver in the fee token
+    pub receiver_fee_deposit: u64,
+    /// Whether the current award is an automated settler's outcome awaiting escalation
+    pub automated_outcome: bool,
+    /// Whether an automated outcome was escalated to human arbitration
+    pub escalated: bool,
+    /// Hidden agreed amount of the escrow, once disclosed for the dispute
+    pub disclosed_amount: Option<u64>,
+    /// Account that paid the rent, refunded when the dispute is garbage collected
+    pub rent_payer: Pubkey,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Dispute {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Check if the dispute is still awaiting an award
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, DisputeStatus::EvidencePhase | DisputeStatus::UnderReview)
+    }
+
+    /// Close the evidence phase once its deadline has passed
+    pub fn advance_phase(&mut self, now: i64) {
+        if self.status == DisputeStatus::EvidencePhase && now > self.evidence_deadline {
+            self.status = DisputeStatus::UnderReview;
+        }
+    }
+
+    /// Move the dispute to the given status, rejecting invalid lifecycle steps
+    pub fn transition(&mut self, to: DisputeStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(to),
+            EscrowError::InvalidDisputeTransition
+        );
+        self.status = to;
+        Ok(())
+    }
+}
+
+/// Kind of entry in a mediation log
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum MediationEntryKind {
+    /// Hashed off-chain note
+    Note = 0,
+    /// Proposed settlement split
+    SettlementOffer = 1,
+}
+
+/// Timestamped entry in a mediation log
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct MediationEntry {
+    /// Payer, receiver or arbiter that appended the entry
+    pub author: Pubkey,
+    /// Note or settlement offer
+    pub kind: MediationEntryKind,
+    /// Hash of the off-chain note content
+    pub content_hash: [u8; 32],
+    /// Amount offered back to the payer (settlement offers only)
+    pub payer_amount: u64,
+    /// Timestamp when the entry was appended
+    pub timestamp: i64,
+}
+
+/// Mediation log account
+/// 
+/// Append-only record of pre-award negotiation on a dispute, shared by the parties and the arbiter, 
+/// and kept as a reviewable record for appeals.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct MediationLog {
+    /// Dispute the log belongs to
+    pub dispute: Pubkey,
+    /// Entries in the order they were appended
+    #[max_len(MAX_MEDIATION_ENTRIES)]
+    pub entries: Vec<MediationEntry>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl MediationLog {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
\ No newline at end of file
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::{BPS_DENOMINATOR, MAX_ACCEPTED_MINTS, MAX_CO_SIGNERS, MAX_OBSERVERS, MAX_RECOVERY_ADDRESSES, SECONDS_PER_YEAR};
 
 /// Escrow status enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
 pub enum EscrowStatus {
     /// Escrow has been created, but no payment has been made
     Pending = 0,
@@ -11,6 +12,8 @@ pub enum EscrowStatus {
     Completed = 2,
     /// Escrow has an arbitration proposal pending
     Arbitration = 3,
+    /// Escrow passed its end time before completing; funds can only be refunded or disputed
+    Expired = 4,
 }
 
 impl Default for EscrowStatus {
@@ -20,12 +23,16 @@ impl Default for EscrowStatus {
 }
 
 /// Currency type enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
 pub enum CurrencyType {
     /// Native SOL
     Native = 0,
     /// SPL Token
     SplToken = 1,
+    /// Metaplex NFT (amount 1, decimals 0), held in a vault-owned token account
+    Nft = 2,
+    /// Metaplex programmable NFT, moved through the token metadata program to respect its rule set
+    ProgrammableNft = 3,
 }
 
 impl Default for CurrencyType {
@@ -34,15 +41,182 @@ impl Default for CurrencyType {
     }
 }
 
+/// Oracle a USD-denominated escrow is priced by
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum OracleSource {
+    /// Pyth price account
+    #[default]
+    Pyth = 0,
+    /// Switchboard on-demand pull feed
+    Switchboard = 1,
+    /// Pyth pull oracle price update, posted through the Pyth receiver in the same transaction
+    PythPull = 2,
+}
+
+/// Price read from an oracle feed, normalized across oracle sources
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct OraclePrice {
+    /// Price, scaled by 10^expo
+    pub price: i64,
+    /// Confidence interval, scaled by 10^expo
+    pub conf: u64,
+    /// Price exponent
+    pub expo: i32,
+    /// Timestamp the price was published at
+    pub publish_time: i64,
+}
+
+/// Attestation both parties of a regulated escrow must hold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum AttestationKind {
+    /// No attestation required
+    #[default]
+    None = 0,
+    /// Solana Attestation Service attestation under a credential and schema
+    Sas = 1,
+    /// Designated soulbound (non-transferable) token
+    SoulboundToken = 2,
+}
+
 /// Release assent type for asymmetric escrows
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
 pub enum ReleaseAssentType {
     Payer = 0,
     Receiver = 1,
 }
 
+/// Arbitration fee type enumeration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum ArbitrationFeeType {
+    /// Fee is a share of the disputed amount, in basis points
+    #[default]
+    Bps = 0,
+    /// Fee is a flat amount in the escrow currency
+    Flat = 1,
+}
+
+/// Arbitration fee terms, fixed at escrow creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct ArbitrationFee {
+    /// How the fee value is interpreted
+    pub fee_type: ArbitrationFeeType,
+    /// Basis points or flat amount, depending on fee_type
+    pub value: u64,
+}
+
+impl ArbitrationFee {
+    /// Calculate the fee owed on a disputed amount (never more than the amount itself)
+    pub fn calculate(&self, disputed_amount: u64) -> Option<u64> {
+        let fee = match self.fee_type {
+            ArbitrationFeeType::Bps => disputed_amount
+                .checked_mul(self.value)?
+                .checked_div(crate::constants::BPS_DENOMINATOR)?,
+            ArbitrationFeeType::Flat => self.value,
+        };
+        Some(fee.min(disputed_amount))
+    }
+}
+
+/// How disputes on an escrow are decided
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum ArbiterKind {
+    /// The arbiter decides alone
+    #[default]
+    Individual = 0,
+    /// An assigned arbiter panel votes on the award
+    Panel = 1,
+    /// A jury drawn from the arbiter pool votes on the award
+    Jury = 2,
+    /// A Realms governance account awards through an executed proposal
+    Governance = 3,
+}
+
+/// Kind of deal an escrow is for, set at creation for analytics
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum EscrowCategory {
+    /// No category given
+    #[default]
+    Uncategorized = 0,
+    /// Payment for freelance work
+    Freelance = 1,
+    /// Sale of goods
+    GoodsSale = 2,
+    /// Rental or deposit
+    Rental = 3,
+    /// Bounty payout
+    Bounty = 4,
+    /// Any other kind of deal
+    Other = 5,
+}
+
+/// Fee terms copied from the program config (and creating platform) when an escrow is created
+/// 
+/// Later config changes don't apply to existing escrows; the protocol fee rate itself is kept in 
+/// `AsymEscrow::fee_bps`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct FeeTerms {
+    /// Share of native protocol fees diverted to the insurance fund (bps)
+    pub insurance_fee_bps: u16,
+    /// Share of lending yield taken by the protocol (bps)
+    pub yield_fee_bps: u16,
+    /// Bond each disputing party posts (lamports)
+    pub dispute_bond: u64,
+    /// Share of the protocol fee due to the creating platform (bps)
+    pub platform_fee_share_bps: u16,
+}
+
+/// Payment mint an escrow accepts, with the amount owed in it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub struct AcceptedMint {
+    /// Token mint
+    pub mint: Pubkey,
+    /// Amount owed when paying in this mint
+    pub amount: u64,
+}
+
+/// Key co-signing a party's release consent, with its assent record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub struct CoSigner {
+    /// Co-signer's key
+    pub key: Pubkey,
+    /// Whether this co-signer has assented to release
+    pub assented: bool,
+}
+
+/// Named observer key with its permitted read-oriented actions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub struct Observer {
+    /// Observer's key
+    pub key: Pubkey,
+    /// Observer's name (e.g. the auditing team), UTF-8 padded with zeros
+    pub name: [u8; 32],
+    /// Permitted actions (bitmask of observer_permissions)
+    pub permissions: u8,
+}
+
+/// Social recovery settings for a party's key, with any pending rotation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, InitSpace)]
+pub struct PartyRecovery {
+    /// Addresses that can propose rotating the party's key if it is lost
+    #[max_len(MAX_RECOVERY_ADDRESSES)]
+    pub recovery_addresses: Vec<Pubkey>,
+    /// Seconds a proposed rotation waits, during which the party's current key can veto it
+    pub delay: i64,
+    /// Key the party's address is being rotated to (Pubkey::default() = none pending)
+    pub pending_addr: Pubkey,
+    /// Timestamp after which the pending rotation can be executed
+    pub executable_at: i64,
+}
+
+impl PartyRecovery {
+    /// Check if a rotation of the party's key is pending
+    pub fn is_pending(&self) -> bool {
+        self.pending_addr != Pubkey::default()
+    }
+}
+
 /// Escrow party data structure
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, InitSpace)]
 pub struct EscrowParty {
     /// Party's wallet address
     pub addr: Pubkey,
@@ -56,10 +230,47 @@ pub struct EscrowParty {
     pub amount_refunded: u64,
     /// Amount released to the other party
     pub amount_released: u64,
-    /// Amount paid by this party
+    /// Amount paid by this party (net of token transfer fees, as received by the vault)
     pub amount_paid: u64,
     /// Whether this party has given release consent
     pub released: bool,
+    /// Amount actually delivered to the other party (net of token transfer fees)
+    pub amount_delivered: u64,
+    /// Decimals of the currency, fixed at creation (NATIVE_DECIMALS for native SOL, 0 for NFTs)
+    pub decimals: u8,
+    /// Hot wallet allowed to act for this party (Pubkey::default() = none)
+    pub delegate: Pubkey,
+    /// Largest amount the delegate can deposit or consent to release in a single action
+    pub delegate_limit: u64,
+    /// Short-lived key a front-end can act with during a session (Pubkey::default() = none)
+    pub session_key: Pubkey,
+    /// Actions the session key may perform (bitmask of session_permissions)
+    pub session_permissions: u8,
+    /// Slot after which the session key stops working
+    pub session_expiry_slot: u64,
+    /// Nonce of the next intent this party signs for a relayer to submit
+    pub relay_nonce: u64,
+}
+
+impl EscrowParty {
+    /// Check if a signer can act for this party on an amount, as the party itself or its delegate within its limit
+    pub fn is_authorized(&self, signer: &Pubkey, amount: u64) -> bool {
+        *signer == self.addr
+            || (self.delegate != Pubkey::default() && *signer == self.delegate && amount <= self.delegate_limit)
+    }
+
+    /// Check if a signer is this party's unexpired session key with a permission
+    pub fn has_session(&self, signer: &Pubkey, permission: u8, slot: u64) -> bool {
+        self.session_key != Pubkey::default()
+            && *signer == self.session_key
+            && self.session_permissions & permission == permission
+            && slot <= self.session_expiry_slot
+    }
+
+    /// Check if a signer can perform an action for this party, by key, delegate or session key
+    pub fn can_act(&self, signer: &Pubkey, amount: u64, permission: u8, slot: u64) -> bool {
+        self.is_authorized(signer, amount) || self.has_session(signer, permission, slot)
+    }
 }
 
 /// Asymmetrical escrow account
@@ -71,7 +282,7 @@ pub struct EscrowParty {
 /// off-chain component cannot be verified programmatically, arbitration mechanisms are essential for dispute 
 /// resolution when parties disagree about delivery or quality.
 #[account]
-#[derive(Debug)]
+#[derive(Debug, InitSpace)]
 pub struct AsymEscrow {
     /// Unique identifier for the escrow (derived from creator + nonce)
     pub id: [u8; 32],
@@ -97,24 +308,157 @@ pub struct AsymEscrow {
     pub nonce: u64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Arbiter who can settle disputes (Pubkey::default() = no arbitration)
+    pub arbiter: Pubkey,
+    /// Fee paid to the arbiter out of the escrowed funds when a dispute is awarded
+    pub arbitration_fee: ArbitrationFee,
+    /// Arbitration fee already paid out of the escrowed funds
+    pub arbitration_fee_paid: u64,
+    /// Second-tier arbiter who decides appeals (Pubkey::default() = awards are final)
+    pub appeal_arbiter: Pubkey,
+    /// How disputes are decided
+    pub arbiter_kind: ArbiterKind,
+    /// Oracle or bot that can propose an automated settlement (Pubkey::default() = none)
+    pub automated_settler: Pubkey,
+    /// Token the arbitration fee is paid in (Pubkey::default() = paid out of the escrowed funds)
+    pub arbitration_fee_mint: Pubkey,
+    /// How long after a release request a dispute can still be opened (seconds)
+    pub dispute_window: i64,
+    /// Timestamp of the first release assent (0 = not requested)
+    pub release_requested_at: i64,
+    /// Commitment to the agreed amount for escrows with hidden terms ([0; 32] = amount is public)
+    pub amount_commitment: [u8; 32],
+    /// Verified collection the escrowed NFT must belong to (Pubkey::default() = any)
+    pub nft_collection: Pubkey,
+    /// Obligation in USD, with USD_DECIMALS decimals (0 = denominated in the escrow currency)
+    pub usd_amount: u64,
+    /// Oracle price feed converting the USD amount to the escrow currency (the feed id for Pyth pull oracles)
+    pub price_feed: Pubkey,
+    /// Oracle the price feed belongs to
+    pub oracle_source: OracleSource,
+    /// Lowest USD price per whole token (USD_DECIMALS) accepted at settlement (0 = no floor)
+    pub price_floor: u64,
+    /// Highest USD price per whole token (USD_DECIMALS) accepted at settlement (0 = no ceiling)
+    pub price_ceiling: u64,
+    /// Whether settlement hit a price outside the collar, requiring fresh consent to the current rate
+    pub collar_breached: bool,
+    /// Lending adapter idle funds may be supplied to (Pubkey::default() = no lending)
+    pub lending_adapter: Pubkey,
+    /// Amount currently supplied to the lending adapter
+    pub lent_amount: u64,
+    /// Payer's share of the lending yield left after the protocol's share (bps); the rest goes to the receiver
+    pub yield_payer_bps: u16,
+    /// Stake pool the escrowed SOL is staked into
+    pub stake_pool: Pubkey,
+    /// SOL converted into the staking position (0 = not staked)
+    pub staked_lamports: u64,
+    /// Liquid staking tokens held for the position
+    pub lst_amount: u64,
+    /// Payment mints the payer can choose between at first deposit (empty = only the escrow currency)
+    #[max_len(MAX_ACCEPTED_MINTS)]
+    pub accepted_mints: Vec<AcceptedMint>,
+    /// Annual interest rate charged on the unpaid balance after the due date (bps, 0 = no interest)
+    pub interest_rate_bps: u16,
+    /// Timestamp after which the unpaid balance accrues interest
+    pub payment_due_time: i64,
+    /// Maximum total interest that can accrue, in the escrow currency
+    pub interest_cap: u64,
+    /// Interest crystallized into the payer's obligation so far
+    pub accrued_interest: u64,
+    /// Timestamp interest was last crystallized up to
+    pub interest_accrued_at: i64,
+    /// SHA-256 hash whose preimage releases the funds to the receiver ([0; 32] = not an HTLC)
+    pub hashlock: [u8; 32],
+    /// Timestamp after which an unclaimed HTLC can be reclaimed by the payer
+    pub timelock: i64,
+    /// Wormhole chain id of the emitter whose message releases the escrow (0 = no cross-chain trigger)
+    pub wormhole_emitter_chain: u16,
+    /// Wormhole emitter address whose message releases the escrow
+    pub wormhole_emitter: [u8; 32],
+    /// Receipt NFT minted to the receiver for their claim (Pubkey::default() = none)
+    pub receipt_mint: Pubkey,
+    /// Whether the receipt is a transferable claim token whose holder receives the payout
+    pub claim_transferable: bool,
+    /// Escrow the receiver's payout is deposited into (Pubkey::default() = paid to the receiver)
+    pub chained_escrow: Pubkey,
+    /// Number of escrows chained upstream of this one
+    pub chain_depth: u8,
+    /// Party whose release consent is given by its co-signers (Pubkey::default() = none)
+    pub co_signed_party: Pubkey,
+    /// Number of co-signer assents needed for the party's consent
+    pub co_signer_threshold: u8,
+    /// Keys co-signing the party's release consent
+    #[max_len(MAX_CO_SIGNERS)]
+    pub co_signers: Vec<CoSigner>,
+    /// Social recovery for the payer's key
+    pub payer_recovery: PartyRecovery,
+    /// Social recovery for the receiver's key
+    pub receiver_recovery: PartyRecovery,
+    /// Attestation both parties must hold at creation and settlement
+    pub attestation_kind: AttestationKind,
+    /// SAS credential, or soulbound token mint, the attestation must be issued under
+    pub attestation_issuer: Pubkey,
+    /// SAS schema the attestation must follow (SAS only)
+    pub attestation_schema: Pubkey,
+    /// Guardian that can hold a large release for a fixed window (Pubkey::default() = none)
+    pub guardian: Pubkey,
+    /// Release amount above which the guardian can hold a release
+    pub guardian_threshold: u64,
+    /// Timestamp until which the guardian holds large releases (0 = never held)
+    pub guardian_hold_until: i64,
+    /// Platform that created the escrow on the parties' behalf (Pubkey::default() = none)
+    pub platform: Pubkey,
+    /// Observer keys that can snapshot the escrow or append audit notes, but never move funds
+    #[max_len(MAX_OBSERVERS)]
+    pub observers: Vec<Observer>,
+    /// Number of audit notes appended by observers
+    pub audit_note_count: u32,
+    /// Sequence number of the escrow's last emitted event
+    pub event_seq: u64,
+    /// Kind of deal the escrow is for
+    pub category: EscrowCategory,
+    /// Fee terms in force when the escrow was created
+    pub fee_terms: FeeTerms,
+    /// Program called when the escrow is released or refunded (Pubkey::default() = none)
+    pub settlement_hook: Pubkey,
+    /// Whether deposits, releases and refunds write an SPL memo
+    pub memo_enabled: bool,
+    /// Client reference carried in the escrow's memos (UTF-8, zero-padded; all zeros = none)
+    pub client_ref: [u8; 32],
+    /// Oracle price the USD amount was last converted at, kept for audit (publish_time 0 = never converted)
+    pub quoted_price: OraclePrice,
+    /// Whether a call into an external program is in progress, locking deposits, releases and refunds
+    pub external_call_in_progress: bool,
 }
 
 impl AsymEscrow {
     /// Calculate space needed for account
     pub const fn space() -> usize {
-        8 + // discriminator
-        32 + // id
-        200 + // payer (EscrowParty)
-        200 + // receiver (EscrowParty)
-        8 + // timestamp
-        8 + // start_time
-        8 + // end_time
-        1 + // status
-        1 + // released
-        2 + // fee_bps
-        32 + // creator
-        8 + // nonce
-        1 // bump
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Set the escrow status, returning the previous status if it changed
+    pub fn set_status(&mut self, status: EscrowStatus) -> Option<EscrowStatus> {
+        let old = self.status;
+        self.status = status;
+        (old != status).then_some(old)
+    }
+
+    /// Advance the escrow's event sequence, returning the number of the event about to be emitted
+    pub fn next_event_seq(&mut self) -> u64 {
+        self.event_seq += 1;
+        self.event_seq
+    }
+
+    /// Check if the escrow has an end time and it has passed
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.end_time > 0 && now > self.end_time
+    }
+
+    /// Check if the escrow can still be marked expired (it is open and past its end time)
+    pub fn can_expire(&self, now: i64) -> bool {
+        self.is_expired(now)
+            && (self.status == EscrowStatus::Pending || self.status == EscrowStatus::Active)
     }
 
     /// Get remaining escrow amount
@@ -122,6 +466,171 @@ impl AsymEscrow {
         self.payer.amount_paid
             .saturating_sub(self.payer.amount_refunded)
             .saturating_sub(self.payer.amount_released)
+            .saturating_sub(self.arbitration_fee_paid)
+    }
+
+    /// Check if the escrow has an arbiter assigned
+    pub fn has_arbiter(&self) -> bool {
+        self.arbiter != Pubkey::default()
+    }
+
+    /// Check if disputes can be opened on this escrow
+    pub fn can_dispute(&self) -> bool {
+        self.has_arbiter() || self.arbiter_kind == ArbiterKind::Jury
+    }
+
+    /// Check if the escrow has an automated settler assigned
+    pub fn has_automated_settler(&self) -> bool {
+        self.automated_settler != Pubkey::default()
+    }
+
+    /// Check if the arbitration fee is paid in a separate fee token
+    pub fn has_fee_token(&self) -> bool {
+        self.arbitration_fee_mint != Pubkey::default()
+    }
+
+    /// Check if a dispute can still be opened, i.e. no release has been requested or the dispute window hasn't elapsed
+    pub fn is_dispute_window_open(&self, now: i64) -> bool {
+        self.release_requested_at == 0
+            || now <= self.release_requested_at.saturating_add(self.dispute_window)
+    }
+
+    /// Check if the agreed amount is hidden behind a commitment
+    /// 
+    /// Only the terms are hidden: deposits, releases and refunds move plain amounts visible on-chain.
+    pub fn has_hidden_terms(&self) -> bool {
+        self.amount_commitment != [0u8; 32]
+    }
+
+    /// Check if the obligation is denominated in USD and converted through a price feed
+    pub fn is_usd_denominated(&self) -> bool {
+        self.usd_amount > 0
+    }
+
+    /// Check if a USD price per whole token lies within the escrow's price collar
+    pub fn is_price_in_collar(&self, usd_price: u64) -> bool {
+        (self.price_floor == 0 || usd_price >= self.price_floor)
+            && (self.price_ceiling == 0 || usd_price <= self.price_ceiling)
+    }
+
+    /// Check if any escrowed funds are currently supplied to a lending adapter
+    pub fn is_lent(&self) -> bool {
+        self.lent_amount > 0
+    }
+
+    /// Check if the escrowed SOL is currently held as a liquid staking position
+    pub fn is_staked(&self) -> bool {
+        self.staked_lamports > 0
+    }
+
+    /// Check if the escrow is hash-time-locked
+    pub fn is_htlc(&self) -> bool {
+        self.hashlock != [0u8; 32]
+    }
+
+    /// Check if a Wormhole message from another chain can release the escrow
+    pub fn has_wormhole_trigger(&self) -> bool {
+        self.wormhole_emitter_chain != 0
+    }
+
+    /// Check if a receipt NFT was minted for the escrow
+    pub fn has_receipt(&self) -> bool {
+        self.receipt_mint != Pubkey::default()
+    }
+
+    /// Check if the receiver's claim is tokenized, i.e. paid out to whoever holds the transferable receipt
+    pub fn is_claim_tokenized(&self) -> bool {
+        self.has_receipt() && self.claim_transferable
+    }
+
+    /// Check if the escrow calls a settlement hook when it settles
+    pub fn has_settlement_hook(&self) -> bool {
+        self.settlement_hook != Pubkey::default()
+    }
+
+    /// Check if the escrow releases into another escrow
+    pub fn is_chained(&self) -> bool {
+        self.chained_escrow != Pubkey::default()
+    }
+
+    /// Check if a party's release consent is given by its co-signers rather than its own key
+    pub fn has_co_signers(&self, party: &Pubkey) -> bool {
+        self.co_signed_party != Pubkey::default() && self.co_signed_party == *party
+    }
+
+    /// Get the position of a key among the co-signers
+    pub fn co_signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.co_signers.iter().position(|c| c.key == *key)
+    }
+
+    /// Check if enough co-signers have assented to give the co-signed party's consent
+    pub fn is_co_signer_threshold_met(&self) -> bool {
+        self.co_signers.iter().filter(|c| c.assented).count() >= self.co_signer_threshold as usize
+    }
+
+    /// Check if both parties must hold an attestation (KYC-gated escrow)
+    pub fn requires_attestation(&self) -> bool {
+        self.attestation_kind != AttestationKind::None
+    }
+
+    /// Check if the escrow has a guardian assigned
+    pub fn has_guardian(&self) -> bool {
+        self.guardian != Pubkey::default()
+    }
+
+    /// Check if a release of an amount is held by the guardian at a time
+    pub fn is_release_held(&self, amount: u64, now: i64) -> bool {
+        self.has_guardian() && amount > self.guardian_threshold && now < self.guardian_hold_until
+    }
+
+    /// Check if a key is one of the escrow's observers
+    pub fn is_observer(&self, key: &Pubkey) -> bool {
+        self.observers.iter().any(|o| o.key == *key)
+    }
+
+    /// Check if a key is an observer with a given permission
+    pub fn can_observe(&self, key: &Pubkey, permission: u8) -> bool {
+        self.observers.iter().any(|o| o.key == *key && o.permissions & permission == permission)
+    }
+
+    /// Check if the escrow charges interest on a late unpaid balance
+    pub fn accrues_interest(&self) -> bool {
+        self.interest_rate_bps > 0
+    }
+
+    /// Calculate interest accrued on the unpaid principal since the last crystallization, up to the cap
+    pub fn pending_interest(&self, now: i64) -> u64 {
+        let from = self.interest_accrued_at.max(self.payment_due_time);
+        if !self.accrues_interest() || now <= from {
+            return 0;
+        }
+        
+        //simple interest: prior interest does not itself accrue
+        let unpaid_principal = self.payer.amount
+            .saturating_sub(self.accrued_interest)
+            .saturating_sub(self.payer.amount_paid);
+        let interest = (unpaid_principal as u128)
+            .saturating_mul(self.interest_rate_bps as u128)
+            .saturating_mul((now - from) as u128)
+            / (BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128);
+        
+        let headroom = self.interest_cap.saturating_sub(self.accrued_interest);
+        interest.min(headroom as u128) as u64
+    }
+
+    /// Check if the payer can still switch to paying in a mint, i.e. it is accepted and nothing has been paid yet
+    pub fn can_select_mint(&self, mint: &Pubkey) -> bool {
+        self.payer.amount_paid == 0 && self.accepted_mints.iter().any(|m| m.mint == *mint)
+    }
+
+    /// Check if a completed escrow was settled in the receiver's favor (more released than refunded)
+    pub fn is_settled_to_receiver(&self) -> bool {
+        self.payer.amount_released > self.payer.amount_refunded
+    }
+
+    /// Check if awards on this escrow can be appealed
+    pub fn has_appeal_arbiter(&self) -> bool {
+        self.appeal_arbiter != Pubkey::default()
     }
 
     /// Check if escrow is within valid time window
@@ -142,4 +651,91 @@ impl AsymEscrow {
 
         start_valid && end_valid
     }
+}
+
+/// Symmetrical escrow account
+/// 
+/// Symmetrical escrow contract for on-chain swaps between two parties.
+/// 
+/// A symmetrical escrow involves both parties depositing an on-chain asset (token A vs token B). Since both 
+/// sides can be verified programmatically, no arbitration is needed: settlement atomically swaps the deposits 
+/// once both parties consent, and either party can refund both deposits once the escrow has expired.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct SymEscrow {
+    /// Unique identifier for the escrow (derived from creator + nonce)
+    pub id: [u8; 32],
+    /// First party information
+    pub payer: EscrowParty,
+    /// Second party information
+    pub receiver: EscrowParty,
+    /// Timestamp when the escrow was created
+    pub timestamp: i64,
+    /// Timestamp when the escrow period begins (0 = immediate)
+    pub start_time: i64,
+    /// Timestamp when the escrow period ends, after which deposits can be refunded
+    pub end_time: i64,
+    /// Current escrow status
+    pub status: EscrowStatus,
+    /// Whether the swap has been executed
+    pub released: bool,
+    /// Fee in basis points (bps), charged on both legs
+    pub fee_bps: u16,
+    /// Escrow creator (for PDA derivation)
+    pub creator: Pubkey,
+    /// Nonce for unique escrow generation
+    pub nonce: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+    /// Sequence number of the escrow's last emitted event
+    pub event_seq: u64,
+    /// Kind of deal the escrow is for
+    pub category: EscrowCategory,
+}
+
+impl SymEscrow {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Set the escrow status, returning the previous status if it changed
+    pub fn set_status(&mut self, status: EscrowStatus) -> Option<EscrowStatus> {
+        let old = self.status;
+        self.status = status;
+        (old != status).then_some(old)
+    }
+
+    /// Advance the escrow's event sequence, returning the number of the event about to be emitted
+    pub fn next_event_seq(&mut self) -> u64 {
+        self.event_seq += 1;
+        self.event_seq
+    }
+
+    /// Check if both parties have deposited their side of the swap
+    pub fn is_fully_deposited(&self) -> bool {
+        self.payer.amount_paid > 0 && self.receiver.amount_paid > 0
+    }
+
+    /// Check if the escrow period has ended
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.end_time
+    }
+
+    /// Check if the escrow can still be marked expired (it is open and past its end time)
+    pub fn can_expire(&self, now: i64) -> bool {
+        self.is_expired(now)
+            && (self.status == EscrowStatus::Pending || self.status == EscrowStatus::Active)
+    }
+
+    /// Get the party record of an address, if it is a party to the escrow
+    pub fn party_mut(&mut self, addr: &Pubkey) -> Option<&mut EscrowParty> {
+        if *addr == self.payer.addr {
+            Some(&mut self.payer)
+        } else if *addr == self.receiver.addr {
+            Some(&mut self.receiver)
+        } else {
+            None
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+/// Protocol insurance fund
+/// 
+/// Holds lamports funded by a share of native protocol fees and by slashed arbiter bonds, paid out 
+/// through authority-approved claims to parties harmed by arbiter misconduct or program bugs.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct InsuranceFund {
+    /// Lamports available for claims (excludes the account's rent reserve)
+    pub balance: u64,
+    /// Total lamports ever deposited
+    pub total_deposited: u64,
+    /// Total lamports paid out in claims
+    pub total_claimed: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl InsuranceFund {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Record lamports deposited into the fund
+    pub fn record_deposit(&mut self, amount: u64) -> Option<()> {
+        self.balance = self.balance.checked_add(amount)?;
+        self.total_deposited = self.total_deposited.checked_add(amount)?;
+        Some(())
+    }
+}
\ No newline at end of file
@@ -1,5 +1,27 @@
 pub mod escrow;
 pub mod program_config;
+pub mod dispute;
+pub mod arbiter;
+pub mod reputation;
+pub mod insurance;
+pub mod bundle;
+pub mod allowlist;
+pub mod blocklist;
+pub mod platform;
+pub mod stats;
+pub mod admin_audit;
+pub mod automation;
 
 pub use escrow::*;
-pub use program_config::*;
\ No newline at end of file
+pub use program_config::*;
+pub use dispute::*;
+pub use arbiter::*;
+pub use reputation::*;
+pub use insurance::*;
+pub use bundle::*;
+pub use allowlist::*;
+pub use blocklist::*;
+pub use platform::*;
+pub use stats::*;
+pub use admin_audit::*;
+pub use automation::*;
\ No newline at end of file
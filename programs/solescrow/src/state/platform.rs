@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::constants::seeds;
+
+/// Registered custodial platform
+/// 
+/// Platforms create escrows on behalf of their end users and pay the rent for them. The platform is 
+/// recorded on each escrow it creates, apart from the parties, so its share of fees can be attributed.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Platform {
+    /// Platform's signing authority, which pays for the escrows it creates
+    pub authority: Pubkey,
+    /// Platform's share of protocol fees on its escrows (basis points)
+    pub fee_share_bps: u16,
+    /// Timestamp when the platform registered
+    pub registered_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+    /// Marketplace program creating escrows through `create_escrow_via_partner` (Pubkey::default() = none)
+    pub program: Pubkey,
+}
+
+impl Platform {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Check if the platform is a partner marketplace program, acting through its partner authority PDA
+    pub fn is_partner_program(&self) -> bool {
+        self.program != Pubkey::default()
+    }
+
+    /// Partner authority PDA a marketplace program signs escrow creation with
+    pub fn partner_authority(program: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[seeds::PARTNER_AUTHORITY], program).0
+    }
+}
\ No newline at end of file
@@ -1,8 +1,22 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    MAX_AUTOMATION_PROGRAMS, MAX_LENDING_ADAPTERS, MAX_SETTLEMENT_HOOKS, MAX_STAKE_POOLS, MAX_SWAP_ROUTERS,
+    MAX_TRANSFER_HOOK_PROGRAMS,
+};
+
+/// Resolution applied when an arbiter misses the decision deadline
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace, Default)]
+pub enum DefaultResolution {
+    /// Refund the remaining funds to the payer
+    #[default]
+    Refund = 0,
+    /// Split the remaining funds evenly between the parties
+    Split = 1,
+}
 
 /// Program configuration account
 #[account]
-#[derive(Debug)]
+#[derive(Debug, InitSpace)]
 pub struct ProgramConfig {
     /// Program authority
     pub authority: Pubkey,
@@ -14,17 +28,82 @@ pub struct ProgramConfig {
     pub paused: bool,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Length of the dispute evidence phase (seconds)
+    pub evidence_window: i64,
+    /// Time the arbiter has to decide after the evidence phase (seconds)
+    pub decision_window: i64,
+    /// Resolution applied to disputes not decided in time
+    pub default_resolution: DefaultResolution,
+    /// Bond each disputing party posts (lamports)
+    pub dispute_bond: u64,
+    /// Share of native protocol fees diverted to the insurance fund (bps)
+    pub insurance_fee_bps: u16,
+    /// Shortest dispute window an escrow may set (seconds)
+    pub min_dispute_window: i64,
+    /// Longest dispute window an escrow may set (seconds)
+    pub max_dispute_window: i64,
+    /// Token-2022 transfer hook programs escrowed mints may use
+    #[max_len(MAX_TRANSFER_HOOK_PROGRAMS)]
+    pub transfer_hook_allowlist: Vec<Pubkey>,
+    /// Share of lending yield taken by the protocol (bps)
+    pub yield_fee_bps: u16,
+    /// Lending adapter programs escrowed funds may be supplied to
+    #[max_len(MAX_LENDING_ADAPTERS)]
+    pub lending_adapter_allowlist: Vec<Pubkey>,
+    /// SPL stake pools escrowed SOL may be staked into
+    #[max_len(MAX_STAKE_POOLS)]
+    pub stake_pool_allowlist: Vec<Pubkey>,
+    /// Global guardian that can hold large native releases (Pubkey::default() = none)
+    pub guardian: Pubkey,
+    /// Native release amount above which the global guardian can hold a release (lamports)
+    pub guardian_threshold: u64,
+    /// Sequence number of the last program-level event (events not tied to one escrow)
+    pub event_seq: u64,
+    /// Number of admin actions recorded in the audit trail
+    pub admin_action_count: u64,
+    /// Programs escrows may call into when they settle
+    #[max_len(MAX_SETTLEMENT_HOOKS)]
+    pub settlement_hook_allowlist: Vec<Pubkey>,
+    /// Automation framework programs timed escrow actions may be registered with
+    #[max_len(MAX_AUTOMATION_PROGRAMS)]
+    pub automation_program_allowlist: Vec<Pubkey>,
+    /// Router programs escrowed tokens may be swapped through at settlement
+    #[max_len(MAX_SWAP_ROUTERS)]
+    pub swap_router_allowlist: Vec<Pubkey>,
+    /// Largest slippage tolerance a swap at settlement may be quoted with (bps)
+    pub max_swap_slippage_bps: u16,
+    /// Largest price impact a swap at settlement may have against the fair price (bps)
+    pub max_swap_price_impact_bps: u16,
+    /// Realms governance whose native treasury is the authority (Pubkey::default() = authority is a plain key)
+    pub governance: Pubkey,
+    /// Address lookup table of the program's hot accounts (Pubkey::default() = none)
+    pub lookup_table: Pubkey,
+    /// Whether payments emit the compact payment event in place of the full payment events
+    pub compact_events: bool,
 }
 
 impl ProgramConfig {
     /// Calculate space needed for account
     pub const fn space() -> usize {
-        8 + // discriminator
-        32 + // authority
-        32 + // fee_vault
-        2 + // default_fee_bps
-        1 + // paused
-        1 // bump
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Advance the program-level event sequence, returning the number of the event about to be emitted
+    pub fn next_event_seq(&mut self) -> u64 {
+        self.event_seq += 1;
+        self.event_seq
+    }
+
+    /// Advance the admin action counter, returning the index of the audit entry being recorded
+    pub fn next_admin_action_index(&mut self) -> u64 {
+        let index = self.admin_action_count;
+        self.admin_action_count += 1;
+        index
+    }
+
+    /// Check if the authority is a Realms governance, so config changes pass through token-holder votes
+    pub fn is_governed(&self) -> bool {
+        self.governance != Pubkey::default()
     }
 
     /// Program config PDA seed
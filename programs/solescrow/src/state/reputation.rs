@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use crate::constants::RECENT_RATINGS_LEN;
+
+/// Per-address reputation account
+/// 
+/// Aggregates an address's escrow history, updated when escrows it is a party to complete, so 
+/// counterparties can assess risk before entering a new escrow.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Reputation {
+    /// Address the reputation belongs to
+    pub addr: Pubkey,
+    /// Escrows settled by consent (release or refund)
+    pub settlements_completed: u64,
+    /// Disputes opened by this address
+    pub disputes_opened: u64,
+    /// Disputes awarded against this address
+    pub disputes_lost: u64,
+    /// Total escrowed volume across completed escrows
+    pub volume: u64,
+    /// Number of ratings received from counterparties
+    pub ratings_count: u64,
+    /// Sum of rating scores received
+    pub ratings_sum: u64,
+    /// Escrowed volume behind the ratings received
+    pub rated_volume: u64,
+    /// Timestamp of the last update
+    pub last_updated: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Reputation {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Record an escrow completed by consent
+    pub fn record_settlement(&mut self, volume: u64, now: i64) {
+        self.settlements_completed = self.settlements_completed.saturating_add(1);
+        self.volume = self.volume.saturating_add(volume);
+        self.last_updated = now;
+    }
+
+    /// Record an escrow completed by arbitration
+    pub fn record_dispute(&mut self, volume: u64, opened: bool, lost: bool, now: i64) {
+        if opened {
+            self.disputes_opened = self.disputes_opened.saturating_add(1);
+        }
+        if lost {
+            self.disputes_lost = self.disputes_lost.saturating_add(1);
+        }
+        self.volume = self.volume.saturating_add(volume);
+        self.last_updated = now;
+    }
+
+    /// Record a rating received from a counterparty
+    pub fn record_rating(&mut self, score: u8, volume: u64, now: i64) {
+        self.ratings_count = self.ratings_count.saturating_add(1);
+        self.ratings_sum = self.ratings_sum.saturating_add(score as u64);
+        self.rated_volume = self.rated_volume.saturating_add(volume);
+        self.last_updated = now;
+    }
+}
+
+/// Rating left by one escrow party for the other, one per party per completed escrow
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Rating {
+    /// Rated escrow
+    pub escrow: Pubkey,
+    /// Party leaving the rating
+    pub rater: Pubkey,
+    /// Party being rated
+    pub ratee: Pubkey,
+    /// Score from MIN_RATING_SCORE to MAX_RATING_SCORE
+    pub score: u8,
+    /// Hash of the off-chain comment
+    pub comment_hash: [u8; 32],
+    /// Timestamp of the rating
+    pub timestamp: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Rating {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+}
+
+/// Per-address rating aggregate
+/// 
+/// Summarizes the ratings an address has received so a client can show its average and latest scores 
+/// with a single account fetch. Only ratings left after the account is created are counted.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct RatingAggregate {
+    /// Address the ratings belong to
+    pub addr: Pubkey,
+    /// Number of ratings received
+    pub count: u64,
+    /// Sum of rating scores received
+    pub sum: u64,
+    /// Most recent scores received, oldest first
+    #[max_len(RECENT_RATINGS_LEN)]
+    pub recent_scores: Vec<u8>,
+    /// Timestamp of the last rating
+    pub last_rated_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RatingAggregate {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Record a rating, dropping the oldest recent score once the window is full
+    pub fn record(&mut self, score: u8, now: i64) {
+        self.count = self.count.saturating_add(1);
+        self.sum = self.sum.saturating_add(score as u64);
+        if self.recent_scores.len() >= RECENT_RATINGS_LEN {
+            self.recent_scores.remove(0);
+        }
+        self.recent_scores.push(score);
+        self.last_rated_at = now;
+    }
+}
\ No newline at end of file
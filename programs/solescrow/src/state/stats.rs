@@ -0,0 +1,276 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_TVL_CURRENCIES, VOLUME_BUCKET_SECONDS, VOLUME_HISTORY_BUCKETS};
+use crate::state::EscrowCategory;
+
+/// Per-currency escrow statistics
+/// 
+/// Aggregates kept for treasury and BI dashboards, one per currency mint (the default pubkey for native SOL). 
+/// Tracking starts once the account is created: escrows already active at that point are not counted, so 
+/// the active count saturates at zero rather than underflowing when they complete.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct CurrencyStats {
+    /// Currency mint the statistics cover
+    pub currency: Pubkey,
+    /// Total amount deposited into escrows
+    pub volume_in: u128,
+    /// Total amount released to receivers, net of fees
+    pub volume_released: u128,
+    /// Total amount refunded to payers
+    pub volume_refunded: u128,
+    /// Total protocol fees collected on releases
+    pub fees_collected: u128,
+    /// Escrows funded but not yet completed
+    pub active_escrows: u64,
+    /// Timestamp of the last update
+    pub updated_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CurrencyStats {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Record a deposit, counting the escrow as active if it was just funded
+    pub fn record_deposit(&mut self, amount: u64, activated: bool, now: i64) {
+        self.volume_in = self.volume_in.saturating_add(amount as u128);
+        if activated {
+            self.active_escrows = self.active_escrows.saturating_add(1);
+        }
+        self.updated_at = now;
+    }
+
+    /// Record a release, no longer counting the escrow as active if it just completed
+    pub fn record_release(&mut self, amount: u64, fee: u64, completed: bool, now: i64) {
+        self.volume_released = self.volume_released.saturating_add(amount as u128);
+        self.fees_collected = self.fees_collected.saturating_add(fee as u128);
+        self.record_completion(completed, now);
+    }
+
+    /// Record a refund, no longer counting the escrow as active if it just completed
+    pub fn record_refund(&mut self, amount: u64, completed: bool, now: i64) {
+        self.volume_refunded = self.volume_refunded.saturating_add(amount as u128);
+        self.record_completion(completed, now);
+    }
+
+    fn record_completion(&mut self, completed: bool, now: i64) {
+        if completed {
+            self.active_escrows = self.active_escrows.saturating_sub(1);
+        }
+        self.updated_at = now;
+    }
+}
+
+/// Value locked in escrows of one currency
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, InitSpace)]
+pub struct TvlEntry {
+    /// Currency mint (the default pubkey for native SOL)
+    pub currency: Pubkey,
+    /// Amount currently locked in escrow vaults
+    pub locked: u64,
+}
+
+/// Global TVL ledger
+/// 
+/// Tracks the value locked per currency across all asymmetric escrows, for the currencies the authority 
+/// has chosen to track. Locked value grows with deposits and shrinks as funds leave escrow vaults; 
+/// `audit_tvl` reconciles it against the vaults themselves.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct TvlLedger {
+    /// Tracked currencies and their locked value
+    #[max_len(MAX_TVL_CURRENCIES)]
+    pub entries: Vec<TvlEntry>,
+    /// Timestamp of the last update
+    pub updated_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl TvlLedger {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Start tracking a currency; returns false if it is already tracked
+    pub fn track(&mut self, currency: Pubkey) -> bool {
+        if self.locked(&currency).is_some() {
+            return false;
+        }
+        self.entries.push(TvlEntry { currency, locked: 0 });
+        true
+    }
+
+    /// Value locked in a currency, if it is tracked
+    pub fn locked(&self, currency: &Pubkey) -> Option<u64> {
+        self.entries.iter().find(|e| e.currency == *currency).map(|e| e.locked)
+    }
+
+    /// Apply funds entering and leaving escrow vaults (untracked currencies are ignored)
+    pub fn record(&mut self, currency: &Pubkey, deposited: u64, withdrawn: u64, now: i64) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.currency == *currency) {
+            entry.locked = entry.locked.saturating_add(deposited).saturating_sub(withdrawn);
+            self.updated_at = now;
+        }
+    }
+}
+
+/// Per-address lifetime counters
+/// 
+/// Lightweight escrow history, updated as escrows the address is a party to settle. Unlike `Reputation`, 
+/// the counters can't be left out of a settlement once created, so they can back rate limits.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct UserStats {
+    /// Address the counters belong to
+    pub addr: Pubkey,
+    /// Escrows settled with the address as payer
+    pub escrows_as_payer: u64,
+    /// Escrows settled with the address as receiver
+    pub escrows_as_receiver: u64,
+    /// Total escrowed volume across settled escrows
+    pub settled_volume: u64,
+    /// Settled escrows that went to arbitration
+    pub disputes: u64,
+    /// Timestamp of the last settlement
+    pub last_settled_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl UserStats {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Record a settled escrow the address was a party to
+    pub fn record_settlement(&mut self, as_payer: bool, volume: u64, disputed: bool, now: i64) {
+        if as_payer {
+            self.escrows_as_payer = self.escrows_as_payer.saturating_add(1);
+        } else {
+            self.escrows_as_receiver = self.escrows_as_receiver.saturating_add(1);
+        }
+        if disputed {
+            self.disputes = self.disputes.saturating_add(1);
+        }
+        self.settled_volume = self.settled_volume.saturating_add(volume);
+        self.last_settled_at = now;
+    }
+}
+
+/// Per-category escrow counters
+/// 
+/// Segment-level analytics, one per escrow category. Counts rather than volumes, as an escrow category 
+/// spans currencies; volumes are kept per currency in `CurrencyStats`.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct CategoryStats {
+    /// Category the counters cover
+    pub category: EscrowCategory,
+    /// Escrows that received their first payment
+    pub escrows_funded: u64,
+    /// Escrows settled by release, refund or award
+    pub escrows_settled: u64,
+    /// Settled escrows that went to arbitration
+    pub disputes: u64,
+    /// Escrows funded but not yet settled
+    pub active_escrows: u64,
+    /// Timestamp of the last update
+    pub updated_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CategoryStats {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Record an escrow receiving its first payment
+    pub fn record_funded(&mut self, now: i64) {
+        self.escrows_funded = self.escrows_funded.saturating_add(1);
+        self.active_escrows = self.active_escrows.saturating_add(1);
+        self.updated_at = now;
+    }
+
+    /// Record an escrow settling
+    pub fn record_settled(&mut self, disputed: bool, now: i64) {
+        self.escrows_settled = self.escrows_settled.saturating_add(1);
+        if disputed {
+            self.disputes = self.disputes.saturating_add(1);
+        }
+        self.active_escrows = self.active_escrows.saturating_sub(1);
+        self.updated_at = now;
+    }
+}
+
+/// Volume of one currency over one day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct VolumeBucket {
+    /// Day the bucket covers, in days since the Unix epoch
+    pub day: i64,
+    /// Amount deposited into escrows
+    pub volume_in: u64,
+    /// Amount leaving escrows by release, refund or award
+    pub volume_settled: u64,
+}
+
+/// Rolling daily volume history for one currency
+/// 
+/// A ring of daily buckets indexed by day, each reset when its slot is reused, so the account stays a 
+/// fixed size while covering the last `VOLUME_HISTORY_BUCKETS` days. Volume entering escrow is counted 
+/// as it is deposited rather than at creation, as escrows with hidden terms carry no amount until then.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct VolumeHistory {
+    /// Currency mint the history covers
+    pub currency: Pubkey,
+    /// Daily buckets, indexed by day modulo the number of buckets
+    pub buckets: [VolumeBucket; VOLUME_HISTORY_BUCKETS],
+    /// Timestamp of the last update
+    pub updated_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl VolumeHistory {
+    /// Calculate space needed for account
+    pub const fn space() -> usize {
+        8 + Self::INIT_SPACE // discriminator + fields
+    }
+
+    /// Day a timestamp falls in, in days since the Unix epoch
+    pub fn day_of(timestamp: i64) -> i64 {
+        timestamp.div_euclid(VOLUME_BUCKET_SECONDS)
+    }
+
+    /// Record volume in today's bucket, resetting it if it still holds an older day
+    pub fn record(&mut self, volume_in: u64, volume_settled: u64, now: i64) {
+        let day = Self::day_of(now);
+        let bucket = &mut self.buckets[day.rem_euclid(VOLUME_HISTORY_BUCKETS as i64) as usize];
+        if bucket.day != day {
+            *bucket = VolumeBucket { day, ..Default::default() };
+        }
+        bucket.volume_in = bucket.volume_in.saturating_add(volume_in);
+        bucket.volume_settled = bucket.volume_settled.saturating_add(volume_settled);
+        self.updated_at = now;
+    }
+
+    /// Total (volume in, volume settled) over the last `days` days, including today
+    pub fn volume_since(&self, days: u64, now: i64) -> (u64, u64) {
+        let today = Self::day_of(now);
+        let days = days.min(VOLUME_HISTORY_BUCKETS as u64) as i64;
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.day > today - days && bucket.day <= today)
+            .fold((0, 0), |(volume_in, volume_settled), bucket| {
+                (volume_in.saturating_add(bucket.volume_in), volume_settled.saturating_add(bucket.volume_settled))
+            })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+//! Helpers shared by the solana-program-test suites
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+pub const ESCROW_AMOUNT: u64 = 1_000_000_000;
+
+/// Address of an escrow program PDA
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &solana_escrow::ID).0
+}
+
+/// System account holding enough SOL to pay for a few escrows
+pub fn funded() -> Account {
+    Account {
+        lamports: 10 * ESCROW_AMOUNT,
+        owner: system_program::ID,
+        ..Account::default()
+    }
+}
+
+/// Run an instruction, returning the compute units it consumed, or `None` (with its logs printed) if it failed
+pub async fn run(context: &mut ProgramTestContext, ix: Instruction, signer: &Keypair) -> Option<u64> {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        context.banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    let outcome = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+    let metadata = outcome.metadata.expect("transaction metadata");
+    if let Err(err) = &outcome.result {
+        println!("{err:?}\n{}", metadata.log_messages.join("\n"));
+        return None;
+    }
+    Some(metadata.compute_units_consumed)
+}
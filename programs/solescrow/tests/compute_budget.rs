@@ -0,0 +1,196 @@
+//! Compute unit budgets of the core asymmetric escrow instructions
+//!
+//! Runs the built program (`target/deploy/solana_escrow.so`, from `anchor build`) in solana-program-test
+//! through a native SOL escrow's lifecycle, and fails if any instruction consumes more compute units than
+//! its budget. Run it with `anchor run bench`; new features that add work to these paths (oracles, hooks,
+//! tracking accounts) must fit within the budgets, or raise them deliberately.
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::{funded, pda, run, ESCROW_AMOUNT};
+use solana_escrow::constants::seeds;
+use solana_escrow::instructions::asym_escrow::CreateAsymEscrowParams;
+use solana_escrow::instructions::initialize::InitializeProgramParams;
+use solana_escrow::state::{ArbitrationFee, ArbiterKind, EscrowCategory, ProgramConfig};
+use solana_program_test::ProgramTest;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+
+/// Most compute units each instruction may consume
+const BUDGETS: &[(&str, u64)] = &[
+    ("initialize_program", 25_000),
+    ("create_asym_escrow", 60_000),
+    ("place_payment_asym", 60_000),
+    ("release_escrow_asym (consent)", 50_000),
+    ("release_escrow_asym (release)", 120_000),
+];
+
+#[tokio::test]
+#[ignore = "needs the program built with `anchor build`; run with `anchor run bench`"]
+async fn compute_units_within_budget() {
+    let authority = Keypair::new();
+    let payer = Keypair::new();
+    let receiver = Keypair::new();
+    let fee_vault = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::new("solana_escrow", solana_escrow::ID, None);
+    program_test.prefer_bpf(true);
+    program_test.add_account(authority.pubkey(), funded());
+    program_test.add_account(payer.pubkey(), funded());
+    program_test.add_account(receiver.pubkey(), funded());
+    program_test.add_account(fee_vault, funded());
+    let mut context = program_test.start_with_context().await;
+
+    let program_config = pda(&[ProgramConfig::SEED]);
+    let event_authority = pda(&[seeds::EVENT_AUTHORITY]);
+    let mut consumed = Vec::new();
+
+    let ix = Instruction {
+        program_id: solana_escrow::ID,
+        accounts: solana_escrow::accounts::InitializeProgram {
+            authority: authority.pubkey(),
+            program_config,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: solana_escrow::instruction::InitializeProgram {
+            params: InitializeProgramParams { fee_vault, default_fee_bps: 100 },
+        }
+        .data(),
+    };
+    consumed.push(run(&mut context, ix, &authority).await.expect("instruction failed"));
+
+    //native SOL escrow created by the payer
+    let nonce = 1u64;
+    let escrow = pda(&[seeds::ASYM_ESCROW, payer.pubkey().as_ref(), &nonce.to_le_bytes()]);
+    let params = CreateAsymEscrowParams {
+        payer: payer.pubkey(),
+        receiver: receiver.pubkey(),
+        currency: Pubkey::default(),
+        amount: ESCROW_AMOUNT,
+        start_time: 0,
+        end_time: 0,
+        nonce,
+        arbiter: Pubkey::default(),
+        arbitration_fee: ArbitrationFee::default(),
+        appeal_arbiter: Pubkey::default(),
+        arbiter_kind: ArbiterKind::default(),
+        automated_settler: Pubkey::default(),
+        arbitration_fee_mint: Pubkey::default(),
+        dispute_window: 0,
+        amount_commitment: [0; 32],
+        category: EscrowCategory::default(),
+    };
+    let ix = Instruction {
+        program_id: solana_escrow::ID,
+        accounts: solana_escrow::accounts::CreateAsymEscrow {
+            creator: payer.pubkey(),
+            escrow,
+            program_config,
+            token_mint: None,
+            payer_allowlist: pda(&[seeds::COUNTERPARTY_ALLOWLIST, payer.pubkey().as_ref()]),
+            system_program: system_program::ID,
+            event_authority,
+            program: solana_escrow::ID,
+        }
+        .to_account_metas(None),
+        data: solana_escrow::instruction::CreateAsymEscrow { params }.data(),
+    };
+    consumed.push(run(&mut context, ix, &payer).await.expect("instruction failed"));
+
+    //the tracking accounts are passed at their PDAs whether or not they exist
+    let currency = Pubkey::default();
+    let category = [EscrowCategory::default() as u8];
+    let escrow_vault = pda(&[seeds::ESCROW_VAULT, escrow.as_ref()]);
+    let payer_blocklist_entry = pda(&[seeds::BLOCKED_ADDRESS, payer.pubkey().as_ref()]);
+    let receiver_blocklist_entry = pda(&[seeds::BLOCKED_ADDRESS, receiver.pubkey().as_ref()]);
+    let currency_stats = pda(&[seeds::CURRENCY_STATS, currency.as_ref()]);
+    let category_stats = pda(&[seeds::CATEGORY_STATS, &category]);
+    let volume_history = pda(&[seeds::VOLUME_HISTORY, currency.as_ref()]);
+    let tvl_ledger = pda(&[seeds::TVL_LEDGER]);
+
+    let ix = Instruction {
+        program_id: solana_escrow::ID,
+        accounts: solana_escrow::accounts::PlacePaymentAsym {
+            payer: payer.pubkey(),
+            escrow,
+            program_config,
+            escrow_vault,
+            price_feed: None,
+            payer_blocklist_entry,
+            receiver_blocklist_entry,
+            currency_stats,
+            category_stats,
+            volume_history,
+            tvl_ledger,
+            memo_program: None,
+            system_program: system_program::ID,
+            event_authority,
+            program: solana_escrow::ID,
+        }
+        .to_account_metas(None),
+        data: solana_escrow::instruction::PlacePaymentAsym { amount: ESCROW_AMOUNT }.data(),
+    };
+    consumed.push(run(&mut context, ix, &payer).await.expect("instruction failed"));
+
+    //the payer's consent is recorded, the receiver's executes the release
+    for signer in [&payer, &receiver] {
+        let ix = Instruction {
+            program_id: solana_escrow::ID,
+            accounts: solana_escrow::accounts::ReleaseEscrowAsym {
+                signer: signer.pubkey(),
+                escrow,
+                program_config,
+                escrow_vault,
+                receiver: receiver.pubkey(),
+                fee_vault,
+                insurance_fund: None,
+                claim_token_account: None,
+                chained_escrow: None,
+                payer_reputation: None,
+                receiver_reputation: None,
+                receipt_mint: None,
+                receipt_token_account: None,
+                receipt_token_program: None,
+                price_feed: None,
+                posted_vaa: None,
+                sysvar_instructions: None,
+                payer_attestation: None,
+                receiver_attestation: None,
+                payer_blocklist_entry,
+                receiver_blocklist_entry,
+                payout_blocklist_entry: receiver_blocklist_entry,
+                currency_stats,
+                category_stats,
+                volume_history,
+                tvl_ledger,
+                payer_stats: pda(&[seeds::USER_STATS, payer.pubkey().as_ref()]),
+                receiver_stats: pda(&[seeds::USER_STATS, receiver.pubkey().as_ref()]),
+                settlement_hook: None,
+                settlement_hook_authority: None,
+                settlement_hook_state: None,
+                memo_program: None,
+                system_program: system_program::ID,
+                event_authority,
+                program: solana_escrow::ID,
+            }
+            .to_account_metas(None),
+            data: solana_escrow::instruction::ReleaseEscrowAsym {}.data(),
+        };
+        consumed.push(run(&mut context, ix, signer).await.expect("instruction failed"));
+    }
+
+    //report every instruction before failing, so one regression doesn't hide another
+    let mut over_budget = Vec::new();
+    for ((name, budget), units) in BUDGETS.iter().zip(consumed) {
+        println!("{name}: {units} / {budget} CU");
+        if units > *budget {
+            over_budget.push(*name);
+        }
+    }
+    assert!(over_budget.is_empty(), "over compute budget: {}", over_budget.join(", "));
+}
@@ -0,0 +1,214 @@
+//! Consent refunds of a native SOL escrow whose payout out of the vault fails
+//!
+//! Runs the program as a native builtin in solana-program-test, so `cargo test` covers it without a BPF
+//! build. The vault's balance is rewritten between instructions to reach the failure paths of the payout,
+//! which must fail the refund instruction and leave the escrow untouched.
+mod common;
+
+use anchor_lang::solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use common::{funded, pda, run, ESCROW_AMOUNT};
+use solana_escrow::constants::seeds;
+use solana_escrow::instructions::asym_escrow::CreateAsymEscrowParams;
+use solana_escrow::instructions::initialize::InitializeProgramParams;
+use solana_escrow::state::{ArbitrationFee, ArbiterKind, AsymEscrow, EscrowCategory, ProgramConfig};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+
+//native entry of the program; Anchor's entry borrows the accounts for as long as their data, so they are
+//leaked for the rest of the test
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    solana_escrow::entry(program_id, accounts, data)
+}
+
+struct NativeEscrow {
+    context: ProgramTestContext,
+    payer: Keypair,
+    receiver: Keypair,
+    escrow: Pubkey,
+    escrow_vault: Pubkey,
+}
+
+//helper function to create a fully paid native escrow
+async fn fully_paid_escrow() -> NativeEscrow {
+    let authority = Keypair::new();
+    let payer = Keypair::new();
+    let receiver = Keypair::new();
+    let fee_vault = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::new("solana_escrow", solana_escrow::ID, processor!(process_instruction));
+    program_test.add_account(authority.pubkey(), funded());
+    program_test.add_account(payer.pubkey(), funded());
+    program_test.add_account(receiver.pubkey(), funded());
+    program_test.add_account(fee_vault, funded());
+    let mut context = program_test.start_with_context().await;
+
+    let program_config = pda(&[ProgramConfig::SEED]);
+    let event_authority = pda(&[seeds::EVENT_AUTHORITY]);
+
+    let ix = Instruction {
+        program_id: solana_escrow::ID,
+        accounts: solana_escrow::accounts::InitializeProgram {
+            authority: authority.pubkey(),
+            program_config,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: solana_escrow::instruction::InitializeProgram {
+            params: InitializeProgramParams { fee_vault, default_fee_bps: 100 },
+        }
+        .data(),
+    };
+    assert!(run(&mut context, ix, &authority).await.is_some());
+
+    let nonce = 1u64;
+    let escrow = pda(&[seeds::ASYM_ESCROW, payer.pubkey().as_ref(), &nonce.to_le_bytes()]);
+    let params = CreateAsymEscrowParams {
+        payer: payer.pubkey(),
+        receiver: receiver.pubkey(),
+        currency: Pubkey::default(),
+        amount: ESCROW_AMOUNT,
+        start_time: 0,
+        end_time: 0,
+        nonce,
+        arbiter: Pubkey::default(),
+        arbitration_fee: ArbitrationFee::default(),
+        appeal_arbiter: Pubkey::default(),
+        arbiter_kind: ArbiterKind::default(),
+        automated_settler: Pubkey::default(),
+        arbitration_fee_mint: Pubkey::default(),
+        dispute_window: 0,
+        amount_commitment: [0; 32],
+        category: EscrowCategory::default(),
+    };
+    let ix = Instruction {
+        program_id: solana_escrow::ID,
+        accounts: solana_escrow::accounts::CreateAsymEscrow {
+            creator: payer.pubkey(),
+            escrow,
+            program_config,
+            token_mint: None,
+            payer_allowlist: pda(&[seeds::COUNTERPARTY_ALLOWLIST, payer.pubkey().as_ref()]),
+            system_program: system_program::ID,
+            event_authority,
+            program: solana_escrow::ID,
+        }
+        .to_account_metas(None),
+        data: solana_escrow::instruction::CreateAsymEscrow { params }.data(),
+    };
+    assert!(run(&mut context, ix, &payer).await.is_some());
+
+    let currency = Pubkey::default();
+    let category = [EscrowCategory::default() as u8];
+    let escrow_vault = pda(&[seeds::ESCROW_VAULT, escrow.as_ref()]);
+    let ix = Instruction {
+        program_id: solana_escrow::ID,
+        accounts: solana_escrow::accounts::PlacePaymentAsym {
+            payer: payer.pubkey(),
+            escrow,
+            program_config,
+            escrow_vault,
+            price_feed: None,
+            payer_blocklist_entry: pda(&[seeds::BLOCKED_ADDRESS, payer.pubkey().as_ref()]),
+            receiver_blocklist_entry: pda(&[seeds::BLOCKED_ADDRESS, receiver.pubkey().as_ref()]),
+            currency_stats: pda(&[seeds::CURRENCY_STATS, currency.as_ref()]),
+            category_stats: pda(&[seeds::CATEGORY_STATS, &category]),
+            volume_history: pda(&[seeds::VOLUME_HISTORY, currency.as_ref()]),
+            tvl_ledger: pda(&[seeds::TVL_LEDGER]),
+            memo_program: None,
+            system_program: system_program::ID,
+            event_authority,
+            program: solana_escrow::ID,
+        }
+        .to_account_metas(None),
+        data: solana_escrow::instruction::PlacePaymentAsym { amount: ESCROW_AMOUNT }.data(),
+    };
+    assert!(run(&mut context, ix, &payer).await.is_some());
+
+    NativeEscrow { context, payer, receiver, escrow, escrow_vault }
+}
+
+//helper function to set the lamports held by the escrow vault
+fn set_vault_lamports(escrow: &mut NativeEscrow, lamports: u64) {
+    let vault = Account { lamports, owner: system_program::ID, ..Account::default() };
+    escrow.context.set_account(&escrow.escrow_vault, &AccountSharedData::from(vault));
+}
+
+//helper function to refund the payer on the receiver's consent, returning whether it succeeded
+async fn refund(escrow: &mut NativeEscrow, amount: u64) -> bool {
+    let currency = Pubkey::default();
+    let category = [EscrowCategory::default() as u8];
+    let ix = Instruction {
+        program_id: solana_escrow::ID,
+        accounts: solana_escrow::accounts::RefundEscrowAsym {
+            signer: escrow.receiver.pubkey(),
+            escrow: escrow.escrow,
+            program_config: pda(&[ProgramConfig::SEED]),
+            escrow_vault: escrow.escrow_vault,
+            payer: escrow.payer.pubkey(),
+            escrow_token_account: None,
+            payer_token_account: None,
+            payer_reputation: None,
+            receiver_reputation: None,
+            token_mint: None,
+            receipt_mint: None,
+            receipt_token_account: None,
+            receipt_token_program: None,
+            sysvar_instructions: None,
+            currency_stats: pda(&[seeds::CURRENCY_STATS, currency.as_ref()]),
+            category_stats: pda(&[seeds::CATEGORY_STATS, &category]),
+            volume_history: pda(&[seeds::VOLUME_HISTORY, currency.as_ref()]),
+            tvl_ledger: pda(&[seeds::TVL_LEDGER]),
+            payer_stats: pda(&[seeds::USER_STATS, escrow.payer.pubkey().as_ref()]),
+            receiver_stats: pda(&[seeds::USER_STATS, escrow.receiver.pubkey().as_ref()]),
+            settlement_hook: None,
+            settlement_hook_authority: None,
+            settlement_hook_state: None,
+            memo_program: None,
+            token_program: None,
+            system_program: system_program::ID,
+            event_authority: pda(&[seeds::EVENT_AUTHORITY]),
+            program: solana_escrow::ID,
+        }
+        .to_account_metas(None),
+        data: solana_escrow::instruction::RefundEscrowAsym { amount }.data(),
+    };
+    run(&mut escrow.context, ix, &escrow.receiver).await.is_some()
+}
+
+async fn amount_refunded(escrow: &mut NativeEscrow) -> u64 {
+    let account = escrow.context.banks_client.get_account(escrow.escrow).await.unwrap().unwrap();
+    AsymEscrow::try_deserialize(&mut account.data.as_slice()).unwrap().payer.amount_refunded
+}
+
+#[tokio::test]
+async fn failed_payout_fails_the_refund() {
+    let mut escrow = fully_paid_escrow().await;
+
+    //the vault no longer holds the whole payment, so paying it back fails
+    set_vault_lamports(&mut escrow, ESCROW_AMOUNT / 2);
+    assert!(!refund(&mut escrow, ESCROW_AMOUNT).await);
+    assert_eq!(amount_refunded(&mut escrow).await, 0);
+}
+
+#[tokio::test]
+async fn partial_refund_keeps_vault_rent_reserve() {
+    let mut escrow = fully_paid_escrow().await;
+
+    //a vault funded before the rent reserve holds the payment alone, so a partial refund would leave it
+    //holding less than the reserve
+    set_vault_lamports(&mut escrow, ESCROW_AMOUNT);
+    assert!(!refund(&mut escrow, ESCROW_AMOUNT - 1_000).await);
+    assert_eq!(amount_refunded(&mut escrow).await, 0);
+
+    //refunding it in full empties the vault instead
+    assert!(refund(&mut escrow, ESCROW_AMOUNT).await);
+    assert_eq!(amount_refunded(&mut escrow).await, ESCROW_AMOUNT);
+}
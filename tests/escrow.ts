@@ -286,10 +286,6 @@ describe('AsymEscrow', () => {
                     escrowVault,
                     receiver: testUtils.accounts.receiver1.publicKey,
                     feeVault: testUtils.accounts.feeVault.publicKey,
-                    escrowTokenAccount: null,
-                    receiverTokenAccount: null,
-                    feeTokenAccount: null,
-                    tokenProgram: null,
                     systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .signers([testUtils.accounts.payer1])
@@ -314,10 +310,6 @@ describe('AsymEscrow', () => {
                     escrowVault,
                     receiver: testUtils.accounts.receiver1.publicKey,
                     feeVault: testUtils.accounts.feeVault.publicKey,
-                    escrowTokenAccount: null,
-                    receiverTokenAccount: null,
-                    feeTokenAccount: null,
-                    tokenProgram: null,
                     systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .signers([testUtils.accounts.receiver1])
@@ -349,10 +341,6 @@ describe('AsymEscrow', () => {
                     escrowVault,
                     receiver: testUtils.accounts.receiver1.publicKey,
                     feeVault: testUtils.accounts.feeVault.publicKey,
-                    escrowTokenAccount: null,
-                    receiverTokenAccount: null,
-                    feeTokenAccount: null,
-                    tokenProgram: null,
                     systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .signers([testUtils.accounts.receiver1])
@@ -370,10 +358,6 @@ describe('AsymEscrow', () => {
                     escrowVault,
                     receiver: testUtils.accounts.receiver1.publicKey,
                     feeVault: testUtils.accounts.feeVault.publicKey,
-                    escrowTokenAccount: null,
-                    receiverTokenAccount: null,
-                    feeTokenAccount: null,
-                    tokenProgram: null,
                     systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .signers([testUtils.accounts.payer1])
@@ -382,7 +366,20 @@ describe('AsymEscrow', () => {
             escrowAccount = await program.account.asymEscrow.fetch(escrow);
             expect(escrowAccount.status).to.equal(EscrowStatus.Completed);
 
-            //TODO: finish this test
+            //the whole amount is released: 1% to the fee vault, the rest to the receiver
+            const fee = amount / 100;
+            expect(escrowAccount.payer.amountReleased.toNumber()).to.equal(amount);
+            const finalReceiverBalance = await provider.connection.getBalance(
+                testUtils.accounts.receiver1.publicKey
+            );
+            const finalFeeBalance = await provider.connection.getBalance(
+                testUtils.accounts.feeVault.publicKey
+            );
+            //the receiver paid the fee of its consent transaction
+            expect(finalReceiverBalance).to.be.greaterThan(
+                initialReceiverBalance + amount - fee - LAMPORTS_PER_SOL / 1000
+            );
+            expect(finalFeeBalance).to.equal(initialFeeBalance + fee);
         });
     });
 
@@ -420,6 +417,7 @@ describe('AsymEscrow', () => {
                     payer: testUtils.accounts.payer1.publicKey,
                     escrowTokenAccount: null,
                     payerTokenAccount: null,
+                    tokenMint: null,
                     tokenProgram: null,
                     systemProgram: anchor.web3.SystemProgram.programId,
                 })
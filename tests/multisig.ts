@@ -0,0 +1,109 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Escrow } from '../target/types/escrow';
+import {
+    Keypair,
+    LAMPORTS_PER_SOL,
+    PublicKey,
+    SystemProgram,
+    Transaction,
+    sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import { EscrowTestUtils } from './utils';
+
+//Squads v4 program; multisig vaults are accounts owned by it rather than by the system program
+const SQUADS_PROGRAM_ID = new PublicKey(
+    'SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf'
+);
+
+describe('Multisig Parties', () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const program = anchor.workspace.Escrow as Program<Escrow>;
+    let testUtils: EscrowTestUtils;
+    let vault: Keypair;
+
+    //creates an account owned by the multisig program, standing in for a Squads vault
+    async function createMultisigVault(): Promise<Keypair> {
+        const account = Keypair.generate();
+        const lamports =
+            await provider.connection.getMinimumBalanceForRentExemption(0);
+
+        const tx = new Transaction().add(
+            SystemProgram.createAccount({
+                fromPubkey: testUtils.accounts.admin.publicKey,
+                newAccountPubkey: account.publicKey,
+                lamports,
+                space: 0,
+                programId: SQUADS_PROGRAM_ID,
+            })
+        );
+        await sendAndConfirmTransaction(provider.connection, tx, [
+            testUtils.accounts.admin,
+            account,
+        ]);
+
+        return account;
+    }
+
+    beforeEach(async () => {
+        testUtils = new EscrowTestUtils(program, provider);
+        await testUtils.airdropToAll(3);
+        await testUtils.initializeProgramConfig(100); // 1% fee
+        vault = await createMultisigVault();
+    });
+
+    it('releases native funds to a program-owned receiver', async () => {
+        const amount = LAMPORTS_PER_SOL;
+        const [programConfig] = testUtils.getProgramConfigPDA();
+
+        const escrow = await testUtils.createAsymEscrow(
+            testUtils.accounts.admin,
+            testUtils.accounts.payer1.publicKey,
+            vault.publicKey,
+            amount
+        );
+        const [escrowVault] = testUtils.getEscrowVaultPDA(escrow);
+
+        await testUtils.placePaymentAsym(
+            testUtils.accounts.payer1,
+            escrow,
+            amount
+        );
+
+        const initialVaultBalance = await provider.connection.getBalance(
+            vault.publicKey
+        );
+
+        //the vault's signature stands in for the multisig program's CPI signature
+        for (const signer of [vault, testUtils.accounts.payer1]) {
+            await program.methods
+                .releaseEscrowAsym()
+                .accounts({
+                    signer: signer.publicKey,
+                    escrow,
+                    programConfig,
+                    escrowVault,
+                    receiver: vault.publicKey,
+                    feeVault: testUtils.accounts.feeVault.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([signer])
+                .rpc();
+        }
+
+        const finalVaultBalance = await provider.connection.getBalance(
+            vault.publicKey
+        );
+        expect(finalVaultBalance - initialVaultBalance).to.equal(
+            amount - amount / 100
+        );
+
+        const vaultInfo = await provider.connection.getAccountInfo(
+            vault.publicKey
+        );
+        expect(vaultInfo!.owner.equals(SQUADS_PROGRAM_ID)).to.equal(true);
+    });
+});
@@ -15,6 +15,8 @@ import {
     createAccount,
     mintTo,
     getAccount,
+    getAssociatedTokenAddressSync,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
 } from '@solana/spl-token';
 import { expect } from 'chai';
 
@@ -199,6 +201,14 @@ export class EscrowTestUtils {
             startTime: new anchor.BN(0),
             endTime: new anchor.BN(0),
             nonce: new anchor.BN(nonce),
+            arbiter: PublicKey.default,
+            arbitrationFee: { feeType: { bps: {} }, value: new anchor.BN(0) },
+            appealArbiter: PublicKey.default,
+            arbiterKind: { individual: {} },
+            automatedSettler: PublicKey.default,
+            arbitrationFeeMint: PublicKey.default,
+            disputeWindow: new anchor.BN(0),
+            amountCommitment: Array(32).fill(0),
         };
 
         await this.program.methods
@@ -226,13 +236,33 @@ export class EscrowTestUtils {
         const [programConfig] = this.getProgramConfigPDA();
         const [escrowVault] = this.getEscrowVaultPDA(escrow);
 
-        let payerTokenAccount = null;
-        let escrowTokenAccount = null;
-
         if (currency) {
-            payerTokenAccount = await this.createTokenAccount(payer.publicKey);
-            escrowTokenAccount = await this.createTokenAccount(escrowVault);
+            const payerTokenAccount = await this.createTokenAccount(payer.publicKey);
+            // Created by the first deposit
+            const escrowTokenAccount = getAssociatedTokenAddressSync(
+                currency,
+                escrowVault,
+                true
+            );
             await this.mintTokens(payerTokenAccount, amount);
+
+            await this.program.methods
+                .placeTokenPaymentAsym(new anchor.BN(amount))
+                .accounts({
+                    payer: payer.publicKey,
+                    escrow,
+                    programConfig,
+                    escrowVault,
+                    payerTokenAccount,
+                    escrowTokenAccount,
+                    tokenMint: currency,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([payer])
+                .rpc();
+            return;
         }
 
         await this.program.methods
@@ -242,9 +272,6 @@ export class EscrowTestUtils {
                 escrow,
                 programConfig,
                 escrowVault,
-                payerTokenAccount,
-                escrowTokenAccount,
-                tokenProgram: currency ? TOKEN_PROGRAM_ID : null,
                 systemProgram: SystemProgram.programId,
             })
             .signers([payer])